@@ -0,0 +1,174 @@
+//! Asking for command line arguments that were not given, with
+//! the `clap` feature.
+
+use std::io::{Read, Write};
+
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Command};
+
+use {Answer, Answers, Question};
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Answer with the value of the argument `id` if it was given
+    /// on the command line or in the environment, and ask the
+    /// question otherwise. A flag that was given answers
+    /// `Answer::YES`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate clap;
+    /// # extern crate question;
+    /// # use clap::{Arg, Command};
+    /// # use question::Question;
+    /// # fn main() {
+    /// let matches = Command::new("deploy")
+    ///     .arg(Arg::new("region").long("region"))
+    ///     .get_matches();
+    /// let region = Question::new("Region?").or_arg(&matches, "region");
+    /// # }
+    /// ```
+    pub fn or_arg(&mut self, matches: &ArgMatches, id: &str) -> Option<Answer> {
+        match given(matches, id) {
+            Some(value) => Some(value),
+            None => self.ask(),
+        }
+    }
+
+    /// Configure the question from `arg`: a flag is asked as a
+    /// yes/no question, possible values are the acceptable
+    /// responses and the first default value is the default.
+    pub(crate) fn like_arg(&mut self, arg: &Arg) -> &mut Question<R, W> {
+        if !arg.get_action().takes_values() {
+            return self.yes_no();
+        }
+        let possible: Vec<String> = arg
+            .get_possible_values()
+            .iter()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        if !possible.is_empty() {
+            self.acceptable(possible.iter().map(String::as_str).collect());
+            self.until_acceptable();
+        }
+        if let Some(default) = arg.get_default_values().first() {
+            self.default(Answer::RESPONSE(default.to_string_lossy().into_owned()))
+                .show_defaults();
+        }
+        self
+    }
+}
+
+/// The value of the argument `id` in `matches`, if it was given
+/// rather than defaulted.
+fn given(matches: &ArgMatches, id: &str) -> Option<Answer> {
+    match matches.value_source(id) {
+        Some(ValueSource::CommandLine) | Some(ValueSource::EnvVariable) => {}
+        _ => return None,
+    }
+    let mut values = matches.try_get_raw(id).ok()??;
+    match values.next() {
+        Some(value) if matches.try_get_one::<bool>(id).is_ok() => match value.to_str() {
+            Some("false") => Some(Answer::NO),
+            _ => Some(Answer::YES),
+        },
+        Some(value) => Some(Answer::RESPONSE(value.to_string_lossy().into_owned())),
+        None => Some(Answer::YES),
+    }
+}
+
+/// Fill in the arguments `ids` of `command` that were not given in
+/// `matches` by asking for them, returning every value by id.
+///
+/// Each argument is asked for with its help as the question, or
+/// its id if it has none, using `Question::or_arg`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate clap;
+/// # extern crate question;
+/// # use clap::{Arg, Command};
+/// # fn main() {
+/// let command = Command::new("deploy")
+///     .arg(Arg::new("region").long("region").help("Region to deploy to?"))
+///     .arg(Arg::new("env").long("env").value_parser(["staging", "production"]));
+/// let matches = command.clone().get_matches();
+/// let values = question::fill_args(&command, &matches, &["region", "env"]);
+/// # }
+/// ```
+pub fn fill_args(command: &Command, matches: &ArgMatches, ids: &[&str]) -> Answers {
+    let mut answers = Answers::new();
+    for id in ids {
+        let arg = command.get_arguments().find(|arg| arg.get_id() == *id);
+        let text = match arg.and_then(Arg::get_help) {
+            Some(help) => help.to_string(),
+            None => format!("{}?", id),
+        };
+        let mut question = Question::new(&text);
+        if let Some(arg) = arg {
+            question.like_arg(arg);
+        }
+        if let Some(answer) = question.or_arg(matches, id) {
+            answers.insert(id, answer);
+        }
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ArgAction;
+    use std::io::Cursor;
+
+    fn command() -> Command {
+        Command::new("deploy")
+            .arg(Arg::new("region").long("region"))
+            .arg(
+                Arg::new("env")
+                    .long("env")
+                    .value_parser(["staging", "production"])
+                    .default_value("staging"),
+            )
+            .arg(Arg::new("force").long("force").action(ArgAction::SetTrue))
+    }
+
+    #[test]
+    fn or_arg() {
+        macro_rules! or_arg {
+            ($args:expr, $id:expr, $i:expr, $expected:expr) => {
+                let command = command();
+                let matches = command.clone().get_matches_from($args.iter());
+                let arg = command.get_arguments().find(|a| a.get_id() == $id).unwrap();
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Value?", input, Vec::new())
+                    .like_arg(arg)
+                    .or_arg(&matches, $id);
+                assert_eq!(Some($expected), actual);
+            };
+        }
+        let response = |s: &str| Answer::RESPONSE(s.to_string());
+        or_arg!(["deploy", "--region", "eu"], "region", "", response("eu"));
+        or_arg!(["deploy"], "region", "us\n", response("us"));
+        or_arg!(["deploy"], "env", "\n", response("staging"));
+        or_arg!(
+            ["deploy"],
+            "env",
+            "dev\nproduction\n",
+            response("production")
+        );
+        or_arg!(
+            ["deploy", "--env", "production"],
+            "env",
+            "",
+            response("production")
+        );
+        or_arg!(["deploy", "--force"], "force", "", Answer::YES);
+        or_arg!(["deploy"], "force", "n\n", Answer::NO);
+    }
+}
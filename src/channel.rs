@@ -0,0 +1,141 @@
+//! Asking on a background thread, so the caller can keep working
+//! while the user answers.
+
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+use {Answer, AskError, Question};
+
+/// Withdraws questions asked with `Question::cancel_on`, such as
+/// when the work they were asked alongside finishes first.
+///
+/// Clones share the token, so one can be kept to cancel with while
+/// another is given to the question.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Create a token that has not been cancelled.
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// Withdraw the questions watching this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether `cancel` has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Withdraw the question once `token` is cancelled, failing
+    /// `try_ask` with `AskError::Cancelled`. A prompt being edited
+    /// with the `term` feature is withdrawn straight away; otherwise
+    /// the question is withdrawn before it reads another response,
+    /// and a response read after the token was cancelled is thrown
+    /// away.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{CancelToken, Question};
+    /// let token = CancelToken::new();
+    /// let mut question = Question::new("Keep waiting?");
+    /// question.yes_no().cancel_on(&token);
+    /// // Later, from another thread:
+    /// token.cancel();
+    /// ```
+    pub fn cancel_on(&mut self, token: &CancelToken) -> &mut Question<R, W> {
+        self.cancel = Some(token.clone());
+        self
+    }
+
+    /// Whether the question has been withdrawn by `cancel_on`.
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    /// Ask the question on its own thread, returning a channel the
+    /// result of `try_ask` is sent on once it is answered. The
+    /// calling thread can keep working, checking for the answer
+    /// with `try_recv`. Use `cancel_on` to withdraw the question if
+    /// the answer is no longer needed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{CancelToken, Question};
+    /// # use std::sync::mpsc::TryRecvError;
+    /// # fn download_step() -> bool { true }
+    /// let token = CancelToken::new();
+    /// let mut question = Question::new("Download finished early. Keep waiting?");
+    /// question.yes_no().cancel_on(&token);
+    /// let answer = question.ask_channel();
+    /// loop {
+    ///     if download_step() {
+    ///         token.cancel();
+    ///         break;
+    ///     }
+    ///     match answer.try_recv() {
+    ///         Err(TryRecvError::Empty) => continue,
+    ///         result => {
+    ///             println!("{:?}", result);
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn ask_channel(mut self) -> Receiver<Result<Option<Answer>, AskError>> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // The receiver may have been dropped once the answer
+            // was no longer wanted.
+            let _ = sender.send(self.try_ask());
+        });
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn ask_channel() {
+        let input = Cursor::new(String::from("y\n").into_bytes());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no();
+        let answer = q.ask_channel().recv().unwrap();
+        assert_eq!(Ok(Some(Answer::YES)), answer);
+    }
+
+    #[test]
+    fn cancel_on() {
+        let token = CancelToken::new();
+        let input = Cursor::new(String::from("y\n").into_bytes());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no().cancel_on(&token);
+        token.cancel();
+        assert!(token.clone().is_cancelled());
+        let answer = q.ask_channel().recv().unwrap();
+        assert_eq!(Err(AskError::Cancelled), answer);
+    }
+}
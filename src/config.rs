@@ -0,0 +1,318 @@
+//! Defaults shared by every question an application asks.
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(feature = "i18n")]
+use locale::Locale;
+#[cfg(feature = "color")]
+use theme::Theme;
+use {
+    term, Answer, Backend, Context, Flush, Hook, Layer, Next, PromptFormatter, Prompter, Question,
+};
+
+static INSTALLED: Mutex<Option<QuestionConfig>> = Mutex::new(None);
+
+/// What to do when standard input is not an interactive terminal,
+/// such as when a CLI is run by a script or in CI.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NonInteractive {
+    /// Ask anyway, reading responses from the input.
+    Ask,
+    /// Answer questions that have a default with it, without
+    /// asking. Questions without one are asked.
+    UseDefaults,
+    /// Make every question `strict`, so input that does not answer
+    /// it fails rather than being asked for again.
+    Strict,
+}
+
+/// Settings applied to every `Question` created with
+/// `Question::new` once installed, so a large CLI can configure
+/// its prompts in one place. Anything set on a question itself
+/// overrides them.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{NonInteractive, Question, QuestionConfig};
+/// QuestionConfig::new()
+///     .tries(3)
+///     .show_defaults()
+///     .non_interactive(NonInteractive::UseDefaults)
+///     .install();
+///
+/// // Asked up to three times, showing its default.
+/// Question::new("Continue?").yes_no().confirm();
+/// ```
+#[derive(Clone, Default)]
+pub struct QuestionConfig {
+    tries: Option<u64>,
+    clarification: Option<String>,
+    show_defaults: bool,
+    show_remaining_tries: bool,
+    retry_delay: Option<Duration>,
+    flush: Option<Flush>,
+    wrap: Option<usize>,
+    formatter: Option<Arc<dyn PromptFormatter + Send + Sync>>,
+    #[cfg(feature = "color")]
+    theme: Option<Theme>,
+    #[cfg(feature = "i18n")]
+    locale: Option<Locale>,
+    prompter: Option<Backend>,
+    layers: Vec<Arc<dyn Layer + Send + Sync>>,
+    non_interactive: Option<NonInteractive>,
+    around_prompt: Option<(Hook, Hook)>,
+}
+
+impl QuestionConfig {
+    /// Create a configuration changing nothing.
+    pub fn new() -> QuestionConfig {
+        QuestionConfig::default()
+    }
+
+    /// Apply this configuration to questions created from now on,
+    /// replacing any installed before.
+    pub fn install(&self) {
+        *INSTALLED.lock().unwrap_or_else(|e| e.into_inner()) = Some(self.clone());
+    }
+
+    /// Stop applying the installed configuration, if any.
+    pub fn uninstall() {
+        *INSTALLED.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// The configuration installed, if any.
+    pub fn installed() -> Option<QuestionConfig> {
+        INSTALLED.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// See `Question::tries`.
+    pub fn tries(&mut self, tries: u64) -> &mut QuestionConfig {
+        self.tries = Some(tries);
+        self
+    }
+
+    /// See `Question::clarification`.
+    pub fn clarification<S: Into<String>>(&mut self, clarification: S) -> &mut QuestionConfig {
+        self.clarification = Some(clarification.into());
+        self
+    }
+
+    /// See `Question::show_defaults`.
+    pub fn show_defaults(&mut self) -> &mut QuestionConfig {
+        self.show_defaults = true;
+        self
+    }
+
+    /// See `Question::show_remaining_tries`.
+    pub fn show_remaining_tries(&mut self) -> &mut QuestionConfig {
+        self.show_remaining_tries = true;
+        self
+    }
+
+    /// See `Question::retry_delay`.
+    pub fn retry_delay(&mut self, delay: Duration) -> &mut QuestionConfig {
+        self.retry_delay = Some(delay);
+        self
+    }
+
+    /// See `Question::flush`.
+    pub fn flush(&mut self, flush: Flush) -> &mut QuestionConfig {
+        self.flush = Some(flush);
+        self
+    }
+
+    /// See `Question::wrap`.
+    pub fn wrap(&mut self, width: usize) -> &mut QuestionConfig {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// See `Question::formatter`.
+    pub fn formatter<F>(&mut self, formatter: F) -> &mut QuestionConfig
+    where
+        F: PromptFormatter + Send + Sync + 'static,
+    {
+        self.formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    /// See `Question::theme`.
+    #[cfg(feature = "color")]
+    pub fn theme(&mut self, theme: Theme) -> &mut QuestionConfig {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// See `Question::locale`.
+    #[cfg(feature = "i18n")]
+    pub fn locale(&mut self, locale: Locale) -> &mut QuestionConfig {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// See `Question::prompter`. The prompter is shared by every
+    /// question.
+    pub fn prompter<P>(&mut self, prompter: P) -> &mut QuestionConfig
+    where
+        P: Prompter + Send + 'static,
+    {
+        self.prompter = Some(Arc::new(Mutex::new(prompter)));
+        self
+    }
+
+    /// See `Question::layer`. Layers set here are outside those
+    /// added to each question.
+    pub fn layer<L>(&mut self, layer: L) -> &mut QuestionConfig
+    where
+        L: Layer + Send + Sync + 'static,
+    {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// See `Question::around_prompt`.
+    pub fn around_prompt<B, A>(&mut self, before: B, after: A) -> &mut QuestionConfig
+    where
+        B: Fn() + Send + Sync + 'static,
+        A: Fn() + Send + Sync + 'static,
+    {
+        self.around_prompt = Some((Arc::new(before), Arc::new(after)));
+        self
+    }
+
+    /// What to do when standard input is not an interactive
+    /// terminal. Questions are asked anyway by default.
+    pub fn non_interactive(&mut self, policy: NonInteractive) -> &mut QuestionConfig {
+        self.non_interactive = Some(policy);
+        self
+    }
+
+    /// Apply the settings to `question`, treating its input as
+    /// interactive or not.
+    pub(crate) fn apply<R, W>(&self, question: &mut Question<R, W>, interactive: bool)
+    where
+        R: Read,
+        W: Write,
+    {
+        if let Some(tries) = self.tries {
+            question.tries(tries);
+        }
+        if self.clarification.is_some() {
+            question.clarification = self.clarification.clone();
+        }
+        question.show_defaults |= self.show_defaults;
+        question.show_remaining_tries |= self.show_remaining_tries;
+        if self.retry_delay.is_some() {
+            question.retry_delay = self.retry_delay;
+        }
+        if let Some(flush) = self.flush {
+            question.flush = flush;
+        }
+        if self.wrap.is_some() {
+            question.wrap = self.wrap;
+        }
+        if let Some(ref formatter) = self.formatter {
+            question.formatter = formatter.clone();
+        }
+        #[cfg(feature = "color")]
+        {
+            if self.theme.is_some() {
+                question.theme = self.theme;
+            }
+        }
+        #[cfg(feature = "i18n")]
+        {
+            if self.locale.is_some() {
+                question.locale = self.locale.clone();
+            }
+        }
+        if let Some(ref prompter) = self.prompter {
+            question.prompter = Some(prompter.clone());
+            question.keys = false;
+        }
+        if let Some((ref before, ref after)) = self.around_prompt {
+            question.before_prompt = Some(before.clone());
+            question.after_prompt = Some(after.clone());
+        }
+        question.layers = self.layers.clone();
+        match self.non_interactive {
+            _ if interactive => {}
+            Some(NonInteractive::UseDefaults) => question.layers.push(Arc::new(UseDefaults)),
+            Some(NonInteractive::Strict) => {
+                question.strict();
+            }
+            Some(NonInteractive::Ask) | None => {}
+        }
+    }
+}
+
+/// Apply the installed configuration, if any, to a new question.
+pub(crate) fn configure<R, W>(question: &mut Question<R, W>)
+where
+    R: Read,
+    W: Write,
+{
+    if let Some(config) = QuestionConfig::installed() {
+        config.apply(question, term::is_interactive());
+    }
+}
+
+/// Answers questions that have a default with it.
+struct UseDefaults;
+
+impl Layer for UseDefaults {
+    fn ask(&self, context: &Context, next: Next) -> Option<Answer> {
+        match context.take_default() {
+            Some(default) => Some(default),
+            None => next.ask(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use {AskError, AssumeYes};
+
+    #[test]
+    fn config() {
+        let mut config = QuestionConfig::new();
+        config
+            .tries(2)
+            .clarification("Please answer yes or no")
+            .show_defaults()
+            .non_interactive(NonInteractive::UseDefaults);
+
+        let input = Cursor::new(b"maybe\nperhaps\ny\n".to_vec());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Continue?", input, &mut output);
+            config.apply(&mut q, true);
+            q.yes_no().default(Answer::NO);
+            assert_eq!(None, q.ask());
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Continue? (y/N) Please answer yes or no\nContinue? (y/N) ",
+            output
+        );
+
+        let mut q = Question::with_cursor("Continue?", Cursor::new(Vec::new()), Vec::new());
+        config.apply(&mut q, false);
+        q.yes_no().default(Answer::NO);
+        assert_eq!(Some(Answer::NO), q.ask());
+
+        let mut q = Question::with_cursor("Name?", Cursor::new(Vec::new()), Vec::new());
+        config
+            .non_interactive(NonInteractive::Strict)
+            .layer(AssumeYes);
+        config.apply(&mut q, false);
+        assert_eq!(Err(AskError::EndOfInput), q.accept("Jo").try_ask());
+        assert_eq!(1, q.layers.len());
+    }
+}
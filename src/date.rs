@@ -0,0 +1,101 @@
+use std::io::{Read, Write};
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+use Question;
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for a date written in `format`, using chrono's
+    /// `strftime` syntax such as `"%Y-%m-%d"`.
+    ///
+    /// Responses that are not a valid date in the format are
+    /// rejected with the format shown, and the question is asked
+    /// again as set by `tries` or `until_acceptable`. Requires the
+    /// `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let start = Question::new("Start date? (YYYY-MM-DD)")
+    ///     .until_acceptable()
+    ///     .date("%Y-%m-%d");
+    /// ```
+    pub fn date(&mut self, format: &str) -> Option<NaiveDate> {
+        self.ask_parsed(|response| {
+            NaiveDate::parse_from_str(response, format).map_err(|e| {
+                format!(
+                    "'{}' is not a date in the format {}: {}",
+                    response, format, e
+                )
+            })
+        })
+    }
+
+    /// Ask for a date and time in RFC 3339 form, such as
+    /// `2024-01-31T09:30:00Z`, keeping the offset it was given
+    /// with. Requires the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let at = Question::new("Run at?")
+    ///     .until_acceptable()
+    ///     .datetime_rfc3339();
+    /// ```
+    pub fn datetime_rfc3339(&mut self) -> Option<DateTime<FixedOffset>> {
+        self.ask_parsed(|response| {
+            DateTime::parse_from_rfc3339(response).map_err(|e| {
+                format!(
+                    "'{}' is not an RFC 3339 date and time, like 2024-01-31T09:30:00Z: {}",
+                    response, e
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn date() {
+        macro_rules! date {
+            ($i:expr, $format:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Date?", input, Vec::new())
+                    .tries(2)
+                    .date($format);
+                assert_eq!($expected, actual);
+            };
+        }
+        let day = NaiveDate::from_ymd_opt(2024, 2, 29);
+        date!("2024-02-29\n", "%Y-%m-%d", day);
+        date!("2023-02-29\n2024-02-29\n", "%Y-%m-%d", day);
+        date!("29/02/2024\n", "%d/%m/%Y", day);
+        date!("tomorrow\nsoon\n", "%Y-%m-%d", None);
+    }
+
+    #[test]
+    fn datetime_rfc3339() {
+        let input = Cursor::new(String::from("9:30\n2024-01-31T09:30:00+02:00\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("When?", input, &mut output)
+                .until_acceptable()
+                .datetime_rfc3339();
+            let expected = DateTime::parse_from_rfc3339("2024-01-31T07:30:00Z").unwrap();
+            assert_eq!(Some(expected), actual);
+            assert_eq!(2 * 3600, actual.unwrap().offset().local_minus_utc());
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert!(output.starts_with("When? '9:30' is not an RFC 3339 date and time"));
+    }
+}
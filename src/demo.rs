@@ -0,0 +1,193 @@
+//! Running a form with answers typed automatically, to record
+//! demos and try out wizards without anyone at the keyboard.
+
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+use form::is_enabled;
+use {Answers, Form};
+
+/// A script of answers typed into a `Form` by `Form::demo`.
+///
+/// Each answer is typed one keystroke at a time after a short
+/// pause, as a person would, and echoed to the output so the
+/// whole interaction can be recorded with a tool such as
+/// asciinema. Answers are used in order, one per line asked for,
+/// so a rejected answer is followed by the next one.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Demo, Form, Question};
+/// let mut confirm = Question::new("Continue?");
+/// confirm.yes_no().until_acceptable();
+///
+/// let mut form = Form::new();
+/// form.question("name", Question::new("What is your name?"))
+///     .question("continue", confirm);
+/// form.demo(&Demo::new(&["Arthur", "maybe", "yes"]), &mut std::io::stdout());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Demo {
+    answers: Vec<String>,
+    keystroke: Duration,
+    pause: Duration,
+}
+
+impl Demo {
+    /// Create a `Demo` typing `answers`, with delays that read
+    /// naturally.
+    pub fn new(answers: &[&str]) -> Demo {
+        Demo {
+            answers: answers.iter().map(|a| a.to_string()).collect(),
+            keystroke: Duration::from_millis(80),
+            pause: Duration::from_millis(700),
+        }
+    }
+
+    /// Set the delay between keystrokes.
+    pub fn keystroke_delay(&mut self, delay: Duration) -> &mut Demo {
+        self.keystroke = delay;
+        self
+    }
+
+    /// Set the pause before typing each answer.
+    pub fn pause(&mut self, pause: Duration) -> &mut Demo {
+        self.pause = pause;
+        self
+    }
+}
+
+impl<R, W> Form<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Run the form as a demo, typing the answers scripted by
+    /// `demo` and writing the whole interaction to `output`.
+    /// Returns the answers as `ask` would.
+    pub fn demo<O: Write>(&self, demo: &Demo, output: &mut O) -> Answers {
+        let typist = Rc::new(RefCell::new(Typist {
+            demo,
+            output,
+            answer: 0,
+            typed: Vec::new(),
+        }));
+        let mut answers = Answers::new();
+        for (id, question) in &self.questions {
+            let mut question = question.rebind(Keyboard(typist.clone()), Screen(typist.clone()));
+            question.keys = false;
+            let answer = if is_enabled(&self.tags, self.all_tags, &question) {
+                question.ask()
+            } else {
+                question.resolve_default();
+                question.default.clone()
+            };
+            if let Some(answer) = answer {
+                answers.insert(id, answer);
+            }
+        }
+        answers
+    }
+}
+
+/// Types out the answers of a `Demo`, sharing the output with
+/// the questions being answered.
+struct Typist<'a, O: Write + 'a> {
+    demo: &'a Demo,
+    output: &'a mut O,
+    answer: usize,
+    typed: Vec<u8>,
+}
+
+impl<'a, O: Write> Typist<'a, O> {
+    /// Type the next keystroke, echoing it to the output.
+    fn type_byte(&mut self) -> io::Result<Option<u8>> {
+        if self.typed.is_empty() {
+            match self.demo.answers.get(self.answer) {
+                Some(answer) => {
+                    self.answer += 1;
+                    self.typed = format!("{}\n", answer).into_bytes();
+                    self.typed.reverse();
+                    thread::sleep(self.demo.pause);
+                }
+                None => return Ok(None),
+            }
+        }
+        thread::sleep(self.demo.keystroke);
+        let byte = self.typed.pop();
+        if let Some(byte) = byte {
+            self.output.write_all(&[byte])?;
+            self.output.flush()?;
+        }
+        Ok(byte)
+    }
+}
+
+/// The input half of a `Typist`.
+struct Keyboard<'a, O: Write + 'a>(Rc<RefCell<Typist<'a, O>>>);
+
+impl<'a, O: Write> Read for Keyboard<'a, O> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.0.borrow_mut().type_byte()? {
+            Some(byte) => {
+                buf[0] = byte;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+/// The output half of a `Typist`.
+struct Screen<'a, O: Write + 'a>(Rc<RefCell<Typist<'a, O>>>);
+
+impl<'a, O: Write> Write for Screen<'a, O> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().output.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use {Answer, Question};
+
+    #[test]
+    fn demo() {
+        let mut form = Form::new();
+        let name = Question::with_cursor("Name?", Cursor::new(Vec::new()), Vec::new());
+        form.question("name", name);
+        let mut q = Question::with_cursor("Continue?", Cursor::new(Vec::new()), Vec::new());
+        q.yes_no().until_acceptable().clarification("Yes or no");
+        form.question("continue", q);
+
+        let mut demo = Demo::new(&["Arthur", "maybe", "y"]);
+        demo.keystroke_delay(Duration::from_millis(0))
+            .pause(Duration::from_millis(0));
+        let mut output = Vec::new();
+        let answers = form.demo(&demo, &mut output);
+
+        assert_eq!(
+            Some(&Answer::RESPONSE(String::from("Arthur"))),
+            answers.get("name")
+        );
+        assert_eq!(Some(&Answer::YES), answers.get("continue"));
+        let output = String::from_utf8(output).expect("Not UTF-8");
+        assert_eq!(
+            "Name? Arthur\nContinue? maybe\nYes or no\nContinue? y\n",
+            output
+        );
+    }
+}
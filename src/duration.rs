@@ -0,0 +1,166 @@
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use locale::Texts;
+use Question;
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for a length of time such as "90s", "5m", "2h30m" or
+    /// "1d", returning it as a `Duration`.
+    ///
+    /// The units are `ms`, `s`, `m`, `h`, `d` and `w`, and several
+    /// may be combined, largest first. Anything else is rejected
+    /// and the question asked again as set by `tries` or
+    /// `until_acceptable`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let ttl = Question::new("Cache TTL?")
+    ///     .until_acceptable()
+    ///     .duration();
+    /// ```
+    pub fn duration(&mut self) -> Option<Duration> {
+        let texts = self.texts();
+        self.ask_parsed(move |response| parse_duration(response, &texts))
+    }
+
+    /// Ask for a length of time like `duration`, only accepting
+    /// one from `min` to `max` inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// use std::time::Duration;
+    ///
+    /// let timeout = Question::new("Timeout?")
+    ///     .until_acceptable()
+    ///     .duration_between(Duration::from_secs(1), Duration::from_secs(3600));
+    /// ```
+    pub fn duration_between(&mut self, min: Duration, max: Duration) -> Option<Duration> {
+        let texts = self.texts();
+        self.ask_parsed(move |response| {
+            let duration = parse_duration(response, &texts)?;
+            if duration < min || duration > max {
+                let (min, max) = (format_duration(min), format_duration(max));
+                let args = [("min", min.as_str()), ("max", max.as_str())];
+                return Err(texts.text("duration-between", &args));
+            }
+            Ok(duration)
+        })
+    }
+}
+
+const UNITS: &[(&str, u64)] = &[
+    ("w", 7 * 24 * 3600 * 1000),
+    ("d", 24 * 3600 * 1000),
+    ("h", 3600 * 1000),
+    ("m", 60 * 1000),
+    ("s", 1000),
+    ("ms", 1),
+];
+
+/// Parse a duration such as "2h30m", where each number is
+/// followed by a unit and the units go from largest to smallest.
+fn parse_duration(response: &str, texts: &Texts) -> Result<Duration, String> {
+    let invalid = || texts.text("not-duration", &[("response", response)]);
+    let mut rest = response.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    let mut millis: u64 = 0;
+    let mut smallest = u64::MAX;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let number: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        let scale = match UNITS.iter().find(|&&(name, _)| name == unit) {
+            Some(&(_, scale)) if scale < smallest => scale,
+            _ => return Err(invalid()),
+        };
+        smallest = scale;
+        millis = number
+            .checked_mul(scale)
+            .and_then(|n| millis.checked_add(n))
+            .ok_or_else(invalid)?;
+        rest = rest[unit_len..].trim_start();
+    }
+    Ok(Duration::from_millis(millis))
+}
+
+/// Format a duration the way it would be typed, such as "2h30m".
+fn format_duration(duration: Duration) -> String {
+    let mut millis = duration.as_millis().min(u128::from(u64::MAX)) as u64;
+    if millis == 0 {
+        return String::from("0s");
+    }
+    let mut text = String::new();
+    for &(name, scale) in UNITS {
+        if millis >= scale {
+            text += &format!("{}{}", millis / scale, name);
+            millis %= scale;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse() {
+        macro_rules! parse {
+            ($i:expr, $expected:expr) => {
+                assert_eq!($expected, parse_duration($i, &Texts::default()).ok());
+            };
+        }
+        parse!("90s", Some(Duration::from_secs(90)));
+        parse!("5m", Some(Duration::from_secs(300)));
+        parse!("2h30m", Some(Duration::from_secs(9000)));
+        parse!("1d 2h", Some(Duration::from_secs(93600)));
+        parse!("1w", Some(Duration::from_secs(604800)));
+        parse!("250ms", Some(Duration::from_millis(250)));
+        parse!("90", None);
+        parse!("5m2h", None);
+        parse!("5x", None);
+        parse!("m", None);
+        parse!("", None);
+        parse!("99999999999999999w", None);
+        assert_eq!("2h30m", format_duration(Duration::from_secs(9000)));
+        assert_eq!(
+            "1d1s500ms",
+            format_duration(Duration::from_millis(86401500))
+        );
+    }
+
+    #[test]
+    fn duration_between() {
+        let input = Cursor::new(String::from("2h\n30m\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Timeout?", input, &mut output)
+                .until_acceptable()
+                .duration_between(Duration::from_secs(1), Duration::from_secs(3600));
+            assert_eq!(Some(Duration::from_secs(1800)), actual);
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!("Timeout? Must be between 1s and 1h\nTimeout? ", output);
+    }
+}
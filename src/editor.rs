@@ -0,0 +1,435 @@
+//! A small line editor used by interactive terminals, so that
+//! input can be pre-filled and edited in place.
+
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use secret;
+use term::{self, Key, RawMode};
+use unicode::char_width;
+use {AskError, Question};
+
+/// How often a question watching a `CancelToken` checks it while
+/// waiting for a key.
+const CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// The text being edited and the position of the cursor in it.
+pub(crate) struct LineEditor {
+    buffer: Vec<char>,
+    cursor: usize,
+    suggestion: String,
+    hidden: bool,
+}
+
+impl Drop for LineEditor {
+    fn drop(&mut self) {
+        if self.hidden {
+            secret::wipe_chars(&mut self.buffer);
+        }
+    }
+}
+
+/// What should happen after a key has been handled.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Edit {
+    Continue,
+    Done,
+    Interrupt,
+    Eof,
+}
+
+impl LineEditor {
+    pub(crate) fn new(initial: &str) -> LineEditor {
+        let buffer: Vec<char> = initial.chars().collect();
+        LineEditor {
+            cursor: buffer.len(),
+            buffer,
+            suggestion: String::new(),
+            hidden: false,
+        }
+    }
+
+    pub(crate) fn text(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    /// Stop showing the text, for secrets.
+    pub(crate) fn hide(&mut self) {
+        self.hidden = true;
+    }
+
+    /// Replace the text, moving the cursor to its end.
+    pub(crate) fn set_text(&mut self, text: &str) {
+        self.buffer = text.chars().collect();
+        self.cursor = self.buffer.len();
+    }
+
+    pub(crate) fn handle(&mut self, key: Key) -> Edit {
+        match key {
+            Key::Char(c) => {
+                self.buffer.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.buffer.remove(self.cursor);
+            }
+            Key::Delete if self.cursor < self.buffer.len() => {
+                self.buffer.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.buffer.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.buffer.len(),
+            Key::Enter => return Edit::Done,
+            Key::Interrupt => return Edit::Interrupt,
+            Key::Eof if self.buffer.is_empty() => return Edit::Eof,
+            _ => {}
+        }
+        Edit::Continue
+    }
+
+    /// Complete the text to the only one of `candidates` it starts,
+    /// or as far as all of those it starts agree.
+    pub(crate) fn complete(&mut self, candidates: &[String]) {
+        let text = self.text();
+        let mut matching = candidates.iter().filter(|c| c.starts_with(&text));
+        let mut common: Vec<char> = match matching.next() {
+            Some(first) => first.chars().collect(),
+            None => return,
+        };
+        for candidate in matching {
+            let same = common
+                .iter()
+                .zip(candidate.chars())
+                .take_while(|&(a, b)| *a == b)
+                .count();
+            common.truncate(same);
+        }
+        if common.len() > self.buffer.len() {
+            self.buffer = common;
+        }
+        self.cursor = self.buffer.len();
+    }
+
+    /// Suggest the rest of the first of `candidates` the text
+    /// starts, shown after the text while the cursor is at its end.
+    pub(crate) fn suggest(&mut self, candidates: &[String]) {
+        let text = self.text();
+        self.suggestion.clear();
+        if text.is_empty() || self.cursor < self.buffer.len() {
+            return;
+        }
+        if let Some(candidate) = candidates.iter().find(|c| c.starts_with(&text)) {
+            self.suggestion.push_str(&candidate[text.len()..]);
+        }
+    }
+
+    /// Redraw the current line, `prompt` followed by the text and
+    /// any suggestion, and leave the terminal cursor at the editing
+    /// position.
+    pub(crate) fn render(&self, prompt: &str) -> String {
+        if self.hidden {
+            return format!("\r\x1b[2K{}", prompt);
+        }
+        let mut frame = format!("\r\x1b[2K{}{}", prompt, self.text());
+        if !self.suggestion.is_empty() {
+            frame += &format!("\x1b[2m{}\x1b[0m", self.suggestion);
+        }
+        frame += "\r";
+        let before: usize = self.buffer[..self.cursor]
+            .iter()
+            .map(|&c| char_width(c))
+            .sum();
+        let column = display_width(prompt) + before;
+        if column > 0 {
+            frame += &format!("\x1b[{}C", column);
+        }
+        frame
+    }
+}
+
+/// The number of columns `text` takes up, ignoring any escape
+/// sequences styling it.
+pub(crate) fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('\x40'..'\x7f').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            width += char_width(c);
+        }
+    }
+    width
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Whether lines are read through the line editor rather than
+    /// as they are typed.
+    pub(crate) fn uses_editor(&self) -> bool {
+        self.keys
+            && (self.prefill
+                || self.allow_chars.is_some()
+                || self.completions.is_some()
+                || self.history.is_some()
+                || self.secret)
+    }
+
+    /// Read a line with the editor, starting with `initial` as the
+    /// text. Returns `None` if input ends or is interrupted.
+    pub(crate) fn edit_line(
+        &mut self,
+        prompt: &str,
+        initial: &str,
+    ) -> Result<Option<String>, std::io::Error> {
+        self.edit_line_until(prompt, initial, None)
+    }
+
+    /// Read a line with the editor like `edit_line`, counting down
+    /// the seconds to `deadline`, if any, until a key is pressed.
+    /// The seconds left replace "{seconds}" in the prompt, or are
+    /// shown before it. If the deadline passes first the line is
+    /// returned as it is.
+    pub(crate) fn edit_line_until(
+        &mut self,
+        prompt: &str,
+        initial: &str,
+        mut deadline: Option<Instant>,
+    ) -> Result<Option<String>, std::io::Error> {
+        let (head, tail) = match prompt.rfind('\n') {
+            Some(i) => prompt.split_at(i + 1),
+            None => ("", prompt),
+        };
+        let seconds = deadline.map(seconds_until);
+        match seconds {
+            Some(seconds) => self.show_text(&head.replace("{seconds}", &seconds.to_string()))?,
+            None => self.show_text(head)?,
+        }
+        let raw = RawMode::enable()?;
+        let mut editor = LineEditor::new(initial);
+        if self.secret {
+            editor.hide();
+        }
+        let mut line = countdown(tail, seconds);
+        let history = self.history_entries();
+        let mut recalled = history.len();
+        let mut draft = String::new();
+        loop {
+            let candidates = match self.completions {
+                Some(ref completions) if !self.secret => completions(&editor.text()),
+                _ => Vec::new(),
+            };
+            editor.suggest(&candidates);
+            let frame = editor.render(&line);
+            self.show(&frame)?;
+            if let Some(until) = deadline {
+                // Read the clock once, so the seconds shown and the
+                // time to wait for the next one agree.
+                let left = until.saturating_duration_since(Instant::now());
+                let seconds = whole_seconds(left);
+                if seconds == 0 {
+                    self.show_text("\n")?;
+                    let line = editor.text();
+                    if line.is_empty() && self.default.is_none() {
+                        self.fail(AskError::TimedOut);
+                        return Ok(None);
+                    }
+                    return Ok(Some(line));
+                }
+                let tick = left.saturating_sub(Duration::from_secs(seconds - 1));
+                if !term::poll_input(tick)? {
+                    line = countdown(tail, Some(seconds - 1));
+                    continue;
+                }
+                deadline = None;
+            }
+            if self.cancel.is_some() {
+                while !term::poll_input(CANCEL_POLL)? {
+                    if self.cancelled() {
+                        self.show_text("\n")?;
+                        return Ok(None);
+                    }
+                }
+            }
+            // The end of input ends the line, whatever is in it.
+            let key = match term::read_key(&mut self.reader)? {
+                Some(key) => key,
+                None => {
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+            };
+            if let (Key::Char(c), Some(allowed)) = (key, self.allow_chars.as_ref()) {
+                if !allowed(c) {
+                    continue;
+                }
+            }
+            if key == Key::Tab {
+                editor.complete(&candidates);
+                continue;
+            }
+            if (key == Key::Up && recalled > 0) || (key == Key::Down && recalled < history.len()) {
+                if recalled == history.len() {
+                    draft = editor.text();
+                }
+                recalled = if key == Key::Up {
+                    recalled - 1
+                } else {
+                    recalled + 1
+                };
+                editor.set_text(history.get(recalled).unwrap_or(&draft));
+                continue;
+            }
+            match editor.handle(key) {
+                Edit::Continue => {}
+                Edit::Done => {
+                    self.show_text("\n")?;
+                    return Ok(Some(editor.text()));
+                }
+                Edit::Interrupt => {
+                    self.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
+                Edit::Eof => {
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// The whole seconds left until `deadline`, rounded up.
+fn seconds_until(deadline: Instant) -> u64 {
+    whole_seconds(deadline.saturating_duration_since(Instant::now()))
+}
+
+/// `left` in seconds, rounded up.
+fn whole_seconds(left: Duration) -> u64 {
+    left.as_secs() + u64::from(left.subsec_nanos() > 0)
+}
+
+/// Show `seconds` in `prompt`, in place of "{seconds}" or before it.
+pub(crate) fn countdown(prompt: &str, seconds: Option<u64>) -> String {
+    match seconds {
+        Some(seconds) if prompt.contains("{seconds}") => {
+            prompt.replace("{seconds}", &seconds.to_string())
+        }
+        Some(seconds) => format!("[{}s] {}", seconds, prompt),
+        None => prompt.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit() {
+        macro_rules! edit {
+            ($initial:expr, $keys:expr, $expected:expr, $edit:expr) => {
+                let mut editor = LineEditor::new($initial);
+                let mut last = Edit::Continue;
+                for key in $keys.iter() {
+                    last = editor.handle(*key);
+                }
+                assert_eq!($expected, editor.text());
+                assert_eq!($edit, last);
+            };
+        }
+        edit!("my-app", [Key::Enter], "my-app", Edit::Done);
+        edit!(
+            "my-app",
+            [Key::Backspace, Key::Backspace, Key::Char('x')],
+            "my-ax",
+            Edit::Continue
+        );
+        edit!(
+            "app",
+            [Key::Home, Key::Char('m'), Key::Char('y')],
+            "myapp",
+            Edit::Continue
+        );
+        edit!(
+            "app",
+            [Key::Left, Key::Left, Key::Delete],
+            "ap",
+            Edit::Continue
+        );
+        edit!("", [Key::Eof], "", Edit::Eof);
+        edit!("a", [Key::Eof, Key::Interrupt], "a", Edit::Interrupt);
+    }
+
+    #[test]
+    fn render() {
+        let mut editor = LineEditor::new("app");
+        assert_eq!("\r\x1b[2KName? app\r\x1b[9C", editor.render("Name? "));
+        editor.handle(Key::Home);
+        assert_eq!("\r\x1b[2Kapp\r", editor.render(""));
+        assert_eq!(4, display_width("\x1b[1mName\x1b[0m"));
+        assert_eq!(5, display_width("名前?"));
+        assert_eq!("[9s] Reboot? ", countdown("Reboot? ", Some(9)));
+        assert_eq!(
+            "Reboot in 9s? ",
+            countdown("Reboot in {seconds}s? ", Some(9))
+        );
+    }
+
+    #[test]
+    fn complete() {
+        macro_rules! complete {
+            ($text:expr, $expected:expr) => {
+                let candidates: Vec<String> = ["main", "master", "feature/login"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                let mut editor = LineEditor::new($text);
+                editor.complete(&candidates);
+                assert_eq!($expected, editor.text());
+            };
+        }
+        complete!("f", "feature/login");
+        complete!("m", "ma");
+        complete!("mas", "master");
+        complete!("x", "x");
+        complete!("main", "main");
+
+        let candidates = vec![String::from("master")];
+        let mut editor = LineEditor::new("ma");
+        editor.suggest(&candidates);
+        assert_eq!(
+            "\r\x1b[2K> ma\x1b[2mster\x1b[0m\r\x1b[4C",
+            editor.render("> ")
+        );
+        editor.handle(Key::Left);
+        editor.suggest(&candidates);
+        assert_eq!("\r\x1b[2K> ma\r\x1b[3C", editor.render("> "));
+        editor.hide();
+        assert_eq!("\r\x1b[2K> ", editor.render("> "));
+    }
+
+    #[test]
+    fn countdown_seconds() {
+        assert_eq!(0, whole_seconds(Duration::ZERO));
+        assert_eq!(1, whole_seconds(Duration::from_nanos(1)));
+        assert_eq!(3, whole_seconds(Duration::from_millis(2001)));
+        assert_eq!(2, whole_seconds(Duration::from_secs(2)));
+        assert_eq!("[3s] Deploy? ", countdown("Deploy? ", Some(3)));
+        assert_eq!(
+            "Deploy in 3s? ",
+            countdown("Deploy in {seconds}s? ", Some(3))
+        );
+    }
+}
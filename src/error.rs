@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why a question could not be answered.
+///
+/// Returned by `Question::try_ask` and `Form::try_ask`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AskError {
+    /// In strict mode, the line read was not an acceptable
+    /// response. Strict questions never read another line to
+    /// ask again.
+    Rejected(String),
+    /// In strict mode, the input ended before the question was
+    /// answered.
+    EndOfInput,
+    /// In a strict `Form`, input remained after the last question
+    /// was answered.
+    SurplusInput(String),
+    /// In strict mode, the line read was not valid UTF-8. Other
+    /// questions show why and read another line instead.
+    InvalidEncoding,
+    /// In a dry run, the question had no default to take.
+    NoDefault,
+    /// The answers given could not be made into the value asked
+    /// for, such as by `Question::ask_for`.
+    Invalid(String),
+    /// The question was withdrawn by the token given to
+    /// `Question::cancel_on`.
+    Cancelled,
+    /// The time budget of a `Form` ran out before the question was
+    /// answered, and it had no default to take.
+    TimedOut,
+    /// Input that is not an interactive terminal, such as a pipe,
+    /// gave the same unacceptable response again and again, so
+    /// asking again would never get an answer.
+    NoInteractiveInput,
+}
+
+impl fmt::Display for AskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AskError::Rejected(ref line) => write!(f, "'{}' is not an acceptable response", line),
+            AskError::EndOfInput => write!(f, "input ended before the question was answered"),
+            AskError::SurplusInput(ref line) => {
+                write!(f, "input remained after the last question: '{}'", line)
+            }
+            AskError::InvalidEncoding => write!(f, "the response was not valid UTF-8"),
+            AskError::NoDefault => write!(f, "the question has no default to take"),
+            AskError::Invalid(ref reason) => write!(f, "{}", reason),
+            AskError::Cancelled => write!(f, "the question was withdrawn"),
+            AskError::TimedOut => write!(f, "time ran out before the question was answered"),
+            AskError::NoInteractiveInput => {
+                write!(f, "the input kept giving the same unacceptable response")
+            }
+        }
+    }
+}
+
+impl Error for AskError {}
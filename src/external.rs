@@ -0,0 +1,186 @@
+//! Answering a question in the user's text editor.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use Question;
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Answer the question by editing text in the user's editor,
+    /// `$VISUAL` or `$EDITOR`, like `git commit` does, for answers
+    /// longer than a line such as descriptions or YAML snippets.
+    /// The editor starts with the text set by `editor_template`,
+    /// if any, and the saved text is the answer, without trailing
+    /// whitespace. Saving an empty file gives the default. The
+    /// text is checked like a typed response, and the editor is
+    /// opened again if it is not acceptable and the question asks
+    /// again.
+    ///
+    /// On a terminal that cannot be driven interactively (see
+    /// `toggle`) a single line is read instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Describe the change:")
+    ///     .edit_in_editor()
+    ///     .editor_template("Summary\n\nDetails\n")
+    ///     .ask();
+    /// ```
+    pub fn edit_in_editor(&mut self) -> &mut Question<R, W> {
+        self.external_editor = true;
+        self
+    }
+
+    /// Set the text the editor starts with for `edit_in_editor`.
+    pub fn editor_template<S: Into<String>>(&mut self, template: S) -> &mut Question<R, W> {
+        self.editor_template = Some(template.into());
+        self
+    }
+
+    /// Whether responses are read with the user's editor.
+    pub(crate) fn edits_externally(&self) -> bool {
+        self.external_editor && self.keys
+    }
+
+    /// Show `prompt` and read a response with the user's editor,
+    /// to be checked like a typed one. Input is treated as having
+    /// ended if the editor could not be run or failed.
+    pub(crate) fn edit_externally(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        self.show(prompt)?;
+        let path = std::env::temp_dir().join(format!(
+            "question-{}-{}.txt",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        let template = self.editor_template.clone().unwrap_or_default();
+        let file = create_private(&path);
+        // Only remove the file if it is ours.
+        let created = file.is_ok();
+        let text = file
+            .and_then(|mut file| file.write_all(template.as_bytes()))
+            .ok()
+            .and_then(|_| editor_command(&path).status().ok())
+            .filter(|status| status.success())
+            .and_then(|_| fs::read_to_string(&path).ok());
+        if created {
+            let _ = fs::remove_file(&path);
+        }
+        self.show_text("\n")?;
+        match text {
+            Some(text) => Ok(Some(text.trim_end().to_string())),
+            None => {
+                self.ended = true;
+                Err(io::Error::other("the editor could not be run"))
+            }
+        }
+    }
+}
+
+/// Create a new file at `path` that only the user can read, failing
+/// if something is already there, so another user cannot have the
+/// answer written to a file or link they planted.
+fn create_private(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// The command opening `path` in the user's editor. Like git, the
+/// editor is run by the shell so it may include arguments.
+fn editor_command(path: &Path) -> Command {
+    let editor = editor().filter(|editor| !editor.trim().is_empty());
+    if cfg!(windows) {
+        let mut command = Command::new(editor.unwrap_or_else(|| String::from("notepad")));
+        command.arg(path);
+        command
+    } else {
+        let editor = editor.unwrap_or_else(|| String::from("vi"));
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", editor))
+            .arg(editor)
+            .arg(path);
+        command
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// The editor tests use instead of `$VISUAL`, so they need not
+    /// change the environment other tests are reading.
+    static TEST_EDITOR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// The user's editor, `$VISUAL` or `$EDITOR`.
+fn editor() -> Option<String> {
+    #[cfg(test)]
+    {
+        if let Some(editor) = TEST_EDITOR.with(|editor| editor.borrow().clone()) {
+            return Some(editor);
+        }
+    }
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::TEST_EDITOR;
+    use std::io::Cursor;
+    use {Answer, Question};
+
+    #[test]
+    fn edit_in_editor() {
+        let editor = "f() { sed s/TODO/Done/ \"$1\" > \"$1.new\" && mv \"$1.new\" \"$1\"; }; f";
+        TEST_EDITOR.with(|test| *test.borrow_mut() = Some(String::from(editor)));
+        let mut q = Question::with_cursor("Describe:", Cursor::new(Vec::new()), Vec::new());
+        q.keys = true;
+        let actual = q
+            .edit_in_editor()
+            .editor_template("Summary: TODO\n\n")
+            .ask();
+        assert_eq!(
+            Some(Answer::RESPONSE(String::from("Summary: Done"))),
+            actual
+        );
+
+        let mut q = Question::with_cursor("Describe:", Cursor::new(Vec::new()), Vec::new());
+        q.keys = true;
+        let actual = q
+            .edit_in_editor()
+            .editor_template("Summary: TODO\n\n")
+            .min_len(20)
+            .tries(2)
+            .ask();
+        assert_eq!(None, actual);
+
+        TEST_EDITOR.with(|test| *test.borrow_mut() = Some(String::from("false")));
+        let mut q = Question::with_cursor("Describe:", Cursor::new(Vec::new()), Vec::new());
+        q.keys = true;
+        assert_eq!(None, q.edit_in_editor().until_acceptable().ask());
+
+        let input = Cursor::new(b"One line\n".to_vec());
+        let mut q = Question::with_cursor("Describe:", input, Vec::new());
+        let actual = q.edit_in_editor().ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("One line"))), actual);
+    }
+}
@@ -0,0 +1,521 @@
+//! Building any `Deserialize` type by asking for each of its
+//! fields, with the `serde` feature.
+
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use serde::de::value::{MapDeserializer, StringDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+
+use {AskError, Question};
+
+impl de::Error for AskError {
+    fn custom<T: Display>(msg: T) -> AskError {
+        AskError::Invalid(msg.to_string())
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Build a `T` by asking one question per field, named after
+    /// the field: yes/no for booleans, parsed numbers, a choice of
+    /// variants for enums, comma separated lists for sequences and
+    /// `KEY=VALUE` entries for maps. An `Option` is left as `None`
+    /// when the response is empty.
+    ///
+    /// Each field is asked with the settings of this question,
+    /// such as `tries` or `until_acceptable`; its text is shown
+    /// first if it is not empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate question;
+    /// # #[macro_use] extern crate serde_derive;
+    /// # use question::Question;
+    /// #[derive(Deserialize)]
+    /// enum Level {
+    ///     Debug,
+    ///     Info,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     host: String,
+    ///     port: u16,
+    ///     tls: bool,
+    ///     level: Level,
+    ///     proxy: Option<String>,
+    /// }
+    ///
+    /// # fn main() {
+    /// let config: Config = Question::new("Let's set up the server.")
+    ///     .until_acceptable()
+    ///     .ask_for()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn ask_for<T: DeserializeOwned>(&mut self) -> Result<T, AskError> {
+        if !self.question.is_empty() {
+            let heading = format!("{}\n", self.question);
+            self.show_text(&heading).map_err(de::Error::custom)?;
+        }
+        T::deserialize(Field {
+            question: self,
+            name: String::new(),
+            pending: None,
+        })
+    }
+
+    /// Ask `text` with `parse` in place of the question, setting it
+    /// up for a field and restoring it afterwards.
+    fn ask_field<T, F>(&mut self, text: String, parse: F) -> Option<T>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        let question = std::mem::replace(&mut self.question, text);
+        let yes_no = std::mem::replace(&mut self.yes_no, false);
+        let valid_responses = self.valid_responses.take();
+        let acceptable = self.acceptable.take();
+        let default = self.default.take();
+        let answer = self.ask_parsed(parse);
+        self.question = question;
+        self.yes_no = yes_no;
+        self.valid_responses = valid_responses;
+        self.acceptable = acceptable;
+        self.default = default;
+        answer
+    }
+}
+
+/// Deserializes the value of one field by asking for it, or from
+/// a response already given when `pending`.
+struct Field<'q, R, W>
+where
+    R: Read + 'q,
+    W: Write + 'q,
+{
+    question: &'q mut Question<R, W>,
+    name: String,
+    pending: Option<String>,
+}
+
+impl<'q, R, W> Field<'q, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Parse the pending response, if any, or ask for the field
+    /// until `parse` accepts the response.
+    fn value<T, F>(&mut self, parse: F) -> Result<T, AskError>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        if let Some(pending) = self.pending.take() {
+            match parse(&pending) {
+                Ok(value) => return Ok(value),
+                Err(reason) => self
+                    .question
+                    .show_text(&format!("{}\n", reason))
+                    .map_err(de::Error::custom)?,
+            }
+        }
+        let text = format!("{}?", self.label());
+        self.ask(text, parse)
+    }
+
+    /// Ask `text` until `parse` accepts the response.
+    fn ask<T, F>(&mut self, text: String, parse: F) -> Result<T, AskError>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.question.ended = false;
+        let answer = self.question.ask_field(text, parse);
+        answer.ok_or_else(|| self.missing())
+    }
+
+    /// The field's name as it is shown.
+    fn label(&self) -> String {
+        self.name.replace('_', " ")
+    }
+
+    /// Why no value was given for the field.
+    fn missing(&self) -> AskError {
+        if self.question.ended {
+            AskError::EndOfInput
+        } else {
+            AskError::Invalid(format!("no acceptable {} was given", self.label()))
+        }
+    }
+
+    fn parsed<T>(&mut self) -> Result<T, AskError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value(|response| {
+            response
+                .parse()
+                .map_err(|e| format!("'{}' is not valid: {}", response, e))
+        })
+    }
+
+    /// The field `name` within this one.
+    fn nested(&mut self, name: &str) -> Field<'_, R, W> {
+        let name = match self.name.as_str() {
+            "" => name.to_string(),
+            parent => format!("{}.{}", parent, name),
+        };
+        Field {
+            question: &mut *self.question,
+            name,
+            pending: None,
+        }
+    }
+}
+
+macro_rules! parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, AskError> {
+                visitor.$visit(self.parsed()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'q, R, W> de::Deserializer<'de> for Field<'q, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    type Error = AskError;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, AskError> {
+        visitor.visit_string(self.value(|response| Ok(response.to_string()))?)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, AskError> {
+        visitor.visit_bool(
+            self.value(|response| match response.to_lowercase().as_str() {
+                "y" | "yes" | "true" => Ok(true),
+                "n" | "no" | "false" => Ok(false),
+                _ => Err(format!("'{}' is not yes or no", response)),
+            })?,
+        )
+    }
+
+    parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, AskError> {
+        let text = format!("{}? (optional)", self.label());
+        match self.ask(text, |response| Ok(response.to_string()))? {
+            ref response if response.is_empty() => visitor.visit_none(),
+            response => {
+                self.pending = Some(response);
+                visitor.visit_some(self)
+            }
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, AskError> {
+        let items: Vec<String> = self.value(|response| {
+            Ok(response
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(String::from)
+                .collect())
+        })?;
+        visitor.visit_seq(Items {
+            field: self,
+            items: items.into_iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        let text = format!("{}? (KEY=VALUE, blank to finish)", self.label());
+        let question = std::mem::replace(&mut self.question.question, text);
+        self.question.ended = false;
+        let map = self.question.ask_map();
+        self.question.question = question;
+        match map {
+            Some(map) => visitor.visit_map(MapDeserializer::new(map.into_iter())),
+            None => Err(self.missing()),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        visitor.visit_map(Fields {
+            field: self,
+            fields: fields.iter(),
+            current: "",
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, AskError> {
+        let pending = self.pending.take().and_then(|pending| {
+            variants
+                .iter()
+                .find(|variant| variant.eq_ignore_ascii_case(&pending))
+        });
+        let variant = match pending {
+            Some(variant) => variant,
+            None => {
+                let text = format!("{}?", self.label());
+                let question = std::mem::replace(&mut self.question.question, text);
+                self.question.ended = false;
+                let index = self.question.select(variants).ask_index();
+                self.question.question = question;
+                match index {
+                    Some(index) => &variants[index],
+                    None => return Err(self.missing()),
+                }
+            }
+        };
+        let variant: StringDeserializer<AskError> = variant.to_string().into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AskError> {
+        visitor.visit_unit()
+    }
+}
+
+/// The fields of a struct, each asked for in turn.
+struct Fields<'q, R, W>
+where
+    R: Read + 'q,
+    W: Write + 'q,
+{
+    field: Field<'q, R, W>,
+    fields: std::slice::Iter<'static, &'static str>,
+    current: &'static str,
+}
+
+impl<'de, 'q, R, W> MapAccess<'de> for Fields<'q, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    type Error = AskError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, AskError> {
+        match self.fields.next() {
+            Some(name) => {
+                self.current = name;
+                let key: StringDeserializer<AskError> = name.to_string().into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, AskError> {
+        let current = self.current;
+        seed.deserialize(self.field.nested(current))
+    }
+}
+
+/// The items of a list already given, parsed one by one.
+struct Items<'q, R, W>
+where
+    R: Read + 'q,
+    W: Write + 'q,
+{
+    field: Field<'q, R, W>,
+    items: std::vec::IntoIter<String>,
+}
+
+impl<'de, 'q, R, W> SeqAccess<'de> for Items<'q, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    type Error = AskError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, AskError> {
+        match self.items.next() {
+            Some(item) => {
+                let field = Field {
+                    question: &mut *self.field.question,
+                    name: self.field.name.clone(),
+                    pending: Some(item),
+                };
+                seed.deserialize(field).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Cursor;
+    use {AskError, Question};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Level {
+        Debug,
+        Info,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        server: Server,
+        tls: bool,
+        log_level: Level,
+        proxy: Option<String>,
+        retries: Option<u8>,
+        tags: Vec<String>,
+        labels: HashMap<String, String>,
+    }
+
+    #[test]
+    fn ask_for() {
+        let input = "example.com\nhttp\n443\ny\n2\n\n300\n3\na, b\nteam=ops\n\n";
+        let input = Cursor::new(String::from(input).into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Server setup", input, &mut output);
+            q.until_acceptable();
+            let config: Config = q.ask_for().unwrap();
+            let mut labels = HashMap::new();
+            labels.insert(String::from("team"), String::from("ops"));
+            assert_eq!(
+                Config {
+                    server: Server {
+                        host: String::from("example.com"),
+                        port: 443,
+                    },
+                    tls: true,
+                    log_level: Level::Info,
+                    proxy: None,
+                    retries: Some(3),
+                    tags: vec![String::from("a"), String::from("b")],
+                    labels,
+                },
+                config
+            );
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Server setup\n\
+             server.host? \
+             server.port? 'http' is not valid: invalid digit found in string\n\
+             server.port? \
+             tls? \
+             log level? \
+             proxy? (optional) \
+             retries? (optional) \
+             '300' is not valid: number too large to fit in target type\n\
+             retries? \
+             tags? \
+             labels? (KEY=VALUE, blank to finish) \
+             labels? (KEY=VALUE, blank to finish) ",
+            output.replace("  1) Debug\n  2) Info\n", "")
+        );
+
+        let input = Cursor::new(b"example.com\n".to_vec());
+        let mut q = Question::with_cursor("", input, Vec::new());
+        assert_eq!(Err(AskError::EndOfInput), q.ask_for::<Server>());
+    }
+}
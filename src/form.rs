@@ -0,0 +1,1296 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::iter::FromIterator;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use editor::display_width;
+use json::Json;
+use outcome::CLOCK;
+use {read_line, Answer, AskError, Question, REDACTED};
+
+/// A sequence of questions, each identified by an id, asked one
+/// after the other.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Answer, Form, Question};
+/// let mut confirm = Question::new("Continue?");
+/// confirm.yes_no().until_acceptable();
+///
+/// let answers = Form::new()
+///     .question("name", Question::new("What is your name?"))
+///     .question("continue", confirm)
+///     .ask();
+/// if answers.get("continue") == Some(&Answer::YES) {
+///     println!("Onward then!");
+/// }
+/// ```
+pub struct Form<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    pub(crate) questions: Vec<(String, Question<R, W>)>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) all_tags: bool,
+    strict: bool,
+    dry_run: bool,
+    review: Option<Question<R, W>>,
+    back: Option<String>,
+    progress: bool,
+    resume: Option<(PathBuf, Answers)>,
+    budget: Option<Duration>,
+}
+
+impl<R, W> Form<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Create a new, empty, `Form`.
+    pub fn new() -> Form<R, W> {
+        Form {
+            questions: Vec::new(),
+            tags: Vec::new(),
+            all_tags: false,
+            strict: false,
+            dry_run: false,
+            review: None,
+            back: None,
+            progress: false,
+            resume: None,
+            budget: None,
+        }
+    }
+
+    /// Add a question, identified by `id`, to the end of the form.
+    pub fn question(&mut self, id: &str, mut question: Question<R, W>) -> &mut Form<R, W> {
+        if self.strict {
+            question.strict();
+        }
+        if self.dry_run {
+            question.dry_run();
+        }
+        self.questions.push((id.to_string(), question));
+        self
+    }
+
+    /// Ask questions tagged with `tag`, alongside the untagged
+    /// questions that are always asked. Questions that are not
+    /// asked answer with their default, if they have one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Form, Question};
+    /// let mut proxy = Question::new("Proxy?");
+    /// proxy.tag("advanced").default(Answer::RESPONSE(String::from("none")));
+    ///
+    /// let mut form = Form::new();
+    /// form.question("name", Question::new("Name?"))
+    ///     .question("proxy", proxy);
+    /// if std::env::args().any(|arg| arg == "--advanced") {
+    ///     form.enable_tag("advanced");
+    /// }
+    /// let answers = form.ask();
+    /// ```
+    pub fn enable_tag(&mut self, tag: &str) -> &mut Form<R, W> {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Ask every question, whatever its tags.
+    pub fn enable_all_tags(&mut self) -> &mut Form<R, W> {
+        self.all_tags = true;
+        self
+    }
+
+    /// Make every question `strict`, and have `try_ask` check
+    /// that no input remains once the last question has been
+    /// answered. Meant for piped input, where each line answers
+    /// exactly one question; on an interactive terminal no check
+    /// for remaining input is made.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let answers = Form::new()
+    ///     .strict()
+    ///     .question("name", Question::new("Name?"))
+    ///     .question("email", Question::new("Email?"))
+    ///     .try_ask();
+    /// ```
+    pub fn strict(&mut self) -> &mut Form<R, W> {
+        self.strict = true;
+        for (_, question) in &mut self.questions {
+            question.strict();
+        }
+        self
+    }
+
+    /// Preview the form rather than asking it, showing each
+    /// question with the default it would take. See
+    /// `Question::dry_run`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let answers = Form::new()
+    ///     .dry_run()
+    ///     .question("name", Question::new("Name?"))
+    ///     .ask();
+    /// ```
+    pub fn dry_run(&mut self) -> &mut Form<R, W> {
+        self.dry_run = true;
+        for (_, question) in &mut self.questions {
+            question.dry_run();
+        }
+        self
+    }
+
+    /// Once every question has been asked, show a summary of the
+    /// answers and ask `question`, such as "Is this correct?", as
+    /// a yes/no question. Answering no lists the questions to
+    /// choose one to answer again, then shows the summary again.
+    /// Secret answers are shown as `REDACTED`. Only `ask` reviews
+    /// the answers, as `try_ask` is meant for piped input.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let answers = Form::new()
+    ///     .question("name", Question::new("Name?"))
+    ///     .question("email", Question::new("Email?"))
+    ///     .review(Question::new("Is this correct?"))
+    ///     .ask();
+    /// ```
+    pub fn review(&mut self, question: Question<R, W>) -> &mut Form<R, W> {
+        self.review = Some(question);
+        self
+    }
+
+    /// Let `token`, such as "back", be entered to return to the
+    /// previous question and answer it again, carrying on from
+    /// there. Entered at the first question, it is asked again.
+    /// Only `ask` goes back, and only for questions answered by
+    /// typing a response.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let mut email = Question::new("Email? (or 'back')");
+    /// email.until_acceptable();
+    ///
+    /// let answers = Form::new()
+    ///     .back_token("back")
+    ///     .question("name", Question::new("Name?"))
+    ///     .question("email", email)
+    ///     .ask();
+    /// ```
+    pub fn back_token(&mut self, token: &str) -> &mut Form<R, W> {
+        self.back = Some(token.to_string());
+        self
+    }
+
+    /// Show where each question is in the form before it, such as
+    /// "[3/7] Email?". Only the questions enabled by their tags
+    /// are counted. A `PromptFormatter` can change how the
+    /// progress is shown.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let answers = Form::new()
+    ///     .show_progress()
+    ///     .question("name", Question::new("Name?"))
+    ///     .question("email", Question::new("Email?"))
+    ///     .ask();
+    /// ```
+    pub fn show_progress(&mut self) -> &mut Form<R, W> {
+        self.progress = true;
+        self
+    }
+
+    /// Number the questions that will be asked, if the progress
+    /// is shown.
+    fn number_questions(&mut self) {
+        if !self.progress {
+            return;
+        }
+        let (tags, all) = (&self.tags, self.all_tags);
+        let total = self
+            .questions
+            .iter()
+            .filter(|(_, question)| is_enabled(tags, all, question))
+            .count();
+        let enabled = self
+            .questions
+            .iter_mut()
+            .filter(|(_, question)| is_enabled(tags, all, question));
+        for (position, (_, question)) in enabled.enumerate() {
+            question.progress = Some((position + 1, total));
+        }
+    }
+
+    /// Save the answers to the file at `path` as each question is
+    /// answered, so an interrupted form can carry on where it left
+    /// off. Any answers already saved there are loaded now, and
+    /// the questions they answer are not asked again unless the
+    /// user goes back to them. The file is removed once every
+    /// question has been answered. Answers to `secret` questions
+    /// are never saved, so they are asked again.
+    ///
+    /// A missing file is not an error, but one that cannot be read
+    /// or is not answers saved by a form is. Failing to save the
+    /// answers does not stop the form being asked. Only `ask`
+    /// saves and resumes answers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// # fn main() -> std::io::Result<()> {
+    /// let answers = Form::new()
+    ///     .question("hostname", Question::new("Hostname?"))
+    ///     .question("region", Question::new("Region?"))
+    ///     .resume(".provision-answers.json")?
+    ///     .ask();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume<P: AsRef<Path>>(&mut self, path: P) -> io::Result<&mut Form<R, W>> {
+        let path = path.as_ref().to_path_buf();
+        let saved = match fs::read_to_string(&path) {
+            Ok(text) => Answers::from_json(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Answers::new(),
+            Err(e) => return Err(e),
+        };
+        self.resume = Some((path, saved));
+        Ok(self)
+    }
+
+    /// The answer saved for `id` by an earlier run, if the form is
+    /// being resumed.
+    fn saved(&self, id: &str) -> Option<Answer> {
+        if self.is_secret(id) {
+            return None;
+        }
+        self.resume
+            .as_ref()
+            .and_then(|(_, saved)| saved.get(id).cloned())
+    }
+
+    /// Whether the question `id` is `secret`, so its answer is
+    /// kept out of the resume file.
+    fn is_secret(&self, id: &str) -> bool {
+        self.questions
+            .iter()
+            .any(|(question_id, question)| question_id == id && question.secret)
+    }
+
+    /// Save `answers` to the resume file, if there is one, once
+    /// `id` has been answered.
+    fn save(&mut self, id: &str, answers: &Answers) {
+        let secret = self.is_secret(id);
+        if let Some((ref path, ref mut saved)) = self.resume {
+            if let Some(answer) = answers.get(id).filter(|_| !secret) {
+                saved.insert(id, answer.clone());
+            }
+            // The answers are still returned if they cannot be saved.
+            let _ = fs::write(path, saved.to_json());
+        }
+    }
+
+    /// Remove the resume file once every question that is asked
+    /// has been answered.
+    fn finish(&mut self, answers: &Answers) {
+        let (tags, all) = (&self.tags, self.all_tags);
+        let complete = self
+            .questions
+            .iter()
+            .all(|(id, question)| !is_enabled(tags, all, question) || answers.get(id).is_some());
+        if let Some((ref path, _)) = self.resume {
+            if complete {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    /// Give the whole form `budget` to be answered in, so an
+    /// unattended run has a bounded worst case. Once it runs out
+    /// the remaining questions take their defaults, and `try_ask`
+    /// fails with `AskError::TimedOut` at the first without one.
+    ///
+    /// Until then each question counts down the time left, as set
+    /// by `Question::countdown`, unless its own countdown is
+    /// shorter, so a question waiting on an interactive terminal
+    /// stops when the budget does. Elsewhere the budget is checked
+    /// between questions. Platforms without a clock, such as
+    /// WebAssembly in the browser, have no budget.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Form, Question};
+    /// # use std::time::Duration;
+    /// let mut region = Question::new("Region?");
+    /// region.default(Answer::RESPONSE(String::from("eu-west-1")));
+    ///
+    /// let answers = Form::new()
+    ///     .time_budget(Duration::from_secs(60))
+    ///     .question("region", region)
+    ///     .try_ask();
+    /// ```
+    pub fn time_budget(&mut self, budget: Duration) -> &mut Form<R, W> {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// When the time budget runs out, if there is one, starting
+    /// from now.
+    fn deadline(&self) -> Option<Instant> {
+        self.budget
+            .filter(|_| CLOCK)
+            .map(|budget| Instant::now() + budget)
+    }
+
+    /// Ask every question in order, collecting the answers by id.
+    /// Questions that were not answered, such as when every try
+    /// was used up, are left out, as are questions filtered out by
+    /// their tags that have no default. Input ending at a question
+    /// set to `OnEof::Abort` stops the form there.
+    pub fn ask(&mut self) -> Answers {
+        self.number_questions();
+        let deadline = self.deadline();
+        let mut answers = Answers::new();
+        let mut i = 0;
+        while i < self.questions.len() {
+            let enabled = is_enabled(&self.tags, self.all_tags, &self.questions[i].1);
+            let saved = self.saved(&self.questions[i].0);
+            let (ref id, ref mut question) = self.questions[i];
+            let asked = enabled && saved.is_none() && has_time(deadline);
+            let answer = if enabled && saved.is_some() {
+                saved
+            } else if asked {
+                question.back = self.back.clone();
+                question.went_back = false;
+                within(question, deadline, Question::ask)
+            } else {
+                question.resolve_default();
+                question.default.clone()
+            };
+            if question.went_back {
+                question.went_back = false;
+                i = self.previous(i);
+                if let Some((_, ref mut saved)) = self.resume {
+                    saved.remove(&self.questions[i].0);
+                }
+                continue;
+            }
+            let (id, aborted) = (id.clone(), asked && question.aborted());
+            if let Some(answer) = answer {
+                answers.insert(&id, answer);
+            }
+            self.save(&id, &answers);
+            if aborted {
+                return answers;
+            }
+            i += 1;
+        }
+        self.review_answers(&mut answers);
+        self.finish(&answers);
+        answers
+    }
+
+    /// The position of the question asked before the one at `i`,
+    /// or `i` itself if it is the first.
+    fn previous(&self, i: usize) -> usize {
+        self.questions[..i]
+            .iter()
+            .rposition(|(_, question)| is_enabled(&self.tags, self.all_tags, question))
+            .unwrap_or(i)
+    }
+
+    /// Review `answers` with the `review` question, if there is
+    /// one, until they are confirmed or input ends.
+    fn review_answers(&mut self, answers: &mut Answers) {
+        let (questions, review) = match self.review {
+            Some(ref mut review) => (&mut self.questions, review),
+            None => return,
+        };
+        loop {
+            let answered: Vec<usize> = (0..questions.len())
+                .filter(|&i| answers.get(&questions[i].0).is_some())
+                .collect();
+            if review.show_text(&summary(questions, answers)).is_err()
+                || review.confirm() == Answer::YES
+                || review.ended
+            {
+                return;
+            }
+            let which = review.text("review-which", &[]);
+            let confirm = std::mem::replace(&mut review.question, which);
+            let labels: Vec<&str> = answered
+                .iter()
+                .map(|&i| questions[i].1.question.as_str())
+                .collect();
+            let choice = review.select(&labels).ask_index();
+            review.question = confirm;
+            let (ref id, ref mut question) = match choice {
+                Some(choice) => &mut questions[answered[choice]],
+                None => return,
+            };
+            if let Some(answer) = question.ask() {
+                answers.insert(id, answer);
+            }
+        }
+    }
+
+    /// Ask the form as a group that repeats: once, then again for
+    /// as long as `another`, such as "Add another server?", is
+    /// answered yes. Returns the answers to each round in order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Form, Question};
+    /// let servers = Form::new()
+    ///     .question("host", Question::new("Host?"))
+    ///     .question("port", Question::new("Port?"))
+    ///     .question("user", Question::new("User?"))
+    ///     .ask_repeated(&mut Question::new("Add another server?"));
+    /// for server in &servers {
+    ///     println!("{:?}", server.get("host"));
+    /// }
+    /// ```
+    pub fn ask_repeated(&mut self, another: &mut Question<R, W>) -> Vec<Answers> {
+        let mut rounds = vec![self.ask()];
+        while another.confirm() == Answer::YES {
+            rounds.push(self.ask());
+        }
+        rounds
+    }
+
+    /// Ask every question in order like `ask`, stopping at the
+    /// first `strict` question that could not be answered. A
+    /// strict form also fails if input remains after the last
+    /// question.
+    pub fn try_ask(&mut self) -> Result<Answers, AskError> {
+        self.number_questions();
+        let mut answers = Answers::new();
+        let deadline = self.deadline();
+        for (id, question) in &mut self.questions {
+            let enabled = is_enabled(&self.tags, self.all_tags, question);
+            let answer = if enabled && has_time(deadline) {
+                within(question, deadline, Question::try_ask)?
+            } else {
+                question.resolve_default();
+                if enabled && question.default.is_none() {
+                    return Err(AskError::TimedOut);
+                }
+                question.default.clone()
+            };
+            if let Some(answer) = answer {
+                answers.insert(id, answer);
+            }
+        }
+        if self.strict {
+            if let Some((_, last)) = self.questions.last_mut() {
+                if !last.keys {
+                    if let Ok(Some(line)) = read_line(&mut last.reader) {
+                        let line = line.trim_end_matches(&['\r', '\n'][..]);
+                        return Err(AskError::SurplusInput(line.to_string()));
+                    }
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Describe the form as a JSON Schema like document, so other
+    /// tools can document it or build an equivalent web form.
+    ///
+    /// Each question becomes a property named by its id, titled
+    /// with the question and described by its help. Yes/no
+    /// questions are booleans, questions with acceptable responses
+    /// are strings listing them as an `enum`, and anything else is
+    /// a free form string. Questions without a default or skip
+    /// token are `required`. Settings with no JSON Schema
+    /// equivalent are prefixed with `x-`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use question::{Form, Question};
+    /// let mut confirm = Question::new("Continue?");
+    /// confirm.yes_no();
+    ///
+    /// let schema = Form::new().question("continue", confirm).schema();
+    /// assert!(schema.contains("\"type\": \"boolean\""));
+    /// ```
+    pub fn schema(&self) -> String {
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        for (id, question) in &self.questions {
+            if question.default.is_none()
+                && question.default_with.is_none()
+                && question.skip.is_none()
+            {
+                required.push(Json::String(id.clone()));
+            }
+            properties.push((id.clone(), question_schema(question)));
+        }
+        Json::Object(vec![
+            (String::from("type"), Json::String(String::from("object"))),
+            (String::from("properties"), Json::Object(properties)),
+            (String::from("required"), Json::Array(required)),
+        ])
+        .to_string()
+    }
+}
+
+impl<R, W> Default for Form<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    fn default() -> Form<R, W> {
+        Form::new()
+    }
+}
+
+/// Whether there is time left before `deadline`, if any.
+fn has_time(deadline: Option<Instant>) -> bool {
+    deadline.is_none_or(|deadline| Instant::now() < deadline)
+}
+
+/// Ask `question` with `ask`, counting down to `deadline` unless
+/// it has no deadline or its own countdown is sooner.
+fn within<R, W, T, F>(question: &mut Question<R, W>, deadline: Option<Instant>, ask: F) -> T
+where
+    R: Read,
+    W: Write,
+    F: FnOnce(&mut Question<R, W>) -> T,
+{
+    let countdown = question.countdown;
+    if let Some(deadline) = deadline {
+        let left = deadline.saturating_duration_since(Instant::now());
+        question.countdown = Some(countdown.map_or(left, |own| own.min(left)));
+    }
+    let answer = ask(question);
+    question.countdown = countdown;
+    answer
+}
+
+/// The answers given to `questions` as a table, each question
+/// followed by its answer.
+fn summary<R: Read, W: Write>(questions: &[(String, Question<R, W>)], answers: &Answers) -> String {
+    let rows: Vec<(&str, &str)> = questions
+        .iter()
+        .filter_map(|(id, question)| {
+            let answer = answers.get(id)?;
+            let answer = if question.secret {
+                REDACTED
+            } else {
+                describe(answer)
+            };
+            Some((question.question.as_str(), answer))
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|&(question, _)| display_width(question))
+        .max()
+        .unwrap_or(0);
+    let mut table = String::new();
+    for (question, answer) in rows {
+        let padding = " ".repeat(width - display_width(question));
+        table += &format!("{}{}  {}\n", question, padding, answer);
+    }
+    table
+}
+
+/// Whether a question is asked given the enabled tags.
+pub(crate) fn is_enabled<R: Read, W: Write>(
+    tags: &[String],
+    all: bool,
+    question: &Question<R, W>,
+) -> bool {
+    all || question.tags.is_empty() || question.tags.iter().any(|tag| tags.contains(tag))
+}
+
+fn question_schema<R: Read, W: Write>(question: &Question<R, W>) -> Json {
+    let answers: Vec<Answer> = question
+        .candidates()
+        .into_iter()
+        .map(|(_, answer)| answer.to_answer())
+        .collect();
+    let boolean = !answers.is_empty()
+        && answers
+            .iter()
+            .all(|a| *a == Answer::YES || *a == Answer::NO);
+
+    let mut schema = vec![(
+        String::from("title"),
+        Json::String(question.question.clone()),
+    )];
+    if let Some(ref help) = question.help {
+        schema.push((String::from("description"), Json::String(help.clone())));
+    }
+    let kind = if boolean { "boolean" } else { "string" };
+    schema.push((String::from("type"), Json::String(kind.to_string())));
+    if !boolean && !answers.is_empty() {
+        let mut values: Vec<Json> = Vec::new();
+        for answer in &answers {
+            let value = answer_json(answer, false);
+            if !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        schema.push((String::from("enum"), Json::Array(values)));
+    }
+    if let Some(ref default) = question.default {
+        schema.push((String::from("default"), answer_json(default, boolean)));
+    }
+    if let Some(ref clarification) = question.clarification {
+        let clarification = Json::String(clarification.clone());
+        schema.push((String::from("x-clarification"), clarification));
+    }
+    if let Some(ref token) = question.skip {
+        schema.push((String::from("x-skip-token"), Json::String(token.clone())));
+    }
+    if !question.tags.is_empty() {
+        let tags = question
+            .tags
+            .iter()
+            .map(|t| Json::String(t.clone()))
+            .collect();
+        schema.push((String::from("x-tags"), Json::Array(tags)));
+    }
+    if question.until_acceptable {
+        schema.push((String::from("x-until-acceptable"), Json::Bool(true)));
+    } else if let Some(tries) = question.tries {
+        schema.push((String::from("x-tries"), Json::Number(tries)));
+    }
+    if question.is_case_sensitive() {
+        schema.push((String::from("x-case-sensitive"), Json::Bool(true)));
+    }
+    if question.accept_prefixes {
+        schema.push((String::from("x-accept-prefixes"), Json::Bool(true)));
+    }
+    Json::Object(schema)
+}
+
+/// An answer as JSON, using booleans for yes and no if `boolean`.
+pub(crate) fn answer_json(answer: &Answer, boolean: bool) -> Json {
+    match *answer {
+        Answer::YES if boolean => Json::Bool(true),
+        Answer::NO if boolean => Json::Bool(false),
+        Answer::YES => Json::String(String::from("yes")),
+        Answer::NO => Json::String(String::from("no")),
+        Answer::ALL | Answer::NEVER => Json::String(answer.to_string()),
+        Answer::RESPONSE(ref response) => Json::String(response.clone()),
+        Answer::SKIPPED | Answer::CANCELLED => Json::Null,
+    }
+}
+
+/// The answers given to a `Form`, in the order they were asked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: Vec<(String, Answer)>,
+}
+
+impl Answers {
+    /// Create an empty set of answers.
+    pub fn new() -> Answers {
+        Answers {
+            answers: Vec::new(),
+        }
+    }
+
+    /// Set the answer for `id`, replacing any previous answer.
+    pub fn insert(&mut self, id: &str, answer: Answer) {
+        match self.answers.iter_mut().find(|(key, _)| key == id) {
+            Some(entry) => entry.1 = answer,
+            None => self.answers.push((id.to_string(), answer)),
+        }
+    }
+
+    /// Remove the answer for `id`, returning it if there was one.
+    pub fn remove(&mut self, id: &str) -> Option<Answer> {
+        let i = self.answers.iter().position(|(key, _)| key == id)?;
+        Some(self.answers.remove(i).1)
+    }
+
+    /// The answers as a JSON object, with yes and no as booleans,
+    /// skipped answers as null, and all and never as objects such
+    /// as `{"answer": "all"}` so they are not read back as a
+    /// response of the same name.
+    pub(crate) fn to_json(&self) -> String {
+        let members = self
+            .answers
+            .iter()
+            .map(|(id, answer)| {
+                let value = match *answer {
+                    Answer::ALL | Answer::NEVER => {
+                        Json::Object(vec![(String::from("answer"), answer_json(answer, true))])
+                    }
+                    _ => answer_json(answer, true),
+                };
+                (id.clone(), value)
+            })
+            .collect();
+        Json::Object(members).to_string()
+    }
+
+    /// Read answers written by `to_json`.
+    pub(crate) fn from_json(text: &str) -> Result<Answers, String> {
+        let members = match Json::parse(text)? {
+            Json::Object(members) => members,
+            _ => return Err(String::from("expected an object of answers")),
+        };
+        let mut answers = Answers::new();
+        for (id, value) in members {
+            let answer = match value {
+                Json::Bool(true) => Answer::YES,
+                Json::Bool(false) => Answer::NO,
+                Json::String(response) => Answer::RESPONSE(response),
+                Json::Null => Answer::SKIPPED,
+                ref object => match object.get("answer") {
+                    Some(Json::String(answer)) if answer == "all" => Answer::ALL,
+                    Some(Json::String(answer)) if answer == "never" => Answer::NEVER,
+                    _ => return Err(format!("'{}' is not an answer", id)),
+                },
+            };
+            answers.insert(&id, answer);
+        }
+        Ok(answers)
+    }
+
+    /// The answer given for `id`, if any.
+    pub fn get(&self, id: &str) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .find(|(key, _)| key == id)
+            .map(|(_, answer)| answer)
+    }
+
+    /// Iterate over the ids and answers in the order they were given.
+    pub fn iter(&self) -> std::slice::Iter<'_, (String, Answer)> {
+        self.answers.iter()
+    }
+
+    /// The number of answers.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Whether there are no answers.
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Compare these answers against a `previous` set, such as the
+    /// answers saved by an earlier run, so the user can be shown
+    /// what is about to change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use question::{Answer, Answers};
+    /// let mut previous = Answers::new();
+    /// previous.insert("workers", Answer::RESPONSE(String::from("2")));
+    /// previous.insert("region", Answer::RESPONSE(String::from("eu")));
+    ///
+    /// let mut answers = Answers::new();
+    /// answers.insert("workers", Answer::RESPONSE(String::from("4")));
+    /// answers.insert("telemetry", Answer::NO);
+    ///
+    /// let changes = answers.diff(&previous);
+    /// assert_eq!("~ workers: 2 -> 4\n+ telemetry: no\n- region: eu\n", changes.to_string());
+    /// ```
+    pub fn diff(&self, previous: &Answers) -> Changes {
+        let mut changes = Changes {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (id, answer) in &self.answers {
+            match previous.get(id) {
+                None => changes.added.push((id.clone(), answer.clone())),
+                Some(old) if old != answer => {
+                    changes
+                        .changed
+                        .push((id.clone(), old.clone(), answer.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for (id, answer) in &previous.answers {
+            if self.get(id).is_none() {
+                changes.removed.push((id.clone(), answer.clone()));
+            }
+        }
+        changes
+    }
+}
+
+impl FromIterator<(String, Answer)> for Answers {
+    fn from_iter<I: IntoIterator<Item = (String, Answer)>>(iter: I) -> Answers {
+        let mut answers = Answers::new();
+        for (id, answer) in iter {
+            answers.insert(&id, answer);
+        }
+        answers
+    }
+}
+
+/// The differences between two sets of `Answers`, created by
+/// `Answers::diff`.
+///
+/// Displaying it lists each change on its own line, prefixed by
+/// `~` for changed, `+` for added and `-` for removed answers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Changes {
+    /// Answers that were not given before, by id.
+    pub added: Vec<(String, Answer)>,
+
+    /// Answers that are no longer given, by id.
+    pub removed: Vec<(String, Answer)>,
+
+    /// Answers that differ, by id, as the old then new answer.
+    pub changed: Vec<(String, Answer, Answer)>,
+}
+
+impl Changes {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (id, old, new) in &self.changed {
+            writeln!(f, "~ {}: {} -> {}", id, describe(old), describe(new))?;
+        }
+        for (id, answer) in &self.added {
+            writeln!(f, "+ {}: {}", id, describe(answer))?;
+        }
+        for (id, answer) in &self.removed {
+            writeln!(f, "- {}: {}", id, describe(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(answer: &Answer) -> &str {
+    match *answer {
+        Answer::YES => "yes",
+        Answer::NO => "no",
+        Answer::RESPONSE(ref response) => response,
+        Answer::SKIPPED => "(skipped)",
+        Answer::CANCELLED => "(cancelled)",
+        Answer::ALL => "yes to all",
+        Answer::NEVER => "no to all",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use OnEof;
+
+    #[test]
+    fn ask_form() {
+        let mut form = Form::new();
+        let name = Cursor::new(b"Arthur\n".to_vec());
+        form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+        let confirm = Cursor::new(b"maybe\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", confirm, Vec::new());
+        q.yes_no().until_acceptable();
+        form.question("continue", q);
+
+        let answers = form.ask();
+        assert_eq!(2, answers.len());
+        let name = Answer::RESPONSE(String::from("Arthur"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(Some(&Answer::YES), answers.get("continue"));
+        assert_eq!(None, answers.get("missing"));
+    }
+
+    #[test]
+    fn abort_on_eof() {
+        let mut form = Form::new();
+        let mut q = Question::with_cursor("Name?", Cursor::new(Vec::new()), Vec::new());
+        q.default(Answer::RESPONSE(String::from("Arthur")));
+        form.question("name", q);
+        let mut q = Question::with_cursor("Towel?", Cursor::new(Vec::new()), Vec::new());
+        q.yes_no().default(Answer::YES).on_eof(OnEof::Abort);
+        form.question("towel", q);
+        let guide = Cursor::new(b"y\n".to_vec());
+        form.question("guide", Question::with_cursor("Guide?", guide, Vec::new()));
+
+        let answers = form.ask();
+        assert_eq!(1, answers.len());
+        let name = Answer::RESPONSE(String::from("Arthur"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(None, answers.get("guide"));
+        assert_eq!(Err(AskError::EndOfInput), form.try_ask().map(|_| ()));
+    }
+
+    #[test]
+    fn review() {
+        let mut form = Form::new();
+        let name = Cursor::new(b"Arthur\nFord\n".to_vec());
+        form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+        let towel = Cursor::new(b"y\n".to_vec());
+        let mut q = Question::with_cursor("Towel?", towel, Vec::new());
+        q.yes_no();
+        form.question("towel", q);
+        let review = Cursor::new(b"n\n1\ny\n".to_vec());
+        form.review(Question::with_cursor("Correct?", review, Vec::new()));
+        let answers = form.ask();
+        let name = Answer::RESPONSE(String::from("Ford"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(Some(&Answer::YES), answers.get("towel"));
+        let output = form.review.unwrap().writer;
+        let expected = "Name?   Arthur\n\
+                        Towel?  yes\n\
+                        Correct?   1) Name?\n\
+                        \x20 2) Towel?\n\
+                        Which answer would you like to change? \
+                        Name?   Ford\n\
+                        Towel?  yes\n\
+                        Correct? ";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn back() {
+        let mut form = Form::new();
+        let name = Cursor::new(b"Arthur\nFord\n".to_vec());
+        form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+        let planet = Cursor::new(b"back\nBetelgeuse\n".to_vec());
+        let mut q = Question::with_cursor("Planet?", planet, Vec::new());
+        q.tag("advanced")
+            .default(Answer::RESPONSE(String::from("Earth")));
+        form.question("planet", q);
+        let towel = Cursor::new(b"back\ny\n".to_vec());
+        let mut q = Question::with_cursor("Towel?", towel, Vec::new());
+        q.yes_no().until_acceptable();
+        form.question("towel", q);
+
+        let answers = form.back_token("back").ask();
+        let actual: Vec<(&str, Answer)> = answers
+            .iter()
+            .map(|(id, answer)| (id.as_str(), answer.clone()))
+            .collect();
+        let expected = vec![
+            ("name", Answer::RESPONSE(String::from("Ford"))),
+            ("planet", Answer::RESPONSE(String::from("Earth"))),
+            ("towel", Answer::YES),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn progress() {
+        let mut form = Form::new();
+        form.show_progress();
+        for &(id, tag) in &[("name", None), ("proxy", Some("advanced")), ("email", None)] {
+            let input = Cursor::new(b"x\n".to_vec());
+            let mut q = Question::with_cursor(id, input, Vec::new());
+            if let Some(tag) = tag {
+                q.tag(tag);
+            }
+            form.question(id, q);
+        }
+        form.ask();
+        let prompts: Vec<String> = form
+            .questions
+            .iter()
+            .map(|(_, q)| String::from_utf8(q.writer.clone()).unwrap())
+            .collect();
+        assert_eq!(vec!["[1/2] name ", "", "[2/2] email "], prompts);
+    }
+
+    #[test]
+    fn resume() {
+        let path =
+            std::env::temp_dir().join(format!("question-resume-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        macro_rules! form {
+            ($name:expr, $towel:expr) => {{
+                let mut form = Form::new();
+                let name = Cursor::new($name.to_vec());
+                form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+                let towel = Cursor::new($towel.to_vec());
+                let mut q = Question::with_cursor("Towel?", towel, Vec::new());
+                q.yes_no().until_acceptable();
+                form.question("towel", q);
+                form.resume(&path).unwrap();
+                form
+            }};
+        }
+        let answers = form!(b"yes\n", b"").ask();
+        assert_eq!(1, answers.len());
+        assert_eq!(
+            "{\n  \"name\": \"yes\"\n}",
+            fs::read_to_string(&path).unwrap()
+        );
+
+        let mut form = form!(b"Ford\n", b"n\n");
+        let answers = form.ask();
+        let name = Answer::RESPONSE(String::from("yes"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(Some(&Answer::NO), answers.get("towel"));
+        assert!(form.questions[0].1.writer.is_empty());
+        assert!(!path.exists());
+
+        let mut form = form!(b"Ford\n", b"");
+        let passphrase = Cursor::new(b"hunter2\n".to_vec());
+        let mut q = Question::with_cursor("Passphrase?", passphrase, Vec::new());
+        q.secret();
+        form.question("passphrase", q);
+        form.ask();
+        assert_eq!(
+            "{\n  \"name\": \"Ford\"\n}",
+            fs::read_to_string(&path).unwrap()
+        );
+        fs::write(&path, "{\"passphrase\": \"hunter2\"}").unwrap();
+        let mut form = form!(b"", b"");
+        let passphrase = Cursor::new(b"swordfish\n".to_vec());
+        let mut q = Question::with_cursor("Passphrase?", passphrase, Vec::new());
+        q.secret();
+        form.question("passphrase", q);
+        form.resume(&path).unwrap();
+        let secret = Answer::RESPONSE(String::from("swordfish"));
+        assert_eq!(Some(&secret), form.ask().get("passphrase"));
+        fs::remove_file(&path).unwrap();
+
+        fs::write(&path, "[]").unwrap();
+        let mut form: Form<Cursor<Vec<u8>>, Vec<u8>> = Form::new();
+        let error = form.resume(&path).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn answers_json() {
+        let mut answers = Answers::new();
+        answers.insert("overwrite", Answer::ALL);
+        answers.insert("upgrade", Answer::NEVER);
+        answers.insert("mode", Answer::RESPONSE(String::from("all")));
+        answers.insert("towel", Answer::YES);
+        answers.insert("name", Answer::SKIPPED);
+        assert_eq!(Ok(answers.clone()), Answers::from_json(&answers.to_json()));
+        assert!(Answers::from_json("{\"overwrite\": {\"answer\": \"some\"}}").is_err());
+    }
+
+    #[test]
+    fn ask_repeated() {
+        let mut form = Form::new();
+        let host = Cursor::new(b"web-1\nweb-2\n".to_vec());
+        form.question("host", Question::with_cursor("Host?", host, Vec::new()));
+        let another = Cursor::new(b"y\nn\n".to_vec());
+        let mut another = Question::with_cursor("Another?", another, Vec::new());
+        let rounds = form.ask_repeated(&mut another);
+        let hosts: Vec<Option<&Answer>> = rounds.iter().map(|a| a.get("host")).collect();
+        let web = |n: &str| Answer::RESPONSE(format!("web-{}", n));
+        assert_eq!(vec![Some(&web("1")), Some(&web("2"))], hosts);
+
+        let another = Cursor::new(Vec::new());
+        let mut another = Question::with_cursor("Another?", another, Vec::new());
+        assert_eq!(1, form.ask_repeated(&mut another).len());
+    }
+
+    #[test]
+    fn time_budget() {
+        macro_rules! budget {
+            ($budget:expr) => {{
+                let mut form = Form::new();
+                form.time_budget($budget);
+                let name = Cursor::new(b"Arthur\n".to_vec());
+                let mut q = Question::with_cursor("Name?", name, Vec::new());
+                q.default(Answer::RESPONSE(String::from("Ford")));
+                form.question("name", q);
+                let planet = Cursor::new(b"Earth\n".to_vec());
+                form.question(
+                    "planet",
+                    Question::with_cursor("Planet?", planet, Vec::new()),
+                );
+                form
+            }};
+        }
+        let mut form = budget!(Duration::from_secs(60));
+        let answers = form.try_ask().unwrap();
+        let name = Answer::RESPONSE(String::from("Arthur"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(None, form.questions[0].1.countdown);
+
+        let answers = budget!(Duration::ZERO).ask();
+        let name = Answer::RESPONSE(String::from("Ford"));
+        assert_eq!(Some(&name), answers.get("name"));
+        assert_eq!(None, answers.get("planet"));
+        assert_eq!(Err(AskError::TimedOut), budget!(Duration::ZERO).try_ask());
+    }
+
+    #[test]
+    fn tags() {
+        macro_rules! tags {
+            ($enable:expr, $all:expr, $expected:expr) => {
+                let mut form = Form::new();
+                let name = Cursor::new(b"Arthur\n".to_vec());
+                form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+                let proxy = Cursor::new(b"socks\n".to_vec());
+                let mut q = Question::with_cursor("Proxy?", proxy, Vec::new());
+                q.tag("network")
+                    .tag("advanced")
+                    .default(Answer::RESPONSE(String::from("none")));
+                form.question("proxy", q);
+                let debug = Cursor::new(b"y\n".to_vec());
+                let mut q = Question::with_cursor("Debug?", debug, Vec::new());
+                q.yes_no().tag("advanced");
+                form.question("debug", q);
+                for tag in $enable.iter() {
+                    form.enable_tag(tag);
+                }
+                if $all {
+                    form.enable_all_tags();
+                }
+                let answers = form.ask();
+                let actual: Vec<(&str, Answer)> = answers
+                    .iter()
+                    .map(|(id, answer)| (id.as_str(), answer.clone()))
+                    .collect();
+                assert_eq!($expected, actual);
+            };
+        }
+        let name = ("name", Answer::RESPONSE(String::from("Arthur")));
+        let none = ("proxy", Answer::RESPONSE(String::from("none")));
+        let socks = ("proxy", Answer::RESPONSE(String::from("socks")));
+        let debug = ("debug", Answer::YES);
+        let nothing: [&str; 0] = [];
+        tags!(nothing, false, vec![name.clone(), none]);
+        tags!(["network"], false, vec![name.clone(), socks.clone()]);
+        tags!(
+            ["advanced"],
+            false,
+            vec![name.clone(), socks.clone(), debug.clone()]
+        );
+        tags!(nothing, true, vec![name, socks, debug]);
+    }
+
+    #[test]
+    fn strict() {
+        macro_rules! strict {
+            ($name:expr, $confirm:expr, $expected:expr) => {
+                let mut form = Form::new();
+                form.strict();
+                let name = Cursor::new($name.to_vec());
+                form.question("name", Question::with_cursor("Name?", name, Vec::new()));
+                let confirm = Cursor::new($confirm.to_vec());
+                let mut q = Question::with_cursor("Continue?", confirm, Vec::new());
+                q.yes_no().until_acceptable();
+                form.question("continue", q);
+                let actual = form.try_ask().map(|answers| answers.len());
+                assert_eq!($expected, actual);
+            };
+        }
+        strict!(b"Arthur\n", b"y\n", Ok(2));
+        strict!(
+            b"Arthur\n",
+            b"maybe\ny\n",
+            Err(AskError::Rejected(String::from("maybe")))
+        );
+        strict!(b"", b"y\n", Err(AskError::EndOfInput));
+        strict!(
+            b"Arthur\n",
+            b"y\nn\n",
+            Err(AskError::SurplusInput(String::from("n")))
+        );
+    }
+
+    #[test]
+    fn schema() {
+        let mut form = Form::new();
+        let mut q = Question::with_cursor("Name?", Cursor::new(Vec::new()), Vec::new());
+        q.default(Answer::RESPONSE(String::from("Arthur")));
+        form.question("name", q);
+        let mut q = Question::with_cursor("Environment?", Cursor::new(Vec::new()), Vec::new());
+        q.acceptable(vec!["staging", "production"]).tries(3);
+        form.question("environment", q);
+        let mut q = Question::with_cursor("Continue?", Cursor::new(Vec::new()), Vec::new());
+        q.yes_no().default(Answer::NO);
+        form.question("continue", q);
+
+        let expected = r#"{
+  "type": "object",
+  "properties": {
+    "name": {
+      "title": "Name?",
+      "type": "string",
+      "default": "Arthur"
+    },
+    "environment": {
+      "title": "Environment?",
+      "type": "string",
+      "enum": [
+        "staging",
+        "production"
+      ],
+      "x-tries": 3
+    },
+    "continue": {
+      "title": "Continue?",
+      "type": "boolean",
+      "default": false
+    }
+  },
+  "required": [
+    "environment"
+  ]
+}"#;
+        assert_eq!(expected, form.schema());
+    }
+
+    #[test]
+    fn diff() {
+        let previous: Answers = vec![
+            (
+                String::from("name"),
+                Answer::RESPONSE(String::from("Arthur")),
+            ),
+            (String::from("continue"), Answer::YES),
+        ]
+        .into_iter()
+        .collect();
+        assert!(previous.diff(&previous).is_empty());
+
+        let mut answers = previous.clone();
+        answers.insert("continue", Answer::NO);
+        answers.insert("towel", Answer::YES);
+        let changes = answers.diff(&previous);
+        assert_eq!(
+            vec![(String::from("continue"), Answer::YES, Answer::NO)],
+            changes.changed
+        );
+        assert_eq!(vec![(String::from("towel"), Answer::YES)], changes.added);
+        assert!(changes.removed.is_empty());
+
+        let changes = previous.diff(&answers);
+        assert_eq!(vec![(String::from("towel"), Answer::YES)], changes.removed);
+        assert_eq!("~ continue: no -> yes\n- towel: yes\n", changes.to_string());
+    }
+}
@@ -0,0 +1,79 @@
+//! Control over the text of every prompt, so a family of tools
+//! can share one look.
+
+/// Renders each part of a prompt.
+///
+/// Every method has a default matching the built in look, so a
+/// formatter only overrides the parts it changes. Each part is
+/// still styled by the question's `Theme`, if any, afterwards.
+///
+/// # Examples
+///
+/// A formatter prefixing questions with "?" and listing the
+/// acceptable responses in brackets.
+///
+/// ```no_run
+/// # use question::{PromptFormatter, Question};
+/// struct Bracketed;
+///
+/// impl PromptFormatter for Bracketed {
+///     fn question(&self, question: &str) -> String {
+///         format!("? {}", question)
+///     }
+///
+///     fn choices(&self, choices: &[&str]) -> String {
+///         format!(" [{}]", choices.join("/"))
+///     }
+///
+///     fn error(&self, error: &str) -> String {
+///         format!("! {}", error)
+///     }
+/// }
+///
+/// Question::new("Environment?")
+///     .acceptable(vec!["staging", "production"])
+///     .formatter(Bracketed)
+///     .ask();
+/// ```
+pub trait PromptFormatter {
+    /// The question itself.
+    fn question(&self, question: &str) -> String {
+        question.to_string()
+    }
+
+    /// The acceptable responses, if there are any. Not shown by
+    /// default.
+    fn choices(&self, choices: &[&str]) -> String {
+        let _ = choices;
+        String::new()
+    }
+
+    /// The hint describing the default answer, such as "Y/n",
+    /// shown when `show_defaults` is set.
+    fn default_hint(&self, hint: &str) -> String {
+        format!(" ({})", hint)
+    }
+
+    /// What follows the prompt, before the user's response.
+    fn separator(&self) -> String {
+        String::from(" ")
+    }
+
+    /// A line above the question explaining why the previous
+    /// response was not accepted, or the clarification.
+    fn error(&self, error: &str) -> String {
+        error.to_string()
+    }
+
+    /// Where the question is in a `Form` showing its progress,
+    /// counting from 1, shown before the question.
+    fn progress(&self, position: usize, total: usize) -> String {
+        format!("[{}/{}] ", position, total)
+    }
+}
+
+/// The built in look, such as "Continue? (Y/n) ".
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultFormatter;
+
+impl PromptFormatter for DefaultFormatter {}
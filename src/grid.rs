@@ -0,0 +1,292 @@
+use std::io::{Read, Write};
+
+use term::{self, Key, RawMode};
+use Question;
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for a grid of numbers, such as a limit per resource
+    /// for each environment, returning one `Vec` per row.
+    ///
+    /// On an interactive terminal (see `toggle`) the grid is
+    /// shown as a table whose cells are chosen with the arrow
+    /// keys or tab, edited by typing, and confirmed with enter.
+    /// Otherwise each row is asked for on its own line, as
+    /// numbers separated by spaces or commas, and an empty line
+    /// keeps the row's initial values.
+    ///
+    /// Cells start with the values in `initial`, or `0` where
+    /// none is given. `None` is returned if input ends first. A
+    /// grid without rows or columns has no cells to ask for, so it
+    /// is returned straight away.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let limits = Question::new("Resource limits?").grid(
+    ///     &["staging", "production"],
+    ///     &["cpus", "memory"],
+    ///     &[vec![1.0, 512.0], vec![4.0, 2048.0]],
+    /// );
+    /// ```
+    pub fn grid(
+        &mut self,
+        rows: &[&str],
+        columns: &[&str],
+        initial: &[Vec<f64>],
+    ) -> Option<Vec<Vec<f64>>> {
+        let mut values: Vec<Vec<f64>> = (0..rows.len())
+            .map(|r| {
+                (0..columns.len())
+                    .map(|c| {
+                        initial
+                            .get(r)
+                            .and_then(|row| row.get(c))
+                            .cloned()
+                            .unwrap_or(0.0)
+                    })
+                    .collect()
+            })
+            .collect();
+        if rows.is_empty() || columns.is_empty() {
+            return Some(values);
+        }
+        let result = if self.keys {
+            self.grid_table(rows, columns, &mut values)
+        } else {
+            self.grid_lines(rows, columns, &mut values)
+        };
+        match result {
+            Ok(true) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn grid_table(
+        &mut self,
+        rows: &[&str],
+        columns: &[&str],
+        values: &mut [Vec<f64>],
+    ) -> Result<bool, std::io::Error> {
+        let raw = RawMode::enable()?;
+        let (mut row, mut column) = (0, 0);
+        let mut edit: Option<String> = None;
+        let mut drawn = 0;
+        loop {
+            let lines = render_grid(
+                &self.question_text(),
+                rows,
+                columns,
+                values,
+                (row, column),
+                &edit,
+            );
+            let mut frame = String::new();
+            if drawn > 1 {
+                frame += &format!("\x1b[{}A", drawn - 1);
+            }
+            frame += "\r";
+            frame += &lines
+                .iter()
+                .map(|line| format!("\x1b[2K{}", line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            drawn = lines.len();
+            self.show(&frame)?;
+
+            let key = match term::read_key(&mut self.reader)? {
+                Some(key) => key,
+                None => {
+                    self.show_text("\n")?;
+                    return Ok(false);
+                }
+            };
+            if let Some(buffer) = edit.take() {
+                match key {
+                    Key::Char(c) if c.is_ascii_digit() || c == '.' || c == '-' => {
+                        edit = Some(buffer + &c.to_string());
+                        continue;
+                    }
+                    Key::Backspace => {
+                        let mut buffer = buffer;
+                        buffer.pop();
+                        edit = Some(buffer);
+                        continue;
+                    }
+                    _ => {
+                        if let Ok(value) = buffer.parse() {
+                            values[row][column] = value;
+                        }
+                    }
+                }
+            }
+            match key {
+                Key::Up => row = row.saturating_sub(1),
+                Key::Down => row = (row + 1).min(rows.len().saturating_sub(1)),
+                Key::Left => column = column.saturating_sub(1),
+                Key::Right => column = (column + 1).min(columns.len().saturating_sub(1)),
+                Key::Tab => {
+                    column += 1;
+                    if column == columns.len() {
+                        column = 0;
+                        row = (row + 1) % rows.len().max(1);
+                    }
+                }
+                Key::Char(c) if c.is_ascii_digit() || c == '.' || c == '-' => {
+                    edit = Some(c.to_string());
+                }
+                Key::Backspace => edit = Some(String::new()),
+                Key::Enter => {
+                    self.show_text("\n")?;
+                    return Ok(true);
+                }
+                Key::Interrupt => {
+                    self.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(false);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn grid_lines(
+        &mut self,
+        rows: &[&str],
+        columns: &[&str],
+        values: &mut [Vec<f64>],
+    ) -> Result<bool, std::io::Error> {
+        let header = format!("{}\n", self.question_text());
+        self.show_text(&header)?;
+        for (r, name) in rows.iter().enumerate() {
+            let current: Vec<String> = values[r].iter().map(|v| v.to_string()).collect();
+            let prompt = format!("{} ({}) [{}] ", name, columns.join(", "), current.join(" "));
+            loop {
+                let line = match self.read_prompted_line(&prompt)? {
+                    Some(line) => line,
+                    None => return Ok(false),
+                };
+                if line.trim().is_empty() {
+                    break;
+                }
+                let parsed: Result<Vec<f64>, _> = line
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse())
+                    .collect();
+                match parsed {
+                    Ok(ref numbers) if numbers.len() == columns.len() => {
+                        values[r].clone_from(numbers);
+                        break;
+                    }
+                    _ => {
+                        let count = columns.len().to_string();
+                        let message = self.text("grid-numbers", &[("count", &count)]);
+                        self.show_text(&format!("{}\n", message))?;
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Render the grid as lines of text, highlighting the selected cell.
+fn render_grid(
+    question: &str,
+    rows: &[&str],
+    columns: &[&str],
+    values: &[Vec<f64>],
+    selected: (usize, usize),
+    edit: &Option<String>,
+) -> Vec<String> {
+    let cell = |r: usize, c: usize| -> String {
+        match *edit {
+            Some(ref buffer) if (r, c) == selected => buffer.clone(),
+            _ => values[r][c].to_string(),
+        }
+    };
+    let label_width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(c, name)| {
+            (0..rows.len())
+                .map(|r| cell(r, c).chars().count())
+                .chain(Some(name.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut lines = vec![question.to_string()];
+    let mut header = format!("{:1$}", "", label_width);
+    for (name, width) in columns.iter().zip(&widths) {
+        header += &format!("  {:>1$} ", name, width);
+    }
+    lines.push(header);
+    for (r, name) in rows.iter().enumerate() {
+        let mut line = format!("{:1$}", name, label_width);
+        for (c, width) in widths.iter().enumerate() {
+            let text = format!(" {:>1$} ", cell(r, c), width);
+            if (r, c) == selected {
+                line += &format!(" \x1b[7m{}\x1b[0m", text);
+            } else {
+                line += &format!(" {}", text);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use Question;
+
+    #[test]
+    fn grid() {
+        macro_rules! grid {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Limits?", input, Vec::new());
+                q.keys = $keys;
+                let initial = [vec![1.0, 512.0]];
+                let actual = q.grid(&["staging", "production"], &["cpus", "memory"], &initial);
+                assert_eq!($expected, actual);
+            };
+        }
+        let unchanged = Some(vec![vec![1.0, 512.0], vec![0.0, 0.0]]);
+        grid!(b"\r", true, unchanged.clone());
+        grid!(
+            b"\x1b[B8\t16\x7f0\r",
+            true,
+            Some(vec![vec![1.0, 512.0], vec![8.0, 10.0]])
+        );
+        grid!(
+            b"\x1b[C2048\x1b[D2\r",
+            true,
+            Some(vec![vec![2.0, 2048.0], vec![0.0, 0.0]])
+        );
+        grid!(b"", true, None);
+        grid!(b"\n\n", false, unchanged);
+        grid!(
+            b"2\n4, 1024\n8 2048\n",
+            false,
+            Some(vec![vec![4.0, 1024.0], vec![8.0, 2048.0]])
+        );
+        grid!(b"\n", false, None);
+
+        let mut q = Question::with_cursor("Limits?", Cursor::new(Vec::new()), Vec::new());
+        q.keys = true;
+        let empty: Vec<Vec<f64>> = Vec::new();
+        assert_eq!(Some(empty), q.grid(&[], &["cpus"], &[]));
+        assert_eq!(Some(vec![vec![]]), q.grid(&["staging"], &[], &[]));
+        assert!(q.writer.is_empty());
+    }
+}
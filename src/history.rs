@@ -0,0 +1,162 @@
+//! Recalling earlier responses with the arrow keys.
+
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use Question;
+
+/// The responses given to a question so far, oldest first, and
+/// the file they are kept in, if any.
+#[derive(Debug, Default)]
+pub(crate) struct History {
+    pub(crate) entries: Vec<String>,
+    file: Option<PathBuf>,
+}
+
+impl History {
+    /// Remember `response`, unless it is empty or the same as the
+    /// last one, appending it to the file if there is one. Failing
+    /// to write the file only loses the entry between runs.
+    pub(crate) fn record(&mut self, response: &str) {
+        if response.is_empty() || self.entries.last().map(String::as_str) == Some(response) {
+            return;
+        }
+        self.entries.push(response.to_string());
+        if let Some(ref path) = self.file {
+            let file = OpenOptions::new().create(true).append(true).open(path);
+            if let Ok(mut file) = file {
+                let _ = writeln!(file, "{}", response.replace('\n', " "));
+            }
+        }
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Remember the responses given each time the question is
+    /// asked, so on an interactive terminal (see `toggle`) up and
+    /// down recall them, like a shell. The history is shared by
+    /// copies of the question, such as those asked by a `Form`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let mut query = Question::new("query>");
+    /// query.history();
+    /// while let Some(answer) = query.ask() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn history(&mut self) -> &mut Question<R, W> {
+        if self.history.is_none() {
+            self.history = Some(Arc::new(Mutex::new(History::default())));
+        }
+        self
+    }
+
+    /// Keep the history, as for `history`, in the file at `path`
+    /// between runs, one response per line. Earlier responses are
+    /// read from it now, if it exists, and each new response is
+    /// appended to it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("query>").history_file(".query_history").ask();
+    /// ```
+    pub fn history_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Question<R, W> {
+        let path = path.as_ref().to_path_buf();
+        let entries = fs::read_to_string(&path)
+            .map(|text| text.lines().map(String::from).collect())
+            .unwrap_or_default();
+        self.history = Some(Arc::new(Mutex::new(History {
+            entries,
+            file: Some(path),
+        })));
+        self
+    }
+
+    /// Remember `response` in the history, if it is kept.
+    pub(crate) fn record_history(&self, response: &str) {
+        if let Some(ref history) = self.history {
+            if let Ok(mut history) = history.lock() {
+                history.record(response);
+            }
+        }
+    }
+
+    /// The history kept so far, oldest first.
+    pub(crate) fn history_entries(&self) -> Vec<String> {
+        match self.history {
+            Some(ref history) => history
+                .lock()
+                .map(|h| h.entries.clone())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use Answer;
+
+    #[test]
+    fn record() {
+        let mut history = History::default();
+        for response in &["ls", "", "ls", "cd /", "ls"] {
+            history.record(response);
+        }
+        assert_eq!(vec!["ls", "cd /", "ls"], history.entries);
+    }
+
+    #[test]
+    fn recall() {
+        let input = Cursor::new(b"ls\rcd /\r\x1b[A\x1b[A\r\x1b[Ax\ry\x1b[A\x1b[B\r".to_vec());
+        let mut q = Question::with_cursor("$", input, Vec::new());
+        q.keys = true;
+        q.history();
+        let answers: Vec<Option<Answer>> = (0..5).map(|_| q.ask()).collect();
+        let response = |s: &str| Some(Answer::RESPONSE(String::from(s)));
+        assert_eq!(
+            vec![
+                response("ls"),
+                response("cd /"),
+                response("ls"),
+                response("lsx"),
+                response("y"),
+            ],
+            answers
+        );
+    }
+
+    #[test]
+    fn history_file() {
+        let path = std::env::temp_dir().join(format!("question-history-{}", std::process::id()));
+        fs::write(&path, "first\n").unwrap();
+        let input = Cursor::new(String::from("second\n\nthird\n").into_bytes());
+        let mut q = Question::with_cursor("query>", input, Vec::new());
+        q.history_file(&path);
+        for _ in 0..3 {
+            q.ask();
+        }
+        assert_eq!(vec!["first", "second", "third"], q.history_entries());
+        assert_eq!("first\nsecond\nthird\n", fs::read_to_string(&path).unwrap());
+
+        let input = Cursor::new(String::from("fourth\n").into_bytes());
+        let mut q = Question::with_cursor("query>", input, Vec::new());
+        q.history_file(&path);
+        assert_eq!(Some(Answer::RESPONSE(String::from("fourth"))), q.ask());
+        assert_eq!(4, q.history_entries().len());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+//! A minimal JSON value, enough to describe questions to other
+//! tools and read their answers without pulling in a
+//! serialization framework.
+
+use std::fmt;
+
+/// A JSON value. Objects keep their keys in insertion order.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn write(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        match *self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::String(ref s) => write_string(f, s),
+            Json::Array(ref values) if values.is_empty() => write!(f, "[]"),
+            Json::Array(ref values) => {
+                writeln!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{:1$}", "", (indent + 1) * 2)?;
+                    value.write(f, indent + 1)?;
+                    if i + 1 < values.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{:1$}]", "", indent * 2)
+            }
+            Json::Object(ref members) if members.is_empty() => write!(f, "{{}}"),
+            Json::Object(ref members) => {
+                writeln!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    write!(f, "{:1$}", "", (indent + 1) * 2)?;
+                    write_string(f, key)?;
+                    write!(f, ": ")?;
+                    value.write(f, indent + 1)?;
+                    if i + 1 < members.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{:1$}}}", "", indent * 2)
+            }
+        }
+    }
+}
+
+impl Json {
+    /// The value on a single line, as JSON lines are written.
+    pub(crate) fn to_line(&self) -> String {
+        match *self {
+            Json::Array(ref values) => {
+                let values: Vec<String> = values.iter().map(Json::to_line).collect();
+                format!("[{}]", values.join(","))
+            }
+            Json::Object(ref members) => {
+                let members: Vec<String> = members
+                    .iter()
+                    .map(|(key, value)| {
+                        format!("{}:{}", Json::String(key.clone()), value.to_line())
+                    })
+                    .collect();
+                format!("{{{}}}", members.join(","))
+            }
+            ref scalar => scalar.to_string(),
+        }
+    }
+
+    /// Parse a JSON value. Only whole numbers that fit in a `u64`
+    /// are supported.
+    pub(crate) fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            position: 0,
+        };
+        let value = parser.value()?;
+        parser.whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(c) => Err(format!("unexpected '{}' after the value", c)),
+        }
+    }
+
+    /// The value of `key`, if this is an object with it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        match *self {
+            Json::Object(ref members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, word: &str) -> Result<(), String> {
+        for expected in word.chars() {
+            if self.next() != Some(expected) {
+                return Err(format!("expected '{}'", word));
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.whitespace();
+        match self.peek() {
+            Some('n') => self.expect("null").map(|()| Json::Null),
+            Some('t') => self.expect("true").map(|()| Json::Bool(true)),
+            Some('f') => self.expect("false").map(|()| Json::Bool(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => {
+                self.position += 1;
+                let mut values = Vec::new();
+                self.whitespace();
+                if self.peek() == Some(']') {
+                    self.position += 1;
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.whitespace();
+                    match self.next() {
+                        Some(',') => {}
+                        Some(']') => return Ok(Json::Array(values)),
+                        _ => return Err(String::from("expected ',' or ']'")),
+                    }
+                }
+            }
+            Some('{') => {
+                self.position += 1;
+                let mut members = Vec::new();
+                self.whitespace();
+                if self.peek() == Some('}') {
+                    self.position += 1;
+                    return Ok(Json::Object(members));
+                }
+                loop {
+                    self.whitespace();
+                    let key = self.string()?;
+                    self.whitespace();
+                    self.expect(":")?;
+                    members.push((key, self.value()?));
+                    self.whitespace();
+                    match self.next() {
+                        Some(',') => {}
+                        Some('}') => return Ok(Json::Object(members)),
+                        _ => return Err(String::from("expected ',' or '}'")),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.position;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.position += 1;
+                }
+                let digits: String = self.chars[start..self.position].iter().collect();
+                digits
+                    .parse()
+                    .map(Json::Number)
+                    .map_err(|_| format!("{} is too large", digits))
+            }
+            Some(c) => Err(format!("unexpected '{}'", c)),
+            None => Err(String::from("unexpected end of input")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.next() != Some('"') {
+            return Err(String::from("expected a string"));
+        }
+        let mut s = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.next() {
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .map_err(|_| format!("invalid escape '\\u{}'", hex))?;
+                        s.push(std::char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    Some(c) => s.push(c),
+                    None => return Err(String::from("unterminated string")),
+                },
+                Some(c) => s.push(c),
+                None => return Err(String::from("unterminated string")),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("null", Json::Null.to_string());
+        assert_eq!(
+            "\"a \\\"b\\\"\\n\"",
+            Json::String(String::from("a \"b\"\n")).to_string()
+        );
+        assert_eq!("[]", Json::Array(Vec::new()).to_string());
+        let object = Json::Object(vec![
+            (String::from("a"), Json::Bool(true)),
+            (
+                String::from("b"),
+                Json::Array(vec![Json::Number(1), Json::Number(2)]),
+            ),
+        ]);
+        assert_eq!(
+            "{\n  \"a\": true,\n  \"b\": [\n    1,\n    2\n  ]\n}",
+            object.to_string()
+        );
+    }
+
+    #[test]
+    fn line() {
+        let object = Json::Object(vec![
+            (String::from("a"), Json::Bool(true)),
+            (
+                String::from("b"),
+                Json::Array(vec![Json::Number(1), Json::Null]),
+            ),
+        ]);
+        assert_eq!("{\"a\":true,\"b\":[1,null]}", object.to_line());
+    }
+
+    #[test]
+    fn parse() {
+        macro_rules! parse {
+            ($text:expr, $expected:expr) => {
+                assert_eq!($expected, Json::parse($text));
+            };
+        }
+        parse!("null", Ok(Json::Null));
+        parse!(" true ", Ok(Json::Bool(true)));
+        parse!("42", Ok(Json::Number(42)));
+        parse!(
+            "\"a\\\"b\\n\\u00e9\"",
+            Ok(Json::String(String::from("a\"b\né")))
+        );
+        parse!(
+            "{\"answer\": [1, \"x\"], \"b\": {}}",
+            Ok(Json::Object(vec![
+                (
+                    String::from("answer"),
+                    Json::Array(vec![Json::Number(1), Json::String(String::from("x"))])
+                ),
+                (String::from("b"), Json::Object(Vec::new())),
+            ]))
+        );
+        parse!("[]", Ok(Json::Array(Vec::new())));
+        parse!("-1", Err(String::from("unexpected '-'")));
+        parse!("\"open", Err(String::from("unterminated string")));
+        parse!("{} x", Err(String::from("unexpected 'x' after the value")));
+        parse!("[1 2]", Err(String::from("expected ',' or ']'")));
+    }
+}
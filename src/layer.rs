@@ -0,0 +1,132 @@
+//! Middleware wrapped around asking a question.
+//!
+//! A `Layer` sees every question before it is asked and can
+//! answer it itself, change the answer, or simply observe it.
+//! This keeps cross cutting concerns such as logging, timing,
+//! caching or assuming "yes" out of the `Question` builder.
+
+use std::cell::Cell;
+
+use Answer;
+
+/// Middleware wrapped around asking a `Question`.
+///
+/// Layers are added with `Question::layer` and run in the
+/// order they were added, the first being the outermost. A
+/// layer asks the user by calling `next.ask()`, or answers on
+/// their behalf by returning without doing so.
+///
+/// # Examples
+///
+/// A layer logging every question and its answer.
+///
+/// ```no_run
+/// # use question::{Answer, Context, Layer, Next, Question};
+/// struct Log;
+///
+/// impl Layer for Log {
+///     fn ask(&self, context: &Context, next: Next) -> Option<Answer> {
+///         let answer = next.ask();
+///         eprintln!("{:?} => {:?}", context.question(), answer);
+///         answer
+///     }
+/// }
+///
+/// Question::new("Continue?").layer(Log).confirm();
+/// ```
+pub trait Layer {
+    /// Produce the answer to the question described by
+    /// `context`, usually by calling `next.ask()`.
+    fn ask(&self, context: &Context, next: Next) -> Option<Answer>;
+}
+
+/// What a `Layer` knows about the question being asked.
+pub struct Context<'a> {
+    question: &'a str,
+    default: Option<&'a Answer>,
+    yes_no: bool,
+    default_taken: Cell<bool>,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(question: &'a str, default: Option<&'a Answer>, yes_no: bool) -> Context<'a> {
+        Context {
+            question,
+            default,
+            yes_no,
+            default_taken: Cell::new(false),
+        }
+    }
+
+    /// The question as it was written.
+    pub fn question(&self) -> &str {
+        self.question
+    }
+
+    /// The default answer, if one has been set.
+    pub fn default(&self) -> Option<&Answer> {
+        self.default
+    }
+
+    /// The default answer, if one has been set, for a layer to
+    /// answer with. Unlike `default`, this marks the default as
+    /// taken, so `AskOutcome::default_used` reports it.
+    pub fn take_default(&self) -> Option<Answer> {
+        self.default_taken.set(self.default.is_some());
+        self.default.cloned()
+    }
+
+    /// Whether a layer took the default with `take_default`.
+    pub(crate) fn default_taken(&self) -> bool {
+        self.default_taken.get()
+    }
+
+    /// Whether this is a yes/no question.
+    pub fn is_yes_no(&self) -> bool {
+        self.yes_no
+    }
+}
+
+/// The rest of the layers, ending with actually asking the user.
+pub struct Next<'a> {
+    ask: &'a mut dyn FnMut() -> Option<Answer>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(ask: &'a mut dyn FnMut() -> Option<Answer>) -> Next<'a> {
+        Next { ask }
+    }
+
+    /// Ask the question through the remaining layers.
+    pub fn ask(self) -> Option<Answer> {
+        (self.ask)()
+    }
+}
+
+/// Answer every yes/no question with `Answer::YES` without
+/// asking, like the `--yes` flag of many tools. Any other
+/// question is asked as usual.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{AssumeYes, Question};
+/// let assume_yes = std::env::args().any(|arg| arg == "--yes");
+/// let mut question = Question::new("Continue?");
+/// if assume_yes {
+///     question.layer(AssumeYes);
+/// }
+/// question.confirm();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct AssumeYes;
+
+impl Layer for AssumeYes {
+    fn ask(&self, context: &Context, next: Next) -> Option<Answer> {
+        if context.is_yes_no() {
+            Some(Answer::YES)
+        } else {
+            next.ask()
+        }
+    }
+}
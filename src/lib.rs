@@ -11,12 +11,142 @@
 //! # use question::Question;
 //! Question::new("Do you want to continue?").confirm();
 //! ```
+//!
+//! # WebAssembly
+//!
+//! Questions read standard input and write standard output, so
+//! under WASI they work as they do natively. In the browser,
+//! where there is no standard input, set a `Prompter` with
+//! `Question::prompter` or `QuestionConfig::prompter` to show each
+//! question in a terminal emulator such as xterm.js and return
+//! what the user types. Retry delays are skipped there, since
+//! there is no clock to wait by.
 #![cfg_attr(feature = "strict", feature(plugin))]
 #![cfg_attr(feature = "strict", plugin(clippy))]
 #![cfg_attr(feature = "strict", deny(warnings))]
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "clap")]
+extern crate clap;
+#[cfg(all(feature = "term", unix))]
+extern crate libc;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+extern crate regex_lite;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "yaml")]
+extern crate serde_yaml;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "unicode")]
+extern crate unicode_normalization;
+#[cfg(feature = "unicode")]
+extern crate unicode_width;
+#[cfg(all(feature = "term", windows))]
+extern crate windows_sys;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+
+#[cfg(feature = "clap")]
+mod args;
+mod channel;
+mod config;
+#[cfg(feature = "chrono")]
+mod date;
+mod demo;
+mod duration;
+mod editor;
+mod error;
+mod external;
+#[cfg(feature = "serde")]
+mod fields;
+mod form;
+mod format;
+mod grid;
+mod history;
+mod json;
+mod layer;
+mod list;
+mod locale;
+mod map;
+mod outcome;
+mod parse;
+mod path;
+mod prompter;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+mod questionnaire;
+mod rank;
+mod remember;
+mod scale;
+mod secret;
+mod select;
+mod session;
+mod stepper;
+mod strength;
+mod tee;
+mod term;
+pub mod test;
+#[cfg(feature = "color")]
+mod theme;
+mod transcript;
+mod typed;
+mod unicode;
+mod validate;
+mod wrap;
+
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use editor::display_width;
+use history::History;
+use outcome::Tally;
+
+#[cfg(feature = "clap")]
+pub use args::fill_args;
+pub use channel::CancelToken;
+pub use config::{NonInteractive, QuestionConfig};
+pub use demo::Demo;
+pub use error::AskError;
+pub use form::{Answers, Changes, Form};
+pub use format::{DefaultFormatter, PromptFormatter};
+pub use layer::{AssumeYes, Context, Layer, Next};
+#[cfg(feature = "i18n")]
+pub use locale::{Locale, LocaleError};
+pub use outcome::AskOutcome;
+pub use path::PathQuestion;
+pub use prompter::{read_answer, JsonLines, Prompt, Prompter, Stdio};
+#[cfg(any(feature = "toml", feature = "yaml"))]
+pub use questionnaire::{DefinitionError, Questionnaire};
+pub use remember::{AnswerStore, FileStore, MemoryStore, Recall};
+pub use scale::ScaleQuestion;
+pub use secret::Secret;
+pub use select::SelectQuestion;
+pub use session::PromptSession;
+pub use stepper::StepperQuestion;
+pub use strength::Strength;
+pub use tee::Tee;
+use term::{Key, RawMode};
+#[cfg(feature = "color")]
+pub use theme::{Color, Style, Theme};
+pub use transcript::{Event, Transcript, REDACTED};
+pub use typed::TypedQuestion;
+#[cfg(feature = "unicode")]
+pub use unicode::Normalization;
+pub use validate::Format;
+
+/// How many times in a row input that is not an interactive
+/// terminal may give the same unacceptable response before a
+/// question stops asking with `AskError::NoInteractiveInput`.
+const STALLED_AFTER: u64 = 5;
 
 /// An `Answer` builder. Once a question has been formulated
 /// either `ask` or `confirm` may be used to get an answer.
@@ -48,15 +178,93 @@ where
     W: Write,
 {
     question: String,
-    prompt: String,
     default: Option<Answer>,
+    default_with: Option<Arc<dyn Fn() -> Answer + Send + Sync>>,
+    skip: Option<String>,
+    env: Option<String>,
+    env_read: bool,
+    back: Option<String>,
+    went_back: bool,
+    progress: Option<(usize, usize)>,
     clarification: Option<String>,
+    last_input: String,
+    help: Option<String>,
+    tags: Vec<String>,
     acceptable: Option<Vec<String>>,
     valid_responses: Option<HashMap<String, Answer>>,
     tries: Option<u64>,
     until_acceptable: bool,
+    required: bool,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    min_strength: Option<Strength>,
     show_defaults: bool,
+    hint: Option<Hint>,
+    map: Option<Mapper>,
+    on_invalid: Option<OnInvalid>,
+    before_prompt: Option<Hook>,
+    after_prompt: Option<Hook>,
+    /// Whether anything has been shown yet while the question is
+    /// asked, or `None` when it is not being asked.
+    shown: Option<bool>,
+    cancel: Option<CancelToken>,
+    session: Option<PromptSession>,
     yes_no: bool,
+    to_all: bool,
+    case_sensitive: Option<bool>,
+    accept_prefixes: bool,
+    suggest_on_typo: Option<usize>,
+    format: Option<Format>,
+    toggle: bool,
+    instant: bool,
+    prefill: bool,
+    multiline: Option<String>,
+    external_editor: bool,
+    editor_template: Option<String>,
+    allow_chars: Option<Arc<dyn Fn(char) -> bool + Send + Sync>>,
+    completions: Option<Completer>,
+    history: Option<Arc<Mutex<History>>>,
+    transcript: Option<Transcript>,
+    secret: bool,
+    dry_run: bool,
+    outcome: Option<AskOutcome>,
+    tally: Tally,
+    prompter: Option<Backend>,
+    phrase: Option<String>,
+    strict: bool,
+    error: Option<AskError>,
+    answer: Option<Answer>,
+    keys: bool,
+    tty: bool,
+    wrap: Option<usize>,
+    show_remaining_tries: bool,
+    retry_delay: Option<Duration>,
+    backoff: bool,
+    countdown: Option<Duration>,
+    counting: bool,
+    redraw: bool,
+    inline_errors: bool,
+    bell_on_prompt: bool,
+    bell_on_invalid: bool,
+    drawn: usize,
+    ended: bool,
+    on_eof: OnEof,
+    flush: Flush,
+    trim: Trim,
+    formatter: Arc<dyn PromptFormatter + Send + Sync>,
+    #[cfg(feature = "color")]
+    theme: Option<Theme>,
+    #[cfg(feature = "color")]
+    color: bool,
+    #[cfg(feature = "i18n")]
+    locale: Option<Locale>,
+    #[cfg(feature = "unicode")]
+    normalization: Option<Normalization>,
+    #[cfg(feature = "unicode")]
+    fold_diacritics: bool,
+    layers: Vec<Arc<dyn Layer + Send + Sync>>,
+    remember: Option<(String, Store)>,
+    recall: Recall,
     reader: R,
     writer: W,
 }
@@ -64,28 +272,177 @@ where
 impl Question<std::io::Stdin, std::io::Stdout> {
     /// Create a new `Question`.
     ///
+    /// The question may contain placeholders, filled in each time
+    /// it is shown: "{default}" becomes the default, "{choices}"
+    /// the acceptable responses, separated by commas, and
+    /// "{attempt}" the number of the attempt, counting from one.
+    ///
+    /// Any `QuestionConfig` installed is applied to it. When the
+    /// `QUESTION_PROTOCOL` environment variable is `json` the
+    /// question is asked with `JsonLines` rather than on the
+    /// terminal.
+    ///
     /// # Examples
     ///
     /// ```no_run
-    /// # use question::Question;
+    /// # use question::{Answer, Question};
     /// Question::new("What is your favorite color?").ask();
+    /// # let path = "config.toml";
+    /// Question::new(format!("Overwrite {}?", path)).confirm();
+    /// Question::new("Deploy to {default}?")
+    ///     .default(Answer::RESPONSE(String::from("staging")))
+    ///     .ask();
     /// ```
-    pub fn new(question: &str) -> Question<std::io::Stdin, std::io::Stdout> {
-        let question = question.to_string();
-        Question {
-            question: question.clone(),
-            prompt: question,
+    pub fn new<S: Into<String>>(question: S) -> Question<std::io::Stdin, std::io::Stdout> {
+        let prompter = protocol();
+        let mut new = Question {
+            question: question.into(),
             default: None,
+            default_with: None,
+            skip: None,
+            env: None,
+            env_read: false,
+            back: None,
+            went_back: false,
+            progress: None,
             acceptable: None,
             valid_responses: None,
             clarification: None,
+            last_input: String::new(),
+            help: None,
+            tags: Vec::new(),
             tries: None,
             until_acceptable: false,
+            required: false,
+            min_len: None,
+            max_len: None,
+            min_strength: None,
             show_defaults: false,
+            hint: None,
+            map: None,
+            on_invalid: None,
+            before_prompt: None,
+            after_prompt: None,
+            shown: None,
+            cancel: None,
+            session: None,
             yes_no: false,
+            to_all: false,
+            case_sensitive: None,
+            accept_prefixes: false,
+            suggest_on_typo: None,
+            format: None,
+            toggle: false,
+            instant: false,
+            prefill: false,
+            multiline: None,
+            external_editor: false,
+            editor_template: None,
+            allow_chars: None,
+            completions: None,
+            history: None,
+            transcript: None,
+            secret: false,
+            dry_run: false,
+            outcome: None,
+            tally: Tally::default(),
+            keys: prompter.is_none() && term::is_interactive(),
+            tty: term::stdin_is_terminal(),
+            prompter,
+            phrase: None,
+            strict: false,
+            error: None,
+            answer: None,
+            wrap: term::width(),
+            show_remaining_tries: false,
+            retry_delay: None,
+            backoff: false,
+            countdown: None,
+            counting: false,
+            redraw: false,
+            inline_errors: false,
+            bell_on_prompt: false,
+            bell_on_invalid: false,
+            drawn: 0,
+            ended: false,
+            on_eof: OnEof::Default,
+            flush: Flush::Prompt,
+            trim: Trim::Both,
+            formatter: Arc::new(DefaultFormatter),
+            #[cfg(feature = "color")]
+            theme: None,
+            #[cfg(feature = "color")]
+            color: theme::supports_color(),
+            #[cfg(feature = "i18n")]
+            locale: None,
+            #[cfg(feature = "unicode")]
+            normalization: None,
+            #[cfg(feature = "unicode")]
+            fold_diacritics: false,
+            layers: Vec::new(),
+            remember: None,
+            recall: Recall::Default,
             reader: std::io::stdin(),
             writer: std::io::stdout(),
-        }
+        };
+        config::configure(&mut new);
+        new
+    }
+
+    /// Show `message` and wait for the user to press any key. On
+    /// terminals that cannot be read one keystroke at a time (see
+    /// `toggle`) the user has to press enter instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// println!("Insert the next disk.");
+    /// Question::pause("Press any key to continue...");
+    /// ```
+    pub fn pause(message: &str) {
+        Question::new(message).wait_for_key();
+    }
+
+    /// Finish building the question, returning an owned copy of it
+    /// as it has been configured, so a question built in one
+    /// expression can be stored, returned or added to a `Form`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// # use std::io::{Stdin, Stdout};
+    /// fn deploy() -> Question<Stdin, Stdout> {
+    ///     Question::new("Deploy to production?")
+    ///         .yes_no()
+    ///         .default(Answer::NO)
+    ///         .build()
+    /// }
+    ///
+    /// let mut question = deploy();
+    /// question.ask();
+    /// ```
+    pub fn build(&mut self) -> Question<std::io::Stdin, std::io::Stdout> {
+        self.rebind(std::io::stdin(), std::io::stdout())
+    }
+
+    /// Finish building the question like `build`, but show it on
+    /// `writer` rather than stdout, such as stderr so prompts stay
+    /// out of piped output, or a `Tee` to keep an audit copy.
+    /// Interactive widgets are still used if stdin and stdout are a
+    /// terminal, and are drawn on `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let name = Question::new("Project name?")
+    ///     .write_to(std::io::stderr())
+    ///     .ask();
+    /// ```
+    pub fn write_to<W: Write>(&mut self, writer: W) -> Question<std::io::Stdin, W> {
+        self.rebind(std::io::stdin(), writer)
     }
 }
 
@@ -96,23 +453,197 @@ where
 {
     #[cfg(test)]
     pub fn with_cursor(question: &str, input: R, output: W) -> Question<R, W> {
-        let question = question.to_string();
         Question {
-            question: question.clone(),
-            prompt: question,
+            question: question.to_string(),
             default: None,
+            default_with: None,
+            skip: None,
+            env: None,
+            env_read: false,
+            back: None,
+            went_back: false,
+            progress: None,
             acceptable: None,
             valid_responses: None,
             clarification: None,
+            last_input: String::new(),
+            help: None,
+            tags: Vec::new(),
             tries: None,
             until_acceptable: false,
+            required: false,
+            min_len: None,
+            max_len: None,
+            min_strength: None,
             show_defaults: false,
+            hint: None,
+            map: None,
+            on_invalid: None,
+            before_prompt: None,
+            after_prompt: None,
+            shown: None,
+            cancel: None,
+            session: None,
             yes_no: false,
+            to_all: false,
+            case_sensitive: None,
+            accept_prefixes: false,
+            suggest_on_typo: None,
+            format: None,
+            toggle: false,
+            instant: false,
+            prefill: false,
+            multiline: None,
+            external_editor: false,
+            editor_template: None,
+            allow_chars: None,
+            completions: None,
+            history: None,
+            transcript: None,
+            secret: false,
+            dry_run: false,
+            outcome: None,
+            tally: Tally::default(),
+            prompter: None,
+            phrase: None,
+            strict: false,
+            error: None,
+            answer: None,
+            keys: false,
+            tty: false,
+            wrap: None,
+            show_remaining_tries: false,
+            retry_delay: None,
+            backoff: false,
+            countdown: None,
+            counting: false,
+            redraw: false,
+            inline_errors: false,
+            bell_on_prompt: false,
+            bell_on_invalid: false,
+            drawn: 0,
+            ended: false,
+            on_eof: OnEof::Default,
+            flush: Flush::Prompt,
+            trim: Trim::Both,
+            formatter: Arc::new(DefaultFormatter),
+            #[cfg(feature = "color")]
+            theme: None,
+            #[cfg(feature = "color")]
+            color: false,
+            #[cfg(feature = "i18n")]
+            locale: None,
+            #[cfg(feature = "unicode")]
+            normalization: None,
+            #[cfg(feature = "unicode")]
+            fold_diacritics: false,
+            layers: Vec::new(),
+            remember: None,
+            recall: Recall::Default,
             reader: input,
             writer: output,
         }
     }
 
+    /// Copy the question, as it has been built, onto other input
+    /// and output.
+    fn rebind<R2, W2>(&self, reader: R2, writer: W2) -> Question<R2, W2>
+    where
+        R2: Read,
+        W2: Write,
+    {
+        Question {
+            question: self.question.clone(),
+            default: self.default.clone(),
+            default_with: self.default_with.clone(),
+            skip: self.skip.clone(),
+            env: self.env.clone(),
+            env_read: false,
+            back: self.back.clone(),
+            went_back: false,
+            progress: None,
+            acceptable: self.acceptable.clone(),
+            valid_responses: self.valid_responses.clone(),
+            clarification: self.clarification.clone(),
+            last_input: String::new(),
+            help: self.help.clone(),
+            tags: self.tags.clone(),
+            tries: self.tries,
+            until_acceptable: self.until_acceptable,
+            required: self.required,
+            min_len: self.min_len,
+            max_len: self.max_len,
+            min_strength: self.min_strength,
+            show_defaults: self.show_defaults,
+            hint: self.hint.clone(),
+            map: self.map.clone(),
+            on_invalid: self.on_invalid.clone(),
+            before_prompt: self.before_prompt.clone(),
+            after_prompt: self.after_prompt.clone(),
+            shown: None,
+            cancel: self.cancel.clone(),
+            session: self.session.clone(),
+            yes_no: self.yes_no,
+            to_all: self.to_all,
+            case_sensitive: self.case_sensitive,
+            accept_prefixes: self.accept_prefixes,
+            suggest_on_typo: self.suggest_on_typo,
+            format: self.format,
+            toggle: self.toggle,
+            instant: self.instant,
+            prefill: self.prefill,
+            multiline: self.multiline.clone(),
+            external_editor: self.external_editor,
+            editor_template: self.editor_template.clone(),
+            allow_chars: self.allow_chars.clone(),
+            completions: self.completions.clone(),
+            history: self.history.clone(),
+            transcript: self.transcript.clone(),
+            secret: self.secret,
+            dry_run: self.dry_run,
+            outcome: None,
+            tally: Tally::default(),
+            prompter: self.prompter.clone(),
+            phrase: self.phrase.clone(),
+            strict: self.strict,
+            error: None,
+            answer: None,
+            keys: self.keys,
+            tty: self.tty,
+            wrap: self.wrap,
+            show_remaining_tries: self.show_remaining_tries,
+            retry_delay: self.retry_delay,
+            backoff: self.backoff,
+            countdown: self.countdown,
+            counting: false,
+            redraw: self.redraw,
+            inline_errors: self.inline_errors,
+            bell_on_prompt: self.bell_on_prompt,
+            bell_on_invalid: self.bell_on_invalid,
+            drawn: 0,
+            ended: false,
+            on_eof: self.on_eof,
+            flush: self.flush,
+            trim: self.trim,
+            formatter: self.formatter.clone(),
+            #[cfg(feature = "color")]
+            theme: self.theme,
+            #[cfg(feature = "color")]
+            color: self.color,
+            #[cfg(feature = "i18n")]
+            locale: self.locale.clone(),
+            #[cfg(feature = "unicode")]
+            normalization: self.normalization,
+            #[cfg(feature = "unicode")]
+            fold_diacritics: self.fold_diacritics,
+            layers: self.layers.clone(),
+            remember: self.remember.clone(),
+            recall: self.recall,
+            reader,
+            writer,
+        }
+    }
+
     /// Add a single acceptable response to the list.
     ///
     /// # Examples
@@ -128,15 +659,11 @@ where
     ///     .until_acceptable()
     ///     .ask();
     /// ```
-    pub fn accept(&mut self, accepted: &str) -> &mut Question<R, W> {
-        let accepted = accepted.to_string();
+    pub fn accept<S: Into<String>>(&mut self, accepted: S) -> &mut Question<R, W> {
+        let accepted = accepted.into();
         match self.acceptable {
             Some(ref mut vec) => vec.push(accepted),
-            None => {
-                let mut vec = Vec::new();
-                vec.push(accepted);
-                self.acceptable = Some(vec);
-            }
+            None => self.acceptable = Some(vec![accepted]),
         }
         self
     }
@@ -155,8 +682,8 @@ where
     ///     .until_acceptable()
     ///     .ask();
     /// ```
-    pub fn acceptable(&mut self, accepted: Vec<&str>) -> &mut Question<R, W> {
-        let mut accepted = accepted.into_iter().map(|x| x.into()).collect();
+    pub fn acceptable<S: Into<String>>(&mut self, accepted: Vec<S>) -> &mut Question<R, W> {
+        let mut accepted = accepted.into_iter().map(S::into).collect();
         match self.acceptable {
             Some(ref mut vec) => vec.append(&mut accepted),
             None => self.acceptable = Some(accepted),
@@ -164,6 +691,54 @@ where
         self
     }
 
+    /// Reject blank responses when there is no default, asking
+    /// again with a message saying a response is required. The
+    /// question is asked until one is given, or as set by `tries`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Project name?").required().ask();
+    /// ```
+    pub fn required(&mut self) -> &mut Question<R, W> {
+        self.required = true;
+        self
+    }
+
+    /// Reject responses shorter than `min` characters, asking
+    /// again with a message saying how long they must be. The
+    /// question is asked until a response is long enough, or as set
+    /// by `tries`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Passphrase?")
+    ///     .secret()
+    ///     .min_len(12)
+    ///     .ask();
+    /// ```
+    pub fn min_len(&mut self, min: usize) -> &mut Question<R, W> {
+        self.min_len = Some(min);
+        self
+    }
+
+    /// Reject responses longer than `max` characters. See
+    /// `min_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Username?").min_len(3).max_len(16).ask();
+    /// ```
+    pub fn max_len(&mut self, max: usize) -> &mut Question<R, W> {
+        self.max_len = Some(max);
+        self
+    }
+
     /// Shorthand the most common case of a yes/no question.
     ///
     /// # Examples
@@ -181,585 +756,4526 @@ where
     /// ```
     pub fn yes_no(&mut self) -> &mut Question<R, W> {
         self.yes_no = true;
-        let response_keys = vec![
-            String::from("yes"),
-            String::from("y"),
-            String::from("no"),
-            String::from("n"),
-        ];
+        self.map_response("yes", Answer::YES)
+            .map_response("y", Answer::YES)
+            .map_response("no", Answer::NO)
+            .map_response("n", Answer::NO);
+        #[cfg(feature = "i18n")]
+        self.localize_yes_no();
+        self
+    }
 
-        let response_values = vec![Answer::YES, Answer::YES, Answer::NO, Answer::NO];
-        let mut valid_responses: HashMap<String, Answer> = response_keys
-            .into_iter()
-            .zip(response_values.into_iter())
-            .collect();
+    /// A yes/no question for each item of a batch, such as files
+    /// to overwrite, that also accepts "a" or "all" as `Answer::ALL`
+    /// and "q" or "never" as `Answer::NEVER`, so the caller can stop
+    /// asking about the remaining items. The hint shows "a/q" after
+    /// "y/n".
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// # let files = ["a.txt", "b.txt"];
+    /// let mut all = None;
+    /// for file in &files {
+    ///     let overwrite = match all {
+    ///         Some(overwrite) => overwrite,
+    ///         None => match Question::new(&format!("Overwrite {}?", file))
+    ///             .yes_no_all()
+    ///             .show_defaults()
+    ///             .confirm()
+    ///         {
+    ///             Answer::ALL => *all.insert(true),
+    ///             Answer::NEVER => *all.insert(false),
+    ///             answer => answer.is_yes(),
+    ///         },
+    ///     };
+    ///     // overwrite the file if `overwrite` is true
+    /// }
+    /// ```
+    pub fn yes_no_all(&mut self) -> &mut Question<R, W> {
+        self.to_all = true;
+        self.yes_no()
+            .map_response("all", Answer::ALL)
+            .map_response("a", Answer::ALL)
+            .map_response("never", Answer::NEVER)
+            .map_response("q", Answer::NEVER)
+    }
 
-        match self.valid_responses {
-            Some(ref mut hashmap) => for (k, v) in valid_responses.drain() {
-                hashmap.insert(k, v);
-            },
-            None => self.valid_responses = Some(valid_responses),
+    /// Accept each of `words` as well as "yes" and "y" as an answer
+    /// of `Answer::YES`, so yes/no questions and `confirm` work in
+    /// other languages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Fortfahren? (ja/nein)")
+    ///     .yes_words(&["ja", "j"])
+    ///     .no_words(&["nein"])
+    ///     .until_acceptable()
+    ///     .confirm();
+    /// ```
+    pub fn yes_words(&mut self, words: &[&str]) -> &mut Question<R, W> {
+        for word in words {
+            self.map_response(word, Answer::YES);
         }
         self
     }
 
-    /// Set a maximum number of attempts to try and get an
-    /// acceptable answer from the user.
+    /// Accept each of `words` as well as "no" and "n" as an answer
+    /// of `Answer::NO`. See `yes_words`.
+    pub fn no_words(&mut self, words: &[&str]) -> &mut Question<R, W> {
+        for word in words {
+            self.map_response(word, Answer::NO);
+        }
+        self
+    }
+
+    /// Map a response to the answer that will be returned when
+    /// the user enters it. Several responses may map to the same
+    /// answer, and mapped responses are validated in the same way
+    /// as those added by `yes_no`.
     ///
     /// # Examples
     ///
-    /// The following will ask the user if they would like
-    /// to continue until either "y", "n", "yes", or "no",
-    /// is entered, or until they have entered 3 invalid
-    /// responses.
+    /// The following will ask the user whether to overwrite a
+    /// file, accepting "a" or "always" as a canonical "always"
+    /// answer alongside the usual yes/no responses.
     ///
     /// ```no_run
-    /// # use question::Question;
-    /// Question::new("Do you want to continue?")
+    /// # use question::{Question, Answer};
+    /// let always = Answer::RESPONSE(String::from("always"));
+    /// let never = Answer::RESPONSE(String::from("never"));
+    /// Question::new("Overwrite? [y]es / [n]o / [a]lways / ne[v]er")
     ///     .yes_no()
-    ///     .tries(3)
+    ///     .map_response("a", always.clone())
+    ///     .map_response("always", always)
+    ///     .map_response("v", never.clone())
+    ///     .map_response("never", never)
+    ///     .until_acceptable()
     ///     .ask();
     /// ```
-    pub fn tries(&mut self, tries: u64) -> &mut Question<R, W> {
-        match tries {
-            0 => self.until_acceptable = true,
-            1 => return self,
-            _ => self.tries = Some(tries),
+    pub fn map_response(&mut self, response: &str, answer: Answer) -> &mut Question<R, W> {
+        let response = response.to_string();
+        match self.valid_responses {
+            Some(ref mut hashmap) => {
+                hashmap.insert(response, answer);
+            }
+            None => {
+                let mut hashmap = HashMap::new();
+                hashmap.insert(response, answer);
+                self.valid_responses = Some(hashmap);
+            }
         }
         self
     }
 
-    /// Never stop asking until the user provides an acceptable
-    /// answer.
+    /// Match responses exactly, so that for example "Y" and "y"
+    /// can be mapped to different answers.
     ///
     /// # Examples
     ///
-    /// The following will ask the user if they would like
-    /// to continue until either "y", "n", "yes", or "no",
-    /// is entered.
-    ///
     /// ```no_run
-    /// # use question::Question;
-    /// Question::new("Do you want to continue?")
+    /// # use question::{Question, Answer};
+    /// Question::new("Overwrite? (y/n, Y for all)")
     ///     .yes_no()
+    ///     .map_response("Y", Answer::RESPONSE(String::from("all")))
+    ///     .case_sensitive()
     ///     .until_acceptable()
     ///     .ask();
     /// ```
-    pub fn until_acceptable(&mut self) -> &mut Question<R, W> {
-        self.until_acceptable = true;
+    pub fn case_sensitive(&mut self) -> &mut Question<R, W> {
+        self.case_sensitive = Some(true);
         self
     }
 
-    /// Show the default response to the user that will be
-    /// submitted if they enter an empty string `"\n"`.
+    /// Ignore case when matching responses. This is the default,
+    /// except for `confirm_phrase`.
     ///
     /// # Examples
     ///
-    /// The following will ask the user if they would like
-    /// to continue until either "y", "n", "yes", or "no",
-    /// is entered. Since no default was set the prompt will
-    /// be displayed with `(y/n)` after it, and if the user
-    /// enters an empty string no answer will be returned
-    /// and they will be re-prompted.
+    /// The following accepts "Staging", "STAGING", and so on,
+    /// returning `Answer::RESPONSE("staging")`.
     ///
     /// ```no_run
     /// # use question::Question;
-    /// Question::new("Do you want to continue?")
-    ///     .yes_no()
+    /// Question::new("Environment?")
+    ///     .acceptable(vec!["staging", "production"])
+    ///     .case_insensitive()
     ///     .until_acceptable()
-    ///     .show_defaults()
     ///     .ask();
     /// ```
-    ///
-    /// If either `Answer::YES` or `Answer::NO` have been set
-    /// as default then the prompt will be shown with that
-    /// entry capitalized, either `(Y/n)` or `(y/N)`.
-    pub fn show_defaults(&mut self) -> &mut Question<R, W> {
-        self.show_defaults = true;
+    pub fn case_insensitive(&mut self) -> &mut Question<R, W> {
+        self.case_sensitive = Some(false);
         self
     }
 
-    /// Provide a default answer.
+    /// Accept any prefix of an acceptable response that is not
+    /// shared with another one, so "ins" is taken as "install".
+    /// If several responses share the prefix the user is asked
+    /// again, and shown the responses they might have meant.
     ///
     /// # Examples
     ///
-    /// The following will ask the user if they would like
-    /// to continue until either "y", "n", "yes", "no", or
-    /// "" an empty string is entered.  If an empty string
-    /// is entered `Answer::YES` will be returned.
-    ///
     /// ```no_run
-    /// # use question::{Question, Answer};
-    /// Question::new("Do you want to continue?")
-    ///     .yes_no()
+    /// # use question::Question;
+    /// Question::new("What next? (install/info/quit)")
+    ///     .acceptable(vec!["install", "info", "quit"])
+    ///     .accept_prefixes()
     ///     .until_acceptable()
-    ///     .default(Answer::YES)
-    ///     .show_defaults()
     ///     .ask();
     /// ```
-    pub fn default(&mut self, answer: Answer) -> &mut Question<R, W> {
-        self.default = Some(answer);
+    pub fn accept_prefixes(&mut self) -> &mut Question<R, W> {
+        self.accept_prefixes = true;
         self
     }
 
-    /// Provide a clarification to be shown if the user does
-    /// not enter an acceptable answer on the first try.
+    /// When a response is not acceptable, suggest the closest
+    /// acceptable responses within `max_distance` edits of it
+    /// when asking again.
     ///
     /// # Examples
     ///
-    /// The following will ask the user if they would like
-    /// to continue until either "y", "n", "yes", "no", or
-    /// "" an empty string is entered.  If an empty string
-    /// is entered `Answer::YES` will be returned. If the
-    /// user does not enter a valid response on the first
-    /// attempt, the clarification will be added to the
-    /// prompt.
+    /// If the user enters "stagign" they are asked again with
+    /// "Did you mean 'staging'?" shown above the question.
     ///
-    /// > Please enter either 'yes' or 'no'
-    /// > Do you want to continue? (Y/n)
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Environment?")
+    ///     .acceptable(vec!["staging", "production"])
+    ///     .suggest_on_typo(2)
+    ///     .until_acceptable()
+    ///     .ask();
+    /// ```
+    pub fn suggest_on_typo(&mut self, max_distance: usize) -> &mut Question<R, W> {
+        self.suggest_on_typo = Some(max_distance);
+        self
+    }
+
+    /// Only accept responses in a common `format`, such as an
+    /// email address, asking again as set by `tries` or
+    /// `until_acceptable` when a response is not.
+    ///
+    /// # Examples
     ///
     /// ```no_run
-    /// # use question::{Question, Answer};
-    /// Question::new("Do you want to continue?")
-    ///     .yes_no()
+    /// # use question::{Format, Question};
+    /// Question::new("Email?")
+    ///     .format(Format::Email)
     ///     .until_acceptable()
-    ///     .default(Answer::YES)
-    ///     .show_defaults()
-    ///     .clarification("Please enter either 'yes' or 'no'\n")
     ///     .ask();
     /// ```
-    pub fn clarification(&mut self, c: &str) -> &mut Question<R, W> {
-        self.clarification = Some(c.into());
+    pub fn format(&mut self, format: Format) -> &mut Question<R, W> {
+        self.format = Some(format);
         self
     }
 
-    /// Ask the user a question exactly as it has been built.
+    /// Map responses to values of any type instead of to an
+    /// `Answer`. See `TypedQuestion` for details.
     ///
     /// # Examples
     ///
-    /// The following will return whatever the user enters
-    /// as an `Answer::RESPONSE(String)`.
+    /// ```no_run
+    /// # use question::Question;
+    /// let workers: Option<u32> = Question::new("How many workers? (1/2/4)")
+    ///     .until_acceptable()
+    ///     .typed()
+    ///     .map_response("1", 1)
+    ///     .map_response("2", 2)
+    ///     .map_response("4", 4)
+    ///     .ask();
+    /// ```
+    pub fn typed<T: Clone>(&mut self) -> TypedQuestion<'_, T, R, W> {
+        TypedQuestion::new(self)
+    }
+
+    /// Ask for a path on the filesystem instead of an
+    /// `Answer`. See `PathQuestion` for details.
+    ///
+    /// # Examples
     ///
     /// ```no_run
     /// # use question::Question;
-    /// Question::new("What is your favorite color?").ask();
+    /// let prefix = Question::new("Install to?")
+    ///     .until_acceptable()
+    ///     .path()
+    ///     .must_be_dir()
+    ///     .create_if_missing()
+    ///     .ask();
     /// ```
-    pub fn ask(&mut self) -> Option<Answer> {
-        self.build_prompt();
-        if self.until_acceptable {
-            return Some(self.until_valid());
-        }
-        if self.tries.is_some() {
-            return self.max_tries();
-        }
-        match self.get_response() {
-            Ok(answer) => Some(answer),
-            Err(_) => None,
-        }
+    pub fn path(&mut self) -> PathQuestion<'_, R, W> {
+        PathQuestion::new(self)
     }
 
-    /// Ask a user a yes/no question until an acceptable
-    /// response is given.
+    /// Ask for a point on a numeric scale, such as a rating
+    /// from 1 to 5. See `ScaleQuestion` for details.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use question::Question;
-    /// Question::new("Continue?").confirm();
+    /// let rating = Question::new("How likely are you to recommend us?")
+    ///     .until_acceptable()
+    ///     .scale(0..=10)
+    ///     .ask();
     /// ```
-    pub fn confirm(&mut self) -> Answer {
-        self.yes_no();
-        self.build_prompt();
-        self.until_valid()
+    pub fn scale(&mut self, range: std::ops::RangeInclusive<i64>) -> ScaleQuestion<'_, R, W> {
+        ScaleQuestion::new(self, range)
     }
 
-    fn get_response(&mut self) -> Result<Answer, std::io::Error> {
-        let prompt = self.prompt.clone();
-        match self.prompt_user(&prompt) {
-            Ok(ref answer) if (self.default != None) && answer == "" => {
-                Ok(self.default.clone().unwrap())
-            }
-            Ok(answer) => Ok(Answer::RESPONSE(answer)),
-            Err(e) => Err(e),
-        }
+    /// Ask for a whole number in `range`, stepped up and down with
+    /// the arrow keys on interactive terminals. See
+    /// `StepperQuestion` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let replicas = Question::new("Replicas?")
+    ///     .stepper(0..=12)
+    ///     .step(3)
+    ///     .ask();
+    /// ```
+    pub fn stepper(&mut self, range: std::ops::RangeInclusive<i64>) -> StepperQuestion<'_, R, W> {
+        StepperQuestion::new(self, range)
     }
 
-    fn get_valid_response(&mut self) -> Option<Answer> {
-        let prompt = self.prompt.clone();
-        let valid_responses = match self.valid_responses.clone() {
-            Some(thing) => thing,
-            None => panic!(),
-        };
-        if let Ok(response) = self.prompt_user(&prompt) {
-            for key in valid_responses.keys() {
-                if *response.trim().to_lowercase() == *key {
-                    return Some(valid_responses[key].clone());
-                }
-                if let Some(default) = self.default.clone() {
-                    if response == "" {
-                        return Some(default);
-                    }
-                }
-            }
-        }
-        None
+    /// Ask the user to choose one of `choices`. See
+    /// `SelectQuestion` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let environment = Question::new("Deploy to?")
+    ///     .select(&["staging", "production"])
+    ///     .ask();
+    /// ```
+    pub fn select(&mut self, choices: &[&str]) -> SelectQuestion<'_, R, W> {
+        SelectQuestion::new(self, choices)
     }
 
-    fn get_acceptable_response(&mut self) -> Option<Answer> {
-        let prompt = self.prompt.clone();
-        let acceptable_responses = match self.acceptable.clone() {
-            Some(thing) => thing,
-            None => panic!(),
-        };
-        if let Ok(response) = self.prompt_user(&prompt) {
-            for acceptable_response in acceptable_responses {
-                if *response.trim().to_lowercase() == acceptable_response {
-                    return Some(Answer::RESPONSE(acceptable_response.clone()));
-                }
-                if let Some(default) = self.default.clone() {
-                    if response == "" {
-                        return Some(default);
-                    }
-                }
-            }
-        }
-        None
+    /// Render a yes/no question as a pair of buttons that are
+    /// toggled with the arrow keys and chosen with enter.
+    ///
+    /// This requires the `term` feature and an interactive
+    /// terminal. Otherwise, such as on a dumb terminal or when
+    /// input is piped, the question falls back to typed input.
+    ///
+    /// # Examples
+    ///
+    /// The following starts with "No" highlighted since it is
+    /// the default.
+    ///
+    /// > Do you want to continue?  Yes  [ No ]
+    ///
+    /// ```no_run
+    /// # use question::{Question, Answer};
+    /// Question::new("Do you want to continue?")
+    ///     .default(Answer::NO)
+    ///     .toggle()
+    ///     .confirm();
+    /// ```
+    pub fn toggle(&mut self) -> &mut Question<R, W> {
+        self.toggle = true;
+        self
     }
 
-    fn max_tries(&mut self) -> Option<Answer> {
-        let mut attempts = 0;
-        while attempts < self.tries.unwrap() {
-            match self.get_valid_response() {
-                Some(answer) => return Some(answer),
+    /// Answer a yes/no question as soon as "y" or "n" is pressed,
+    /// without waiting for enter, which answers with the default
+    /// if there is one. Like `toggle` this needs an interactive
+    /// terminal and otherwise falls back to typed input.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// Question::new("Proceed with installation?")
+    ///     .default(Answer::YES)
+    ///     .show_defaults()
+    ///     .instant()
+    ///     .confirm();
+    /// ```
+    pub fn instant(&mut self) -> &mut Question<R, W> {
+        self.instant = true;
+        self
+    }
+
+    /// Choose when the output is flushed. Every prompt, and
+    /// every frame of an interactive widget, is rendered in
+    /// full before being written in one go, so slow remote
+    /// terminals never see half drawn output.
+    ///
+    /// # Examples
+    ///
+    /// The following leaves flushing entirely to the caller.
+    ///
+    /// ```no_run
+    /// # use question::{Question, Flush};
+    /// Question::new("Do you want to continue?")
+    ///     .flush(Flush::Manual)
+    ///     .confirm();
+    /// ```
+    pub fn flush(&mut self, flush: Flush) -> &mut Question<R, W> {
+        self.flush = flush;
+        self
+    }
+
+    /// Choose which whitespace is trimmed from responses. Both
+    /// ends are trimmed by default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Question, Trim};
+    /// Question::new("Line to append?")
+    ///     .trim(Trim::End)
+    ///     .ask();
+    /// ```
+    pub fn trim(&mut self, trim: Trim) -> &mut Question<R, W> {
+        self.trim = trim;
+        self
+    }
+
+    /// Keep any whitespace around responses, such as the
+    /// indentation of a snippet or spaces in a password, removing
+    /// only the line ending. A response of only spaces is then not
+    /// empty, so it does not take the default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Passphrase?")
+    ///     .secret()
+    ///     .preserve_whitespace()
+    ///     .ask();
+    /// ```
+    pub fn preserve_whitespace(&mut self) -> &mut Question<R, W> {
+        self.trim(Trim::LineEnding)
+    }
+
+    /// Wrap the question to `width` columns, indenting every line
+    /// after the first, so the response is always typed on the
+    /// last line. With the `term` feature the width of the
+    /// terminal is used by default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Would you like to send anonymous crash reports so we can fix problems faster?")
+    ///     .wrap(40)
+    ///     .ask();
+    /// ```
+    pub fn wrap(&mut self, width: usize) -> &mut Question<R, W> {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// When asking again after a response was not accepted, clear
+    /// the previous prompt and response and draw the new prompt,
+    /// with its clarification, in their place. Otherwise every
+    /// attempt adds more lines to the terminal. Only done on an
+    /// interactive terminal (see `toggle`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .clarification("Please enter yes or no")
+    ///     .redraw()
+    ///     .ask();
+    /// ```
+    pub fn redraw(&mut self) -> &mut Question<R, W> {
+        self.redraw = true;
+        self
+    }
+
+    /// When asking again after a response was not accepted, show
+    /// the clarification and reason on the lines below the prompt,
+    /// replacing those shown for the previous attempt, instead of
+    /// above a new copy of the prompt. The previous prompt is
+    /// redrawn in place, as with `redraw`, and the errors are
+    /// cleared once a response is entered. Only done on an
+    /// interactive terminal (see `toggle`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .clarification("Please enter yes or no")
+    ///     .inline_errors()
+    ///     .ask();
+    /// ```
+    pub fn inline_errors(&mut self) -> &mut Question<R, W> {
+        self.inline_errors = true;
+        self
+    }
+
+    /// Ring the terminal bell when the question is first shown, so
+    /// an operator who has switched away from a long running job
+    /// notices it is waiting for them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Migration finished. Switch traffic over?")
+    ///     .yes_no()
+    ///     .bell_on_prompt()
+    ///     .confirm();
+    /// ```
+    pub fn bell_on_prompt(&mut self) -> &mut Question<R, W> {
+        self.bell_on_prompt = true;
+        self
+    }
+
+    /// Ring the terminal bell each time a response is not accepted
+    /// and the question is asked again.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .bell_on_invalid()
+    ///     .ask();
+    /// ```
+    pub fn bell_on_invalid(&mut self) -> &mut Question<R, W> {
+        self.bell_on_invalid = true;
+        self
+    }
+
+    /// Render prompts with `formatter` instead of the built in
+    /// look. See `PromptFormatter`.
+    pub fn formatter<F>(&mut self, formatter: F) -> &mut Question<R, W>
+    where
+        F: PromptFormatter + Send + Sync + 'static,
+    {
+        self.formatter = Arc::new(formatter);
+        self
+    }
+
+    /// Wrap asking the question in a `Layer`. Layers run in the
+    /// order they were added, so the first layer added sees the
+    /// question first and the answer last.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{AssumeYes, Question};
+    /// Question::new("Do you want to continue?")
+    ///     .layer(AssumeYes)
+    ///     .confirm();
+    /// ```
+    pub fn layer<L>(&mut self, layer: L) -> &mut Question<R, W>
+    where
+        L: Layer + Send + Sync + 'static,
+    {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Show the question and read the response through `prompter`
+    /// rather than the question's reader and writer. See
+    /// `Prompter` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Question, Stdio};
+    /// Question::new("Name?").prompter(Stdio).ask();
+    /// ```
+    pub fn prompter<P>(&mut self, prompter: P) -> &mut Question<R, W>
+    where
+        P: Prompter + Send + 'static,
+    {
+        self.prompter = Some(Arc::new(Mutex::new(prompter)));
+        self.keys = false;
+        self
+    }
+
+    /// Preview the question rather than asking it: the question
+    /// is shown with the default it would take, which is returned
+    /// without reading any input. A question with no default is
+    /// shown with "(no default)" and `try_ask` fails with
+    /// `AskError::NoDefault`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+    /// let mut question = Question::new("Region?");
+    /// question.default(Answer::RESPONSE(String::from("eu-west-1")));
+    /// if dry_run {
+    ///     question.dry_run();
+    /// }
+    /// question.ask();
+    /// ```
+    pub fn dry_run(&mut self) -> &mut Question<R, W> {
+        self.dry_run = true;
+        self
+    }
+
+    /// Set a maximum number of attempts to try and get an
+    /// acceptable answer from the user.
+    ///
+    /// # Examples
+    ///
+    /// The following will ask the user if they would like
+    /// to continue until either "y", "n", "yes", or "no",
+    /// is entered, or until they have entered 3 invalid
+    /// responses.
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Do you want to continue?")
+    ///     .yes_no()
+    ///     .tries(3)
+    ///     .ask();
+    /// ```
+    pub fn tries(&mut self, tries: u64) -> &mut Question<R, W> {
+        match tries {
+            0 => self.until_acceptable = true,
+            1 => return self,
+            _ => self.tries = Some(tries),
+        }
+        self
+    }
+
+    /// After a response is not accepted, tell the user how many
+    /// of the `tries` remain, such as "2 attempts left", alongside
+    /// the clarification.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Continue?")
+    ///     .yes_no()
+    ///     .tries(3)
+    ///     .clarification("Please enter yes or no")
+    ///     .show_remaining_tries()
+    ///     .ask();
+    /// ```
+    pub fn show_remaining_tries(&mut self) -> &mut Question<R, W> {
+        self.show_remaining_tries = true;
+        self
+    }
+
+    /// Wait for `delay` before asking again after a response is
+    /// not accepted, rate limiting attempts at questions that gate
+    /// something sensitive, such as a passphrase.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// # use std::time::Duration;
+    /// Question::new("Passphrase?")
+    ///     .accept("correct horse battery staple")
+    ///     .tries(5)
+    ///     .retry_delay(Duration::from_secs(1))
+    ///     .exponential_backoff()
+    ///     .ask();
+    /// ```
+    pub fn retry_delay(&mut self, delay: Duration) -> &mut Question<R, W> {
+        self.retry_delay = Some(delay);
+        self
+    }
+
+    /// Double the `retry_delay` after every response that is not
+    /// accepted.
+    pub fn exponential_backoff(&mut self) -> &mut Question<R, W> {
+        self.backoff = true;
+        self
+    }
+
+    /// Count down from `timeout` while waiting for a response on
+    /// an interactive terminal (see `toggle`), answering with the
+    /// default once it runs out. Pressing any key stops the
+    /// countdown so the user can answer in their own time. The
+    /// seconds left replace "{seconds}" in the question, or are
+    /// shown before it.
+    ///
+    /// Without a default the question ends unanswered when it
+    /// runs out: `ask` returns
+    /// `None`, `confirm` returns `Answer::CANCELLED` and `try_ask`
+    /// on a `strict` question fails with `AskError::TimedOut`.
+    ///
+    /// Elsewhere the question is asked as usual, with "{seconds}"
+    /// showing the whole timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// # use std::time::Duration;
+    /// Question::new("Rebooting in {seconds} seconds, continue?")
+    ///     .default(Answer::YES)
+    ///     .show_defaults()
+    ///     .countdown(Duration::from_secs(10))
+    ///     .confirm();
+    /// ```
+    pub fn countdown(&mut self, timeout: Duration) -> &mut Question<R, W> {
+        self.countdown = Some(timeout);
+        self
+    }
+
+    /// Never stop asking until the user provides an acceptable
+    /// answer. Without any acceptable responses, format or other
+    /// constraint every response is acceptable, so the question is
+    /// asked once. Asking also stops if input ends, or if input that
+    /// is not an interactive terminal gives the same unacceptable
+    /// response five times in a row, as with `yes maybe | program`,
+    /// which `try_ask` reports as `AskError::NoInteractiveInput`.
+    ///
+    /// # Examples
+    ///
+    /// The following will ask the user if they would like
+    /// to continue until either "y", "n", "yes", or "no",
+    /// is entered.
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Do you want to continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .ask();
+    /// ```
+    pub fn until_acceptable(&mut self) -> &mut Question<R, W> {
+        self.until_acceptable = true;
+        self
+    }
+
+    /// Show the default response to the user that will be
+    /// submitted if they enter an empty string `"\n"`.
+    ///
+    /// # Examples
+    ///
+    /// The following will ask the user if they would like
+    /// to continue until either "y", "n", "yes", or "no",
+    /// is entered. Since no default was set the prompt will
+    /// be displayed with `(y/n)` after it, and if the user
+    /// enters an empty string no answer will be returned
+    /// and they will be re-prompted.
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Do you want to continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .show_defaults()
+    ///     .ask();
+    /// ```
+    ///
+    /// If either `Answer::YES` or `Answer::NO` have been set
+    /// as default then the prompt will be shown with that
+    /// entry capitalized, either `(Y/n)` or `(y/N)`.
+    pub fn show_defaults(&mut self) -> &mut Question<R, W> {
+        self.show_defaults = true;
+        self
+    }
+
+    /// Show the default as `format` when `show_defaults` is set,
+    /// with "{default}" replaced by the built in hint, such as
+    /// "Y/n" or the default response. The format includes any
+    /// space after the question.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// // Shows "Continue? [Y/n] "
+    /// Question::new("Continue?")
+    ///     .default(Answer::YES)
+    ///     .show_defaults()
+    ///     .hint_format(" [{default}]")
+    ///     .confirm();
+    /// ```
+    pub fn hint_format(&mut self, format: &str) -> &mut Question<R, W> {
+        let format = format.to_string();
+        self.hint_with(move |hint| format.replace("{default}", hint))
+    }
+
+    /// Show the default as `hint` renders it when `show_defaults`
+    /// is set, given the built in hint, such as "Y/n" or the
+    /// default response. Takes the place of the formatter's
+    /// `default_hint`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// // Shows "Threads? «42» "
+    /// Question::new("Threads?")
+    ///     .default(Answer::RESPONSE(String::from("42")))
+    ///     .show_defaults()
+    ///     .hint_with(|hint| format!(" «{}»", hint))
+    ///     .ask();
+    /// ```
+    pub fn hint_with<F>(&mut self, hint: F) -> &mut Question<R, W>
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.hint = Some(Arc::new(hint));
+        self
+    }
+
+    /// Pre-fill the input line with the default response so the
+    /// user can edit it in place, rather than showing it as a
+    /// hint, on an interactive terminal (see `toggle`). The arrow,
+    /// home and end keys move the cursor and backspace and delete
+    /// remove text. Elsewhere the default works as usual.
+    ///
+    /// Only a default `Answer::RESPONSE` is pre-filled. Clearing
+    /// the line and pressing enter still accepts the default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// Question::new("Project name?")
+    ///     .default(Answer::RESPONSE(String::from("my-app")))
+    ///     .show_defaults()
+    ///     .prefill()
+    ///     .ask();
+    /// ```
+    pub fn prefill(&mut self) -> &mut Question<R, W> {
+        self.prefill = true;
+        self
+    }
+
+    /// Read the response over several lines, until an empty line
+    /// or the end of input, so multi-line pastes are not cut off
+    /// after the first line. Lines are joined with newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Paste the certificate (end with an empty line):")
+    ///     .multiline()
+    ///     .ask();
+    /// ```
+    pub fn multiline(&mut self) -> &mut Question<R, W> {
+        self.multiline_until("")
+    }
+
+    /// Read the response over several lines like `multiline`,
+    /// until a line consisting of `terminator`, such as ".".
+    pub fn multiline_until(&mut self, terminator: &str) -> &mut Question<R, W> {
+        self.multiline = Some(terminator.to_string());
+        self
+    }
+
+    /// Only let characters for which `allowed` returns `true` be
+    /// typed on an interactive terminal (see `toggle`); any other
+    /// keystroke is silently dropped, preventing invalid input
+    /// rather than rejecting it after enter. Elsewhere lines are
+    /// read as they are, so responses should still be validated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Port?").allow_chars(|c| c.is_ascii_digit()).ask();
+    /// Question::new("Username?").allow_chars(|c| !c.is_whitespace()).ask();
+    /// Question::new("Hex color?")
+    ///     .allow_chars(|c| "0123456789abcdefABCDEF#".contains(c))
+    ///     .ask();
+    /// ```
+    pub fn allow_chars<F>(&mut self, allowed: F) -> &mut Question<R, W>
+    where
+        F: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        self.allow_chars = Some(Arc::new(allowed));
+        self
+    }
+
+    /// Complete responses from `candidates`, such as branch names,
+    /// when tab is pressed on an interactive terminal (see
+    /// `toggle`), showing the rest of the first candidate that
+    /// matches as it is typed. Elsewhere lines are read as they
+    /// are.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Branch?")
+    ///     .completions(&["main", "develop", "feature/login"])
+    ///     .ask();
+    /// ```
+    pub fn completions(&mut self, candidates: &[&str]) -> &mut Question<R, W> {
+        let candidates: Vec<String> = candidates.iter().map(|c| c.to_string()).collect();
+        self.completions = Some(Arc::new(move |text: &str| {
+            candidates
+                .iter()
+                .filter(|c| c.starts_with(text))
+                .cloned()
+                .collect()
+        }));
+        self
+    }
+
+    /// Complete responses like `completions`, asking `provider`
+    /// for the candidates each time the text changes, so they can
+    /// come from the filesystem, an API or a database. It is given
+    /// the text typed so far and returns the candidates for it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("File?")
+    ///     .complete_with(|text| {
+    ///         std::fs::read_dir(".")
+    ///             .into_iter()
+    ///             .flatten()
+    ///             .flatten()
+    ///             .map(|entry| entry.file_name().to_string_lossy().into_owned())
+    ///             .filter(|name| name.starts_with(text))
+    ///             .collect()
+    ///     })
+    ///     .ask();
+    /// ```
+    pub fn complete_with<F>(&mut self, provider: F) -> &mut Question<R, W>
+    where
+        F: Fn(&str) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.completions = Some(Arc::new(provider));
+        self
+    }
+
+    /// Treat every line of input as the answer to exactly one
+    /// question, for sessions where input is piped in rather than
+    /// typed. A response that is not acceptable, or input ending,
+    /// stops the question instead of reading another line to ask
+    /// again, and `try_ask` reports why.
+    ///
+    /// This catches automation scripts whose answers no longer
+    /// line up with the questions, rather than silently assigning
+    /// answers to the wrong questions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let answer = Question::new("Continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .strict()
+    ///     .try_ask();
+    /// if let Err(e) = answer {
+    ///     eprintln!("error: {}", e);
+    ///     std::process::exit(1);
+    /// }
+    /// ```
+    pub fn strict(&mut self) -> &mut Question<R, W> {
+        self.strict = true;
+        self
+    }
+
+    /// Choose what the question answers when input ends before it
+    /// is answered, such as when stdin is closed or Ctrl-D is
+    /// pressed. A question that keeps asking, as with
+    /// `until_acceptable`, stops asking either way.
+    ///
+    /// # Examples
+    ///
+    /// The following answers no, rather than the default, when
+    /// there is no one left to answer.
+    ///
+    /// ```no_run
+    /// # use question::{Answer, OnEof, Question};
+    /// let answer = Question::new("Deploy now?")
+    ///     .default(Answer::YES)
+    ///     .on_eof(OnEof::No)
+    ///     .confirm();
+    /// ```
+    pub fn on_eof(&mut self, on_eof: OnEof) -> &mut Question<R, W> {
+        self.on_eof = on_eof;
+        self
+    }
+
+    /// Provide a default answer.
+    ///
+    /// # Examples
+    ///
+    /// The following will ask the user if they would like
+    /// to continue until either "y", "n", "yes", "no", or
+    /// "" an empty string is entered.  If an empty string
+    /// is entered `Answer::YES` will be returned.
+    ///
+    /// ```no_run
+    /// # use question::{Question, Answer};
+    /// Question::new("Do you want to continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .default(Answer::YES)
+    ///     .show_defaults()
+    ///     .ask();
+    /// ```
+    pub fn default(&mut self, answer: Answer) -> &mut Question<R, W> {
+        self.default = Some(answer);
+        self.default_with = None;
+        self
+    }
+
+    /// Provide a default answer computed by `default` when the
+    /// question is first asked, rather than when it is built, so
+    /// expensive lookups are skipped for questions never asked.
+    /// The computed answer is kept for later calls to `ask`.
+    ///
+    /// Layers see no default until the question is asked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// # fn detect_git_user() -> String { String::new() }
+    /// Question::new("Author?")
+    ///     .default_with(|| Answer::RESPONSE(detect_git_user()))
+    ///     .show_defaults()
+    ///     .ask();
+    /// ```
+    pub fn default_with<F>(&mut self, default: F) -> &mut Question<R, W>
+    where
+        F: Fn() -> Answer + Send + Sync + 'static,
+    {
+        self.default = None;
+        self.default_with = Some(Arc::new(default));
+        self
+    }
+
+    /// Provide a token the user can enter to deliberately
+    /// leave the question unanswered. Entering it returns
+    /// `Answer::SKIPPED`, which is distinct from accepting
+    /// the default.
+    ///
+    /// # Examples
+    ///
+    /// The following will return `Answer::SKIPPED` if the
+    /// user enters "-", and the default if they enter an
+    /// empty string.
+    ///
+    /// ```no_run
+    /// # use question::{Question, Answer};
+    /// Question::new("Middle name?")
+    ///     .default(Answer::RESPONSE(String::from("none")))
+    ///     .skip_token("-")
+    ///     .ask();
+    /// ```
+    ///
+    /// An empty string may be used as the token, in which
+    /// case it takes precedence over any default.
+    pub fn skip_token(&mut self, token: &str) -> &mut Question<R, W> {
+        self.skip = Some(token.into());
+        self
+    }
+
+    /// Answer with the value of the environment variable `name`,
+    /// if it is set, instead of asking, giving scripts and CI a
+    /// way to answer without a terminal. The value is taken as the
+    /// first response and checked like any other. A value that is
+    /// not accepted fails the question, without asking, as if it
+    /// were `strict`.
+    ///
+    /// Interactive widgets, such as `toggle` and `select` on a
+    /// terminal, are not shown when the variable is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// // MYAPP_REGION=eu-west-1 answers without asking.
+    /// Question::new("Region?")
+    ///     .acceptable(vec!["us-east-1", "eu-west-1"])
+    ///     .until_acceptable()
+    ///     .env_override("MYAPP_REGION")
+    ///     .ask();
+    /// ```
+    pub fn env_override<S: Into<String>>(&mut self, name: S) -> &mut Question<R, W> {
+        self.env = Some(name.into());
+        self
+    }
+
+    /// The value of the `env_override` variable, if it is set.
+    pub(crate) fn overridden(&self) -> Option<String> {
+        self.env.as_ref().and_then(|name| std::env::var(name).ok())
+    }
+
+    /// Provide a clarification to be shown if the user does
+    /// not enter an acceptable answer on the first try.
+    ///
+    /// The clarification is filled in afresh for each attempt:
+    /// "{attempt}" becomes the number of responses read so far,
+    /// "{tries}" the number allowed by `tries`, "{remaining}" the
+    /// number left, and "{input}" the last response, or `REDACTED`
+    /// for a `secret`. Without `tries`, "{tries}" and "{remaining}"
+    /// become "∞".
+    ///
+    /// # Examples
+    ///
+    /// The following will ask the user if they would like
+    /// to continue until either "y", "n", "yes", "no", or
+    /// "" an empty string is entered.  If an empty string
+    /// is entered `Answer::YES` will be returned. If the
+    /// user does not enter a valid response on the first
+    /// attempt, the clarification will be added to the
+    /// prompt.
+    ///
+    /// > Please enter either 'yes' or 'no'
+    /// > Do you want to continue? (Y/n)
+    ///
+    /// ```no_run
+    /// # use question::{Question, Answer};
+    /// Question::new("Do you want to continue?")
+    ///     .yes_no()
+    ///     .until_acceptable()
+    ///     .default(Answer::YES)
+    ///     .show_defaults()
+    ///     .clarification("Please enter either 'yes' or 'no'\n")
+    ///     .ask();
+    /// ```
+    ///
+    /// Showing which attempt failed.
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Port?")
+    ///     .acceptable(vec!["80", "443"])
+    ///     .tries(3)
+    ///     .clarification("'{input}' is not valid (attempt {attempt}/{tries})")
+    ///     .ask();
+    /// ```
+    pub fn clarification<S: Into<String>>(&mut self, c: S) -> &mut Question<R, W> {
+        self.clarification = Some(c.into());
+        self
+    }
+
+    /// Provide a longer explanation shown when the user enters
+    /// "?" or "help", after which they are asked again. Asking
+    /// for help never counts as one of the `tries`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Enable telemetry?")
+    ///     .yes_no()
+    ///     .help("Telemetry sends anonymous usage statistics to help\n\
+    ///            prioritize development. It can be disabled at any time.")
+    ///     .until_acceptable()
+    ///     .ask();
+    /// ```
+    pub fn help<S: Into<String>>(&mut self, help: S) -> &mut Question<R, W> {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Transform every response `ask` returns with `map`, once it
+    /// has been accepted, so answers can be normalized in one
+    /// place. Defaults given as responses are transformed too.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Project name?")
+    ///     .required()
+    ///     .map(|name| name.to_lowercase().replace(' ', "-"))
+    ///     .ask();
+    /// ```
+    pub fn map<F>(&mut self, map: F) -> &mut Question<R, W>
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.map = Some(Arc::new(map));
+        self
+    }
+
+    /// Call `on_invalid` each time a response is rejected, with the
+    /// number of the attempt, counting from one, and the response,
+    /// such as to log rejections or show a richer explanation than
+    /// the clarification.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Port?")
+    ///     .acceptable(vec!["80", "443", "8080"])
+    ///     .until_acceptable()
+    ///     .on_invalid(|attempt, response| {
+    ///         eprintln!("attempt {}: '{}' is not an open port", attempt, response)
+    ///     })
+    ///     .ask();
+    /// ```
+    pub fn on_invalid<F>(&mut self, on_invalid: F) -> &mut Question<R, W>
+    where
+        F: Fn(u64, &str) + Send + Sync + 'static,
+    {
+        self.on_invalid = Some(Arc::new(on_invalid));
+        self
+    }
+
+    /// Call `before` right before the question is first shown, and
+    /// `after` once it has been answered, such as to hide a
+    /// progress bar or spinner so it does not draw over the
+    /// prompt. Set both on `QuestionConfig` to suspend output for
+    /// every question. Neither is called if the question is
+    /// answered without showing anything, such as by a `Layer`,
+    /// `env_override` or `remember`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use std::sync::Arc;
+    /// let paused = Arc::new(AtomicBool::new(false));
+    /// let resumed = paused.clone();
+    /// Question::new("Continue?")
+    ///     .around_prompt(
+    ///         move || paused.store(true, Ordering::SeqCst),
+    ///         move || resumed.store(false, Ordering::SeqCst),
+    ///     )
+    ///     .confirm();
+    /// ```
+    pub fn around_prompt<B, A>(&mut self, before: B, after: A) -> &mut Question<R, W>
+    where
+        B: Fn() + Send + Sync + 'static,
+        A: Fn() + Send + Sync + 'static,
+    {
+        self.before_prompt = Some(Arc::new(before));
+        self.after_prompt = Some(Arc::new(after));
+        self
+    }
+
+    /// Tag the question, such as "advanced" or "network". A
+    /// `Form` only asks tagged questions when one of their tags
+    /// has been enabled, so rarely needed questions can be left
+    /// out of a basic run.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// Question::new("Proxy?")
+    ///     .tag("network")
+    ///     .tag("advanced")
+    ///     .default(Answer::RESPONSE(String::from("none")));
+    /// ```
+    pub fn tag(&mut self, tag: &str) -> &mut Question<R, W> {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Ask the user a question exactly as it has been built.
+    ///
+    /// # Examples
+    ///
+    /// The following will return whatever the user enters
+    /// as an `Answer::RESPONSE(String)`.
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("What is your favorite color?").ask();
+    /// ```
+    pub fn ask(&mut self) -> Option<Answer> {
+        let layers = self.layers.clone();
+        let answer = self.measure(|q| {
+            let answer = q.recalled(|q| q.ask_through(&layers, Question::ask_unlayered));
+            q.traced(answer)
+        });
+        match (answer, &self.map) {
+            (Some(Answer::RESPONSE(response)), Some(map)) => Some(Answer::RESPONSE(map(&response))),
+            (answer, _) => answer,
+        }
+    }
+
+    /// Ask the question again and again, collecting the answers
+    /// until an empty line is entered or input ends. Each answer is
+    /// validated just as `ask` would validate it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let tags = Question::new("Add another tag? (blank to finish)").ask_many();
+    /// ```
+    pub fn ask_many(&mut self) -> Vec<Answer> {
+        self.ask_many_until("")
+    }
+
+    /// Collect answers like `ask_many`, until `sentinel` is
+    /// entered or input ends. The sentinel takes the place of any
+    /// `skip_token` while the answers are collected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let hosts = Question::new("Host? (\"done\" to finish)").ask_many_until("done");
+    /// ```
+    pub fn ask_many_until(&mut self, sentinel: &str) -> Vec<Answer> {
+        let skip = self.skip.replace(sentinel.into());
+        let mut answers = Vec::new();
+        loop {
+            self.ended = false;
+            match self.ask() {
+                Some(Answer::SKIPPED) | None => break,
+                Some(_) if self.ended => break,
+                Some(answer) => answers.push(answer),
+            }
+        }
+        self.skip = skip;
+        answers
+    }
+
+    /// Ask the question like `ask`, lending the answer rather
+    /// than returning it. The answer is kept by the question until
+    /// it is next asked, so a loop asking the same question many
+    /// times only copies a response when it calls `to_answer`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{AnswerRef, Question};
+    /// let mut question = Question::new("Process the next item?");
+    /// question.yes_no().until_acceptable();
+    /// while let Some(AnswerRef::YES) = question.ask_ref() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn ask_ref(&mut self) -> Option<AnswerRef<'_>> {
+        self.answer = self.ask();
+        self.answer.as_ref().map(Answer::as_borrowed)
+    }
+
+    /// Ask the question like `ask`, reporting why a `strict`
+    /// question could not be answered. Questions that are not
+    /// strict never fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{AskError, Question};
+    /// match Question::new("Name?").strict().try_ask() {
+    ///     Ok(answer) => println!("{:?}", answer),
+    ///     Err(AskError::EndOfInput) => eprintln!("no name given"),
+    ///     Err(e) => eprintln!("error: {}", e),
+    /// }
+    /// ```
+    pub fn try_ask(&mut self) -> Result<Option<Answer>, AskError> {
+        self.error = None;
+        let answer = self.ask();
+        if self.cancelled() {
+            return Err(AskError::Cancelled);
+        }
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(answer),
+        }
+    }
+
+    fn ask_unlayered(&mut self) -> Option<Answer> {
+        self.ended = false;
+        let answer = self.ask_once();
+        self.at_end(answer)
+    }
+
+    fn ask_once(&mut self) -> Option<Answer> {
+        self.resolve_default();
+        if self.dry_run {
+            return self.preview();
+        }
+        self.drawn = 0;
+        self.counting = self.countdown.is_some();
+        if let Some(answer) = self.get_key_response() {
+            return Some(answer);
+        }
+        if self.until_acceptable || (self.constrained() && self.tries.is_none()) {
+            return self.until_valid();
+        }
+        if self.tries.is_some() {
+            return self.max_tries();
+        }
+        if self.validates() {
+            return self.get_valid_response(None).ok();
+        }
+        self.get_response().ok()
+    }
+
+    /// Ask a user a yes/no question until an acceptable
+    /// response is given.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("Continue?").confirm();
+    /// ```
+    ///
+    /// If a layer declines to produce an answer `Answer::NO`
+    /// is returned. If the user bails out instead of answering,
+    /// such as by ending input, `Answer::CANCELLED` is returned.
+    pub fn confirm(&mut self) -> Answer {
+        self.yes_no();
+        let layers = self.layers.clone();
+        self.measure(|q| {
+            let answer = q.recalled(|q| q.ask_through(&layers, |q| Some(q.confirm_unlayered())));
+            q.traced(answer)
+        })
+        .unwrap_or(Answer::NO)
+    }
+
+    fn confirm_unlayered(&mut self) -> Answer {
+        self.resolve_default();
+        if self.dry_run {
+            return self.preview().unwrap_or(Answer::NO);
+        }
+        self.drawn = 0;
+        self.ended = false;
+        self.counting = self.countdown.is_some();
+        if let Some(answer) = self.get_key_response() {
+            return answer;
+        }
+        let answer = self.until_valid();
+        self.at_end(answer).unwrap_or_else(|| self.unanswered())
+    }
+
+    /// The answer to give in place of `answer` if input ended
+    /// while asking, as chosen with `on_eof`.
+    fn at_end(&self, answer: Option<Answer>) -> Option<Answer> {
+        if !self.ended {
+            return answer;
+        }
+        match self.on_eof {
+            OnEof::Default => answer,
+            OnEof::No => Some(Answer::NO),
+            OnEof::Error | OnEof::Abort => None,
+        }
+    }
+
+    /// Whether input ended while asking the question and it was
+    /// set to abort with `OnEof::Abort`.
+    pub(crate) fn aborted(&self) -> bool {
+        self.ended && self.on_eof == OnEof::Abort
+    }
+
+    /// What a yes/no question answers when it ends without an
+    /// acceptable response: `Answer::CANCELLED` if the user bailed
+    /// out or the input stalled, otherwise `Answer::NO`.
+    fn unanswered(&self) -> Answer {
+        if self.ended || self.cancelled() || self.stalled() {
+            Answer::CANCELLED
+        } else {
+            Answer::NO
+        }
+    }
+
+    /// Ask the yes/no question with `confirm`, then, if the answer
+    /// was yes, ask `again`, so one accidental "y" is not enough to
+    /// go ahead with something destructive. Returns `Answer::YES`
+    /// only if both were answered yes, otherwise the answer that
+    /// was not. A yes default is not used for the second question,
+    /// so it cannot be answered by pressing enter twice.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// # let items = 42;
+    /// let answer = Question::new("Are you sure?").confirm_twice(format!(
+    ///     "Really delete {} items? This cannot be undone.",
+    ///     items
+    /// ));
+    /// if answer == Answer::YES {
+    ///     // delete the items
+    /// }
+    /// ```
+    pub fn confirm_twice<S: Into<String>>(&mut self, again: S) -> Answer {
+        let answer = self.confirm();
+        if answer != Answer::YES {
+            return answer;
+        }
+        let question = std::mem::replace(&mut self.question, again.into());
+        let default = self.default.take();
+        self.default = default.clone().filter(|default| *default != Answer::YES);
+        let answer = self.confirm();
+        self.question = question;
+        self.default = default;
+        answer
+    }
+
+    /// Ask the user to type `phrase` exactly before going ahead
+    /// with something destructive, like typing the name of a
+    /// repository to delete it. Returns `Answer::YES` if they did
+    /// and `Answer::NO` otherwise.
+    ///
+    /// The phrase must match case unless `case_insensitive` is
+    /// set. The question is asked once, or as set by `tries` or
+    /// `until_acceptable`, and the clarification is shown as the
+    /// failure message whenever the phrase does not match.
+    /// Defaults are ignored, and since this is not a yes/no
+    /// question `AssumeYes` does not answer it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answer, Question};
+    /// let answer = Question::new("Type 'my-production-db' to delete it:")
+    ///     .clarification("The name did not match, nothing was deleted")
+    ///     .confirm_phrase("my-production-db");
+    /// if answer == Answer::YES {
+    ///     // delete the database
+    /// }
+    /// ```
+    pub fn confirm_phrase(&mut self, phrase: &str) -> Answer {
+        self.phrase = Some(phrase.to_string());
+        let layers = self.layers.clone();
+        self.measure(|q| {
+            let answer = q.ask_through(&layers, |q| Some(q.confirm_phrase_unlayered()));
+            q.traced(answer)
+        })
+        .unwrap_or(Answer::NO)
+    }
+
+    fn confirm_phrase_unlayered(&mut self) -> Answer {
+        self.drawn = 0;
+        self.ended = false;
+        let phrase = self.phrase.clone().unwrap_or_default();
+        let tries = self.attempts();
+        let mut retry = None;
+        for attempt in 0..tries {
+            let prompt = self.build_prompt(retry.as_ref());
+            let response = match self.prompt_user(&prompt) {
+                Ok(_) if self.ended => return Answer::CANCELLED,
+                Ok(response) => response,
+                Err(_) => return Answer::NO,
+            };
+            let matched = if self.case_sensitive.unwrap_or(true) {
+                response == phrase
+            } else {
+                fold_case(&response).eq(fold_case(&phrase))
+            };
+            if matched {
+                return Answer::YES;
+            }
+            self.fail(AskError::Rejected(response));
+            if self.asks_once() || attempt + 1 == tries {
+                break;
+            }
+            self.wait_before_retry(attempt);
+            retry = Some(Retry {
+                invalid: Invalid::Unacceptable,
+                remaining: Some(tries - attempt - 1).filter(|_| !self.until_acceptable),
+            });
+        }
+        if let Some(ref clarification) = self.clarification {
+            let clarification = self.clarify(clarification);
+            let _ = self.show_text(&format!("{}\n", clarification.trim_end_matches('\n')));
+        }
+        Answer::NO
+    }
+
+    /// Ask through each of `layers` in turn, finally asking the
+    /// user with `inner`.
+    fn ask_through(
+        &mut self,
+        layers: &[Arc<dyn Layer + Send + Sync>],
+        inner: fn(&mut Question<R, W>) -> Option<Answer>,
+    ) -> Option<Answer> {
+        match layers.split_first() {
+            Some((layer, rest)) => {
+                let question = self.question.clone();
+                let default = self.default.clone();
+                let context = Context::new(&question, default.as_ref(), self.yes_no);
+                let answer = {
+                    let mut next = || self.ask_through(rest, inner);
+                    layer.ask(&context, Next::new(&mut next))
+                };
+                if context.default_taken() && answer.is_some() {
+                    self.tally.take_default();
+                }
+                answer
+            }
+            None => inner(self),
+        }
+    }
+
+    fn get_response(&mut self) -> Result<Answer, std::io::Error> {
+        let prompt = self.build_prompt(None);
+        match self.prompt_user(&prompt) {
+            Ok(ref answer) if self.is_back(answer) => {
+                self.went_back = true;
+                Ok(Answer::SKIPPED)
+            }
+            Ok(ref answer) if self.is_skip(answer) => Ok(Answer::SKIPPED),
+            Ok(ref answer) if self.default.is_some() && answer.is_empty() => {
+                Ok(self.default.clone().unwrap())
+            }
+            Ok(answer) => Ok(Answer::RESPONSE(answer)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_valid_response(&mut self, retry: Option<&Retry>) -> Result<Answer, Invalid> {
+        let prompt = self.build_prompt(retry);
+        if let Ok(mut response) = self.prompt_user(&prompt) {
+            if self.is_back(&response) {
+                self.went_back = true;
+                return Ok(Answer::SKIPPED);
+            }
+            if self.is_skip(&response) {
+                return Ok(Answer::SKIPPED);
+            }
+            if response.is_empty() {
+                if let Some(ref default) = self.default {
+                    return Ok(default.clone());
+                }
+            }
+            // Matched and checked while borrowed, so the only copy
+            // made is of an answer other than the response itself,
+            // which is `None` here and moved into the answer below.
+            let answer = if response.is_empty() && self.required {
+                Err(Invalid::Reason(self.text("required", &[])))
+            } else if self.valid_responses.is_some() || self.acceptable.is_some() {
+                self.match_response(&response).map(Some)
+            } else {
+                Ok(None)
+            };
+            let answer = answer.and_then(|answer| {
+                match answer {
+                    Some(AnswerRef::RESPONSE(text)) => self.check_response(text)?,
+                    Some(_) => {}
+                    None => self.check_response(&response)?,
+                }
+                Ok(answer.map(AnswerRef::to_answer))
+            });
+            if answer.is_err() {
+                #[cfg(feature = "tracing")]
+                ::tracing::info!(
+                    attempt = self.tally.attempts(),
+                    response = self.logged(&response),
+                    "response rejected"
+                );
+                if let Some(ref on_invalid) = self.on_invalid {
+                    on_invalid(self.tally.attempts(), &response);
+                }
+                if self.secret {
+                    self.fail(AskError::Rejected(String::from(REDACTED)));
+                } else {
+                    self.fail(AskError::Rejected(response.clone()));
+                }
+            }
+            let answer = answer.map(|answer| {
+                answer.unwrap_or_else(|| Answer::RESPONSE(std::mem::take(&mut response)))
+            });
+            if self.secret {
+                secret::wipe(&mut response);
+            }
+            return answer;
+        }
+        Err(Invalid::Unacceptable)
+    }
+
+    /// Check a response that is being accepted as it is against
+    /// the `format`, length and strength it is required to have.
+    fn check_response(&self, text: &str) -> Result<(), Invalid> {
+        if let Some(format) = self.format {
+            if !format.accepts(text) {
+                let format = self.text(format.message_id(), &[]);
+                let reason = self.text("not-format", &[("response", text), ("format", &format)]);
+                return Err(Invalid::Reason(reason));
+            }
+        }
+        let length = text.chars().count();
+        match (self.min_len, self.max_len) {
+            (Some(min), _) if length < min => {
+                let reason = self.text("too-short", &[("min", &min.to_string())]);
+                return Err(Invalid::Reason(reason));
+            }
+            (_, Some(max)) if length > max => {
+                let reason = self.text("too-long", &[("max", &max.to_string())]);
+                return Err(Invalid::Reason(reason));
+            }
+            _ => {}
+        }
+        if let Some(min) = self.min_strength {
+            let strength = Strength::of(text);
+            if strength < min {
+                let reason = self.text("too-weak", &[("strength", &strength.to_string())]);
+                return Err(Invalid::Reason(reason));
+            }
+        }
+        Ok(())
+    }
+
+    /// `text` as it is compared when matching responses, normalized
+    /// if `normalize` or `fold_diacritics` was set.
+    fn normalized<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        #[cfg(feature = "unicode")]
+        {
+            if self.normalization.is_some() || self.fold_diacritics {
+                let normalized = unicode::normalize(text, self.normalization, self.fold_diacritics);
+                return Cow::Owned(normalized);
+            }
+        }
+        Cow::Borrowed(text)
+    }
+
+    /// Find the answer for a response among the valid and
+    /// acceptable responses. An acceptable response is returned
+    /// as it was given to `accept`, not as it was typed.
+    fn match_response(&self, response: &str) -> Result<AnswerRef<'_>, Invalid> {
+        let case_sensitive = self.is_case_sensitive();
+        let response = &*self.normalized(response);
+        let matches = |candidate: &str| {
+            let candidate = &*self.normalized(candidate);
+            if case_sensitive {
+                candidate == response
+            } else {
+                fold_case(candidate).eq(fold_case(response))
+            }
+        };
+        let candidates = self.candidates();
+        if let Some(&(_, answer)) = candidates.iter().find(|&&(c, _)| matches(c)) {
+            return Ok(answer);
+        }
+        if self.accept_prefixes && !response.is_empty() {
+            let starts_with = |candidate: &str| {
+                let candidate = &*self.normalized(candidate);
+                if case_sensitive {
+                    candidate.starts_with(response)
+                } else {
+                    let mut candidate = fold_case(candidate);
+                    fold_case(response).all(|c| candidate.next() == Some(c))
+                }
+            };
+            let mut matching: Vec<&(&str, AnswerRef)> = Vec::new();
+            for candidate in candidates.iter().filter(|&&(c, _)| starts_with(c)) {
+                if !matching.iter().any(|m| m.1 == candidate.1) {
+                    matching.push(candidate);
+                }
+            }
+            match matching.len() {
+                0 => {}
+                1 => return Ok(matching[0].1),
+                _ => {
+                    let mut names: Vec<String> =
+                        matching.iter().map(|&&(c, _)| c.to_string()).collect();
+                    names.sort();
+                    return Err(Invalid::Ambiguous(response.to_string(), names));
+                }
+            }
+        }
+        if let Some(max_distance) = self.suggest_on_typo {
+            let lowercase;
+            let response = if case_sensitive {
+                response
+            } else {
+                lowercase = response.to_lowercase();
+                &lowercase
+            };
+            let distances: Vec<usize> = candidates
+                .iter()
+                .map(|&(c, _)| {
+                    if case_sensitive {
+                        edit_distance(c, response)
+                    } else {
+                        edit_distance(&c.to_lowercase(), response)
+                    }
+                })
+                .collect();
+            let closest = distances.iter().copied().min();
+            if let Some(closest) = closest.filter(|&d| d <= max_distance) {
+                let mut names: Vec<String> = Vec::new();
+                for (&(c, _), &distance) in candidates.iter().zip(&distances) {
+                    // The same response may be a candidate more than once.
+                    if distance == closest && !names.iter().any(|name| name == c) {
+                        names.push(c.to_string());
+                    }
+                }
+                return Err(Invalid::Typo(names));
+            }
+        }
+        Err(Invalid::Unacceptable)
+    }
+
+    /// Every response that will be accepted, paired with the answer
+    /// it produces, in a stable order.
+    fn candidates(&self) -> Vec<(&str, AnswerRef<'_>)> {
+        let mut candidates = Vec::new();
+        if let Some(ref valid_responses) = self.valid_responses {
+            let mut keys: Vec<&String> = valid_responses.keys().collect();
+            keys.sort();
+            for key in keys {
+                candidates.push((key.as_str(), valid_responses[key].as_borrowed()));
+            }
+        }
+        if let Some(ref acceptable) = self.acceptable {
+            for accepted in acceptable {
+                candidates.push((accepted.as_str(), AnswerRef::RESPONSE(accepted)));
+            }
+        }
+        candidates
+    }
+
+    fn get_key_response(&mut self) -> Option<Answer> {
+        if !(self.yes_no && self.keys) || self.overridden().is_some() {
+            return None;
+        }
+        if self.toggle {
+            return self.toggle_buttons().unwrap_or(None);
+        }
+        if self.instant {
+            return self.instant_key().unwrap_or(None);
+        }
+        None
+    }
+
+    fn instant_key(&mut self) -> Result<Option<Answer>, std::io::Error> {
+        let prompt = self.build_prompt(None);
+        self.show(&prompt)?;
+        let raw = RawMode::enable()?;
+        loop {
+            match term::read_key(&mut self.reader)? {
+                Some(Key::Char(c)) => {
+                    let answer = self
+                        .match_response(&c.to_string())
+                        .map(AnswerRef::to_answer);
+                    if let Ok(answer) = answer {
+                        self.show_text(&format!("{}\n", c))?;
+                        return Ok(Some(answer));
+                    }
+                }
+                Some(Key::Enter) if self.default.is_some() => {
+                    self.show_text("\n")?;
+                    return Ok(self.default.clone());
+                }
+                Some(Key::Interrupt) => {
+                    self.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
+                None => {
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn toggle_buttons(&mut self) -> Result<Option<Answer>, std::io::Error> {
+        let raw = RawMode::enable()?;
+        let mut yes = self.default != Some(Answer::NO);
+        loop {
+            let buttons = if yes {
+                "\x1b[7m Yes \x1b[0m  No "
+            } else {
+                " Yes  \x1b[7m No \x1b[0m"
+            };
+            let frame = format!("\r\x1b[2K{} {}", self.question_text(), buttons);
+            self.show(&frame)?;
+            match term::read_key(&mut self.reader)? {
+                Some(Key::Left) | Some(Key::Right) | Some(Key::Tab) => yes = !yes,
+                Some(Key::Char('y')) | Some(Key::Char('Y')) => yes = true,
+                Some(Key::Char('n')) | Some(Key::Char('N')) => yes = false,
+                Some(Key::Enter) => {
+                    self.show_text("\n")?;
+                    return Ok(Some(if yes { Answer::YES } else { Answer::NO }));
+                }
+                Some(Key::Interrupt) => {
+                    self.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
                 None => {
-                    self.build_clarification();
-                    attempts += 1;
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Show the question and wait for a key to be pressed, or for
+    /// a line to be entered when keys cannot be read.
+    fn wait_for_key(&mut self) {
+        let prompt = format!("{} ", self.question_text());
+        if !self.keys {
+            let _ = self.read_prompted_line(&prompt);
+            return;
+        }
+        if self.show(&prompt).is_err() {
+            return;
+        }
+        if let Ok(raw) = RawMode::enable() {
+            let key = term::read_key(&mut self.reader);
+            let _ = self.show_text("\n");
+            if let Ok(Some(Key::Interrupt)) = key {
+                raw.interrupt();
+            }
+        }
+    }
+
+    fn max_tries(&mut self) -> Option<Answer> {
+        let tries = self.tries.unwrap();
+        let mut retry = None;
+        for attempt in 0..tries {
+            match self.get_valid_response(retry.as_ref()) {
+                Ok(answer) => return Some(answer),
+                Err(_) if self.asks_once() => return None,
+                Err(_) if self.stalled() => {
+                    self.report(AskError::NoInteractiveInput);
+                    return None;
+                }
+                Err(invalid) => {
+                    if attempt + 1 < tries {
+                        self.wait_before_retry(attempt);
+                    }
+                    retry = Some(Retry {
+                        invalid,
+                        remaining: Some(tries - attempt - 1),
+                    })
+                }
+            }
+        }
+        None
+    }
+
+    fn until_valid(&mut self) -> Option<Answer> {
+        let mut retry = None;
+        let mut failures = 0;
+        loop {
+            match self.get_valid_response(retry.as_ref()) {
+                Ok(answer) => return Some(answer),
+                Err(_) if self.asks_once() || self.ended => return None,
+                Err(_) if self.stalled() => {
+                    self.report(AskError::NoInteractiveInput);
+                    return None;
+                }
+                Err(invalid) => {
+                    self.wait_before_retry(failures);
+                    failures += 1;
+                    retry = Some(Retry {
+                        invalid,
+                        remaining: None,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Wait out the `retry_delay`, if any, after the response to
+    /// the attempt numbered `failures` from zero was not accepted,
+    /// ringing the bell first if asked to. Platforms without a
+    /// clock do not wait.
+    fn wait_before_retry(&mut self, failures: u64) {
+        if self.bell_on_invalid {
+            // A bell that cannot be rung is no reason to stop asking.
+            let _ = self.show_text("\x07");
+        }
+        if let Some(delay) = self.retry_delay {
+            let delay = if self.backoff {
+                let factor = 2u32.saturating_pow(failures.min(31) as u32);
+                delay.checked_mul(factor).unwrap_or(delay)
+            } else {
+                delay
+            };
+            if outcome::CLOCK {
+                std::thread::sleep(delay);
+            }
+        }
+    }
+
+    /// Whether responses are matched exactly, which they are not
+    /// unless `case_sensitive` was called.
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive.unwrap_or(false)
+    }
+
+    /// Compute a default given to `default_with`, now that the
+    /// question is being asked.
+    fn resolve_default(&mut self) {
+        if let Some(default) = self.default_with.take() {
+            self.default = Some(default());
+        }
+    }
+
+    /// Show the question and the default a dry run takes in its
+    /// place, failing with `AskError::NoDefault` if it has none.
+    fn preview(&mut self) -> Option<Answer> {
+        let taken = match self.default {
+            Some(Answer::YES) => self.text("yes", &[]),
+            Some(Answer::NO) => self.text("no", &[]),
+            Some(Answer::ALL) => self.text("all", &[]),
+            Some(Answer::NEVER) => self.text("never", &[]),
+            Some(Answer::RESPONSE(ref response)) => response.clone(),
+            Some(Answer::SKIPPED) | Some(Answer::CANCELLED) => self.text("preview-skipped", &[]),
+            None => self.text("preview-no-default", &[]),
+        };
+        let prompt = self.build_prompt(None);
+        let _ = self.show(&format!("{}{}\n", prompt, taken));
+        self.tally.take_default();
+        if self.default.is_none() && self.error.is_none() {
+            self.error = Some(AskError::NoDefault);
+        }
+        self.default.clone()
+    }
+
+    /// Whether responses are checked before they are accepted.
+    fn validates(&self) -> bool {
+        self.valid_responses.is_some()
+            || self.acceptable.is_some()
+            || self.format.is_some()
+            || self.constrained()
+    }
+
+    /// Whether responses are constrained by `required`, `min_len`,
+    /// `max_len` or `min_strength`, which ask again until they are
+    /// met.
+    fn constrained(&self) -> bool {
+        self.required
+            || self.min_len.is_some()
+            || self.max_len.is_some()
+            || self.min_strength.is_some()
+    }
+
+    /// How many times a question answered outside of the usual
+    /// retry loop may be asked: once, or as set by `tries` or
+    /// `until_acceptable`.
+    fn attempts(&self) -> u64 {
+        match self.tries {
+            _ if self.until_acceptable => u64::MAX,
+            Some(tries) => tries,
+            None => 1,
+        }
+    }
+
+    /// `text` as it may be logged: `REDACTED` for a `secret`.
+    #[cfg(feature = "tracing")]
+    fn logged<'a>(&self, text: &'a str) -> &'a str {
+        if self.secret {
+            REDACTED
+        } else {
+            text
+        }
+    }
+
+    /// Log `answer` with the `tracing` feature, passing it on.
+    fn traced(&self, answer: Option<Answer>) -> Option<Answer> {
+        #[cfg(feature = "tracing")]
+        match answer {
+            Some(Answer::RESPONSE(ref response)) => {
+                ::tracing::debug!(answer = self.logged(response), "answer given")
+            }
+            ref answer => ::tracing::debug!(answer = ?answer, "answer given"),
+        }
+        answer
+    }
+
+    /// Record why a question that `asks_once` failed, keeping the
+    /// first reason given.
+    fn fail(&mut self, error: AskError) {
+        if self.asks_once() && self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    /// Whether the first response not accepted fails the question
+    /// rather than it being asked again: when it is `strict`, or the
+    /// response came from `env_override`.
+    fn asks_once(&self) -> bool {
+        self.strict || self.overridden().is_some()
+    }
+
+    /// Record `error` for `try_ask`, even if the question is not
+    /// strict.
+    fn report(&mut self, error: AskError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    /// Whether input that is not a terminal, such as a pipe, has
+    /// given the same response `STALLED_AFTER` times in a row, so
+    /// asking again would only read it again.
+    fn stalled(&self) -> bool {
+        !self.tty && self.tally.repeats() >= STALLED_AFTER
+    }
+
+    fn is_skip(&self, response: &str) -> bool {
+        match self.skip {
+            Some(ref token) => response == token,
+            None => false,
+        }
+    }
+
+    /// `clarification` with the placeholders for the attempt just
+    /// made filled in. The response is filled in last, so any
+    /// placeholders it contains are left as they are.
+    fn clarify(&self, clarification: &str) -> String {
+        let attempt = self.tally.attempts();
+        let (tries, remaining) = match self.tries {
+            Some(tries) => (tries.to_string(), tries.saturating_sub(attempt).to_string()),
+            None => (String::from("∞"), String::from("∞")),
+        };
+        clarification
+            .replace("{attempt}", &attempt.to_string())
+            .replace("{tries}", &tries)
+            .replace("{remaining}", &remaining)
+            .replace("{input}", &self.last_input)
+    }
+
+    /// The question with its placeholders filled in for the
+    /// attempt about to be made. See `new`.
+    pub(crate) fn question_text(&self) -> Cow<'_, str> {
+        if !self.question.contains('{') {
+            return Cow::Borrowed(&self.question);
+        }
+        let default = match self.default {
+            Some(Answer::YES) | Some(Answer::ALL) => self.text("yes", &[]),
+            Some(Answer::NO) | Some(Answer::NEVER) => self.text("no", &[]),
+            Some(Answer::RESPONSE(ref response)) => response.clone(),
+            _ => String::new(),
+        };
+        let choices: Vec<&str> = self.candidates().iter().map(|(name, _)| *name).collect();
+        let attempt = self.tally.attempts() + 1;
+        Cow::Owned(
+            self.question
+                .replace("{default}", &default)
+                .replace("{choices}", &choices.join(", "))
+                .replace("{attempt}", &attempt.to_string()),
+        )
+    }
+
+    /// Whether `response` asks a `Form` to go back to the previous
+    /// question.
+    fn is_back(&self, response: &str) -> bool {
+        match self.back {
+            Some(ref token) => response == token,
+            None => false,
+        }
+    }
+
+    /// The built in message `id`, from the question's locale if
+    /// it has one, with its arguments filled in.
+    fn text(&self, id: &str, args: &[(&str, &str)]) -> String {
+        #[cfg(feature = "i18n")]
+        {
+            if let Some(ref locale) = self.locale {
+                return locale::fill(locale.text(id), args);
+            }
+        }
+        locale::fill(locale::english(id), args)
+    }
+
+    /// The question's built in messages, to look up while it is
+    /// borrowed to be asked.
+    fn texts(&self) -> locale::Texts {
+        locale::Texts {
+            #[cfg(feature = "i18n")]
+            locale: self.locale.clone(),
+        }
+    }
+
+    /// Render the prompt shown to the user. The stored question and
+    /// clarification are never modified, so every attempt (and every
+    /// call to `ask`) renders exactly the same text. When the previous
+    /// response was `invalid` the clarification, if any, and the
+    /// reason are shown above the question.
+    fn build_prompt(&self, retry: Option<&Retry>) -> String {
+        let formatter = self.formatter.clone();
+        let mut errors = Vec::new();
+        if let Some(retry) = retry {
+            let remaining = match retry.remaining {
+                Some(1) if self.show_remaining_tries => Some(self.text("attempts-left-one", &[])),
+                Some(n) if self.show_remaining_tries => {
+                    Some(self.text("attempts-left", &[("count", &n.to_string())]))
+                }
+                _ => None,
+            };
+            let clarification = self.clarification.as_ref().map(|c| self.clarify(c));
+            match (clarification, remaining) {
+                (Some(clarification), Some(remaining)) => {
+                    let clarification = clarification.trim_end_matches('\n');
+                    errors.push(format!("{} ({})", clarification, remaining));
+                }
+                (Some(clarification), None) => errors.push(clarification),
+                (None, Some(remaining)) => errors.push(remaining),
+                (None, None) => {}
+            }
+        }
+        match retry.map(|retry| &retry.invalid) {
+            Some(Invalid::Ambiguous(response, candidates)) => {
+                let candidates = candidates.join(", ");
+                let args = [("response", response.as_str()), ("candidates", &candidates)];
+                errors.push(self.text("ambiguous", &args));
+            }
+            Some(Invalid::Typo(suggestions)) => {
+                let quoted: Vec<String> = suggestions.iter().map(|s| format!("'{}'", s)).collect();
+                let (last, rest) = quoted.split_last().unwrap();
+                errors.push(if rest.is_empty() {
+                    self.text("did-you-mean-one", &[("suggestion", last)])
+                } else {
+                    let rest = rest.join(", ");
+                    self.text("did-you-mean", &[("suggestions", &rest), ("last", last)])
+                });
+            }
+            Some(Invalid::Reason(reason)) => errors.push(reason.clone()),
+            Some(Invalid::Items(items)) => {
+                let quoted: Vec<String> = items.iter().map(|s| format!("'{}'", s)).collect();
+                errors.push(self.text("not-valid", &[("items", &quoted.join(", "))]));
+            }
+            _ => {}
+        }
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|error| self.paint(Part::Error, &formatter.error(error)))
+            .collect();
+        let mut prompt = String::new();
+        let candidates = self.candidates();
+        let choices = if candidates.is_empty() {
+            String::new()
+        } else {
+            let choices: Vec<&str> = candidates.iter().map(|(name, _)| *name).collect();
+            formatter.choices(&choices)
+        };
+        let mut hint = String::new();
+        if self.show_defaults {
+            let mut text = match self.default {
+                Some(Answer::YES) | Some(Answer::ALL) => Some(self.text("hint-yes", &[])),
+                Some(Answer::NO) | Some(Answer::NEVER) => Some(self.text("hint-no", &[])),
+                Some(Answer::RESPONSE(_)) if self.uses_editor() => None,
+                Some(Answer::RESPONSE(ref s)) => Some(s.clone()),
+                Some(Answer::SKIPPED) | Some(Answer::CANCELLED) => {
+                    Some(self.text("hint-skip", &[]))
+                }
+                None => Some(self.text("hint-yes-no", &[])),
+            };
+            if let (true, Some(text)) = (self.to_all, text.as_mut()) {
+                text.push('/');
+                text.push_str(&self.text("hint-all", &[]));
+            }
+            hint = match (text, &self.hint) {
+                (Some(text), Some(format)) => format(&text),
+                (Some(text), None) => formatter.default_hint(&text),
+                (None, _) => hint,
+            };
+        }
+        let separator = formatter.separator();
+        let progress = match self.progress {
+            Some((position, total)) => formatter.progress(position, total),
+            None => String::new(),
+        };
+        let mut question = formatter.question(&self.question_text());
+        if let Some(ref session) = self.session {
+            question = session.labeled_text(&question);
+        }
+        if let Some(width) = self.wrap {
+            let reserved =
+                display_width(&choices) + display_width(&hint) + display_width(&separator);
+            // Wrapped together so the first line leaves room for the
+            // progress, which is then styled on its own.
+            let wrapped = wrap::wrap(&(progress.clone() + &question), width, reserved + 1);
+            question = match wrapped.strip_prefix(progress.as_str()) {
+                Some(rest) => rest.to_string(),
+                None => wrap::wrap(&question, width, reserved + 1),
+            };
+        }
+        prompt += &self.paint(Part::Hint, &progress);
+        prompt += &self.paint(Part::Question, &question);
+        prompt += &self.paint(Part::Hint, &choices);
+        prompt += &self.paint(Part::Hint, &hint);
+        prompt += &separator;
+        if errors.is_empty() {
+            prompt
+        } else if self.inline_errors && self.keys {
+            // Drawn below the room the prompt takes, moving back up
+            // rather than saving the cursor, which scrolling moves.
+            let rows = self.rows(&prompt);
+            let below = errors.join("\n");
+            let up = rows + self.rows(&below) - 1;
+            format!("{}{}\x1b[{}A\r{}", "\n".repeat(rows), below, up, prompt)
+        } else {
+            errors.join("\n") + "\n" + &prompt
+        }
+    }
+
+    /// Style `text` as `part` of the prompt with the theme, when
+    /// the output supports it.
+    #[cfg(feature = "color")]
+    fn paint(&self, part: Part, text: &str) -> String {
+        match self.theme {
+            Some(ref theme) if self.color => {
+                let style = match part {
+                    Part::Question => theme.question,
+                    Part::Hint => theme.hint,
+                    Part::Error => theme.error,
+                };
+                style.paint(text)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn paint(&self, _part: Part, text: &str) -> String {
+        text.to_string()
+    }
+
+    /// Write a complete prompt, or frame of a widget, in a single
+    /// write and flush it unless flushing is manual.
+    fn show(&mut self, prompt: &str) -> Result<(), std::io::Error> {
+        self.before_shown();
+        if let Some(ref prompter) = self.prompter {
+            return prompter
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .show(prompt);
+        }
+        self.writer.write_all(prompt.as_bytes())?;
+        match self.flush {
+            Flush::Always | Flush::Prompt => self.writer.flush(),
+            Flush::Manual => Ok(()),
+        }
+    }
+
+    /// Write text that does not wait on the user, only flushing it
+    /// when every write should be flushed.
+    fn show_text(&mut self, text: &str) -> Result<(), std::io::Error> {
+        self.before_shown();
+        if let Some(ref prompter) = self.prompter {
+            return prompter
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .show(text);
+        }
+        self.writer.write_all(text.as_bytes())?;
+        match self.flush {
+            Flush::Always => self.writer.flush(),
+            Flush::Prompt | Flush::Manual => Ok(()),
+        }
+    }
+
+    /// Call the `around_prompt` hook that comes before the question
+    /// is shown, if this is the first thing shown while asking it.
+    fn before_shown(&mut self) {
+        if self.shown == Some(false) {
+            self.shown = Some(true);
+            if let Some(ref before) = self.before_prompt {
+                before();
+            }
+        }
+    }
+
+    fn prompt_user(&mut self, question: &str) -> Result<String, std::io::Error> {
+        if self.bell_on_prompt && self.tally.attempts() == 0 {
+            self.show_text("\x07")?;
+        }
+        loop {
+            let inline = self.inline_errors && self.keys;
+            if (self.redraw || inline) && self.keys && self.drawn > 0 {
+                let clear = format!("\x1b[{}A\r\x1b[J", self.drawn);
+                self.show_text(&clear)?;
+            }
+            self.record_prompt(question);
+            let mut s = match self.read_prompted_line(question) {
+                Ok(Some(s)) => s,
+                Err(ref e) if is_invalid_encoding(e) && !self.strict => {
+                    let message = self.text("invalid-encoding", &[]);
+                    self.show_text(&format!("{}\n", message))?;
+                    self.drawn = 0;
+                    continue;
+                }
+                Err(e) => {
+                    if is_invalid_encoding(&e) {
+                        self.fail(AskError::InvalidEncoding);
+                    }
+                    return Err(e);
+                }
+                Ok(None) => {
+                    self.record_response(None);
+                    self.ended = true;
+                    match self.on_eof {
+                        OnEof::Error | OnEof::Abort => self.report(AskError::EndOfInput),
+                        _ => self.fail(AskError::EndOfInput),
+                    }
+                    String::new()
+                }
+            };
+            if let Some(terminator) = self.multiline.clone() {
+                s = self.read_more_lines(s, &terminator)?;
+            }
+            // Inline errors leave the cursor where the prompt starts.
+            let shown = match question.rsplit_once('\r') {
+                Some((errors, prompt)) if inline => {
+                    if !errors.is_empty() {
+                        self.show_text("\x1b[J")?;
+                    }
+                    prompt
+                }
+                _ => question,
+            };
+            // Text saved in the editor is not left on the screen.
+            let echoed = if self.edits_externally() { "" } else { &s };
+            self.drawn = self.rows(&format!("{}{}", shown, echoed.trim_end_matches('\n')));
+            let response = self.trim.apply(s);
+            if !self.ended {
+                self.record_response(Some(&response));
+            }
+            if !self.secret {
+                self.record_history(&response);
+            }
+            if response == "?" || response.eq_ignore_ascii_case("help") {
+                if let Some(help) = self.help.clone() {
+                    self.show_text(&format!("{}\n", help))?;
+                    self.drawn = 0;
                     continue;
                 }
             }
+            self.tally.response(&response);
+            self.last_input = if self.secret {
+                String::from(REDACTED)
+            } else {
+                response.clone()
+            };
+            #[cfg(feature = "tracing")]
+            ::tracing::debug!(
+                attempt = self.tally.attempts(),
+                response = self.logged(&response),
+                "response read"
+            );
+            return Ok(response);
+        }
+    }
+
+    /// The number of rows `text` takes up on the terminal, taking
+    /// lines too long for it into account when its width is known.
+    fn rows(&self, text: &str) -> usize {
+        text.split('\n')
+            .map(|line| match self.wrap {
+                Some(width) if width > 0 => display_width(line).max(1).div_ceil(width),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Keep reading the lines following `first` until one is
+    /// `terminator` or input ends, joining them with newlines.
+    fn read_more_lines(
+        &mut self,
+        first: String,
+        terminator: &str,
+    ) -> Result<String, std::io::Error> {
+        if first.trim() == terminator {
+            return Ok(String::new());
+        }
+        let mut lines = vec![first.trim_end_matches(&['\r', '\n'][..]).to_string()];
+        let prefill = std::mem::replace(&mut self.prefill, false);
+        let mut result = Ok(());
+        loop {
+            match self.read_prompted_line("") {
+                Ok(Some(ref line)) if line.trim() != terminator => {
+                    lines.push(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+                }
+                Ok(_) => break,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        self.prefill = prefill;
+        result.map(|()| lines.join("\n"))
+    }
+
+    /// Show `prompt` and read a response, or `None` once the input
+    /// ends or the question is withdrawn by `cancel_on`.
+    fn read_prompted_line(&mut self, prompt: &str) -> Result<Option<String>, std::io::Error> {
+        if self.cancelled() {
+            return Ok(None);
+        }
+        if !self.env_read {
+            self.env_read = true;
+            if let Some(value) = self.overridden() {
+                return Ok(Some(value));
+            }
+        }
+        let line = self.prompt_for_line(prompt)?;
+        Ok(line.filter(|_| !self.cancelled()))
+    }
+
+    /// Show `prompt` and read the line entered in response, as is,
+    /// or `None` if the input has ended.
+    fn prompt_for_line(&mut self, prompt: &str) -> Result<Option<String>, std::io::Error> {
+        if self.edits_externally() {
+            return self.edit_externally(prompt);
+        }
+        let initial = match self.default {
+            Some(Answer::RESPONSE(ref s)) if self.prefill => s.clone(),
+            _ => String::new(),
+        };
+        let prompt: Cow<str> = match self.countdown {
+            Some(timeout) if self.keys && self.counting => {
+                self.counting = false;
+                let deadline = std::time::Instant::now() + timeout;
+                return self.edit_line_until(prompt, &initial, Some(deadline));
+            }
+            Some(timeout) => {
+                Cow::Owned(prompt.replace("{seconds}", &timeout.as_secs().to_string()))
+            }
+            None => Cow::Borrowed(prompt),
+        };
+        if let Some(prompter) = self.prompter.clone() {
+            self.before_shown();
+            let choices = self.candidates().into_iter().map(|(c, _)| c).collect();
+            let question = self.question_text();
+            let prompt = Prompt::new(
+                &question,
+                &prompt,
+                self.default.as_ref(),
+                choices,
+                self.yes_no,
+            );
+            let mut prompter = prompter.lock().unwrap_or_else(|e| e.into_inner());
+            return prompter.prompt(&prompt);
+        }
+        if self.uses_editor() {
+            return self.edit_line(&prompt, &initial);
+        }
+        self.show(&prompt)?;
+        read_line(&mut self.reader)
+    }
+}
+
+/// The backend chosen with the `QUESTION_PROTOCOL` environment
+/// variable, if any.
+fn protocol() -> Option<Backend> {
+    match std::env::var("QUESTION_PROTOCOL") {
+        Ok(ref protocol) if protocol == "json" => Some(Arc::new(Mutex::new(JsonLines::stdio()))),
+        _ => None,
+    }
+}
+
+/// Remove leading and trailing whitespace from `s` without
+/// allocating a new string.
+fn trim_in_place(mut s: String) -> String {
+    let end = s.trim_end().len();
+    s.truncate(end);
+    let start = s.len() - s.trim_start().len();
+    s.drain(..start);
+    s
+}
+
+/// The characters of `s` in lower case, for comparing responses
+/// without allocating.
+fn fold_case(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().flat_map(char::to_lowercase)
+}
+
+/// The Levenshtein distance between two strings, counted in
+/// characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Read a single line from `reader`, one byte at a time, so nothing
+/// past the newline is consumed. This lets several prompts share one
+/// input stream without a throwaway buffer swallowing later answers.
+/// Returns `None` if the input ended before anything was read.
+fn read_line<R: Read>(reader: &mut R) -> Result<Option<String>, std::io::Error> {
+    let mut line = Vec::new();
+    let mut byte = [0; 1];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => break,
+            Ok(_) => {
+                line.push(byte[0]);
+                if byte[0] == b'\n' {
+                    break;
+                }
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    if line.is_empty() {
+        return Ok(None);
+    }
+    match String::from_utf8(line) {
+        Ok(line) => Ok(Some(line)),
+        Err(e) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+    }
+}
+
+/// Whether reading a line failed because it was not valid UTF-8,
+/// rather than because of the reader or a prompter.
+fn is_invalid_encoding(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::InvalidData
+        && e.get_ref()
+            .is_none_or(|inner| inner.is::<std::string::FromUtf8Error>())
+}
+
+/// Lists the completions for the text typed so far.
+type Completer = Arc<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
+/// Renders the built in hint for the default.
+type Hint = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Transforms accepted responses.
+type Mapper = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Called with the attempt number and response when a response
+/// is rejected.
+type OnInvalid = Arc<dyn Fn(u64, &str) + Send + Sync>;
+
+/// Called around asking a question.
+type Hook = Arc<dyn Fn() + Send + Sync>;
+
+/// A prompter shared by the copies of a question.
+type Backend = Arc<Mutex<dyn Prompter + Send>>;
+
+/// Where the copies of a question remember its answer.
+type Store = Arc<Mutex<dyn AnswerStore + Send>>;
+
+/// Why a question is being asked again.
+#[derive(Clone, Debug, PartialEq)]
+struct Retry {
+    /// Why the previous response was rejected.
+    invalid: Invalid,
+    /// How many tries remain, including this one, if they are
+    /// limited.
+    remaining: Option<u64>,
+}
+
+/// The parts of a prompt that can be styled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Part {
+    Question,
+    Hint,
+    Error,
+}
+
+/// Why a response was rejected, shown to the user when they are
+/// asked again.
+#[derive(Clone, Debug, PartialEq)]
+enum Invalid {
+    /// The response is not acceptable.
+    Unacceptable,
+
+    /// The response is a prefix of several acceptable responses.
+    Ambiguous(String, Vec<String>),
+
+    /// The response is close to these acceptable responses.
+    Typo(Vec<String>),
+
+    /// These items of a delimited response are not acceptable.
+    Items(Vec<String>),
+
+    /// The response was rejected for the reason given.
+    Reason(String),
+}
+
+/// When a `Question` flushes its output.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Flush {
+    /// Flush after every write, including text such as the
+    /// newline that ends an interactive widget.
+    Always,
+
+    /// Flush once a prompt has been written, right before
+    /// waiting on the user. This is the default.
+    Prompt,
+
+    /// Never flush, leaving it to the caller or writer.
+    Manual,
+}
+
+/// What a `Question` answers when input ends before it is
+/// answered.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum OnEof {
+    /// Answer as an empty response would, with the default if
+    /// there is one. Otherwise `ask` returns `None` and `confirm`
+    /// returns `Answer::CANCELLED`. This is the default.
+    Default,
+
+    /// Answer `Answer::NO`, even if there is a default.
+    No,
+
+    /// Leave the question unanswered, so `ask` returns `None`,
+    /// `confirm` returns `Answer::CANCELLED` and `try_ask` fails
+    /// with `AskError::EndOfInput`, even if the question is not
+    /// `strict`.
+    Error,
+
+    /// Leave the question unanswered as with `Error`, and stop
+    /// asking the rest of the `Form` it is part of, so `Form::ask`
+    /// returns the answers given so far.
+    Abort,
+}
+
+/// Which whitespace is trimmed from a `Question`'s responses.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Trim {
+    /// Trim whitespace from both ends. This is the default.
+    Both,
+
+    /// Trim whitespace from the start, and the line ending.
+    Start,
+
+    /// Trim whitespace from the end, including the line ending.
+    End,
+
+    /// Only remove the line ending, keeping all other whitespace.
+    LineEnding,
+}
+
+impl Trim {
+    fn apply(self, mut s: String) -> String {
+        match self {
+            Trim::Both => trim_in_place(s),
+            Trim::End => {
+                s.truncate(s.trim_end().len());
+                s
+            }
+            Trim::Start | Trim::LineEnding => {
+                if s.ends_with('\n') {
+                    s.pop();
+                    if s.ends_with('\r') {
+                        s.pop();
+                    }
+                }
+                if self == Trim::Start {
+                    let start = s.len() - s.trim_start().len();
+                    s.drain(..start);
+                }
+                s
+            }
+        }
+    }
+}
+
+/// An `Answer` borrowed from the question that produced it, or
+/// from any other `Answer`, without copying a response.
+///
+/// Returned by `Question::ask_ref`.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum AnswerRef<'a> {
+    /// A borrowed `Answer::RESPONSE`.
+    RESPONSE(&'a str),
+    /// `Answer::YES`.
+    YES,
+    /// `Answer::NO`.
+    NO,
+    /// `Answer::SKIPPED`.
+    SKIPPED,
+    /// `Answer::CANCELLED`.
+    CANCELLED,
+    /// `Answer::ALL`.
+    ALL,
+    /// `Answer::NEVER`.
+    NEVER,
+}
+
+impl<'a> AnswerRef<'a> {
+    /// Convert into an owned `Answer`, copying any response.
+    pub fn to_answer(self) -> Answer {
+        match self {
+            AnswerRef::RESPONSE(response) => Answer::RESPONSE(response.to_string()),
+            AnswerRef::YES => Answer::YES,
+            AnswerRef::NO => Answer::NO,
+            AnswerRef::SKIPPED => Answer::SKIPPED,
+            AnswerRef::CANCELLED => Answer::CANCELLED,
+            AnswerRef::ALL => Answer::ALL,
+            AnswerRef::NEVER => Answer::NEVER,
+        }
+    }
+}
+
+impl<'a> From<AnswerRef<'a>> for Answer {
+    fn from(answer: AnswerRef<'a>) -> Answer {
+        answer.to_answer()
+    }
+}
+
+/// An answer, the result of asking a `Question`.
+///
+/// More kinds of answer may be added, so matches on it need a
+/// wildcard arm.
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+#[non_exhaustive]
+pub enum Answer {
+    /// A more complicated `RESPONSE(String)` that
+    /// can be evaluated in the context of the
+    /// application.
+    RESPONSE(String),
+
+    /// A "yes" answer.
+    ///
+    /// Used to represent any answers that are acceptable
+    /// as a "yes" when asking a yes/no question.
+    YES,
+
+    /// A "no" answer.
+    ///
+    /// Used to represent any answers that are acceptable
+    /// as a "no" when asking a yes/no question.
+    NO,
+
+    /// A deliberately skipped answer.
+    ///
+    /// Returned when the user enters the token set with
+    /// `skip_token`, to distinguish "leave this unset" from
+    /// accepting the default.
+    SKIPPED,
+
+    /// The user bailed out instead of answering.
+    ///
+    /// Returned by `confirm` and `confirm_phrase` when input ends,
+    /// such as on Ctrl-D, the user interrupts with Ctrl-C without
+    /// the program exiting, the question is withdrawn with
+    /// `cancel_on`, a `countdown` runs out without a default, or
+    /// piped input gives the same unacceptable response again and
+    /// again, so these are not mistaken for the user saying no. `ask`
+    /// returns `None` instead, and `try_ask` says why.
+    CANCELLED,
+
+    /// A "yes" to this and every remaining item in a batch.
+    ///
+    /// Used to represent "a" and "all" when asking a question set
+    /// with `yes_no_all`.
+    ALL,
+
+    /// A "no" to this and every remaining item in a batch.
+    ///
+    /// Used to represent "q" and "never" when asking a question
+    /// set with `yes_no_all`.
+    NEVER,
+}
+
+impl Answer {
+    /// Borrow the answer as an `AnswerRef`.
+    pub fn as_borrowed(&self) -> AnswerRef<'_> {
+        match *self {
+            Answer::RESPONSE(ref response) => AnswerRef::RESPONSE(response),
+            Answer::YES => AnswerRef::YES,
+            Answer::NO => AnswerRef::NO,
+            Answer::SKIPPED => AnswerRef::SKIPPED,
+            Answer::CANCELLED => AnswerRef::CANCELLED,
+            Answer::ALL => AnswerRef::ALL,
+            Answer::NEVER => AnswerRef::NEVER,
+        }
+    }
+
+    /// Whether the answer is `Answer::YES` or `Answer::ALL`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// if Question::new("Continue?").confirm().is_yes() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn is_yes(&self) -> bool {
+        *self == Answer::YES || *self == Answer::ALL
+    }
+
+    /// Whether the answer is `Answer::NO` or `Answer::NEVER`.
+    pub fn is_no(&self) -> bool {
+        *self == Answer::NO || *self == Answer::NEVER
+    }
+
+    /// The answer as text: the response itself, "yes" or "no",
+    /// "all" or "never" for the answers to all, or an empty string
+    /// if it was skipped or cancelled.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Answer::RESPONSE(ref response) => response,
+            Answer::YES => "yes",
+            Answer::NO => "no",
+            Answer::ALL => "all",
+            Answer::NEVER => "never",
+            Answer::SKIPPED | Answer::CANCELLED => "",
+        }
+    }
+
+    /// Convert into text as `as_str` does, without copying a
+    /// response.
+    pub fn into_string(self) -> String {
+        match self {
+            Answer::RESPONSE(response) => response,
+            answer => answer.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `true` for `Answer::YES` and `Answer::ALL`, and `false`
+/// for anything else.
+impl From<Answer> for bool {
+    fn from(answer: Answer) -> bool {
+        answer.is_yes()
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(response: &'a str) -> Answer {
+        Answer::RESPONSE(response.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(response: String) -> Answer {
+        Answer::RESPONSE(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn build() {
+        let question = Question::new("Deploy?")
+            .yes_no()
+            .default(Answer::NO)
+            .show_defaults()
+            .build();
+        assert!(question.yes_no);
+        assert_eq!(Some(Answer::NO), question.default);
+        assert_eq!("Deploy? (y/N) ", question.build_prompt(None));
+    }
+
+    #[test]
+    fn write_to() {
+        let mut question = Question::new("Deploy?")
+            .yes_no()
+            .default(Answer::NO)
+            .write_to(Tee::new(Vec::new(), Vec::new()));
+        assert!(question.yes_no);
+        assert_eq!(Some(Answer::NO), question.default);
+        question.show_text("(y/N) ").unwrap();
+        let (terminal, log) = question.writer.into_inner();
+        assert_eq!(b"(y/N) ".to_vec(), terminal);
+        assert_eq!(terminal, log);
+    }
+
+    #[test]
+    fn answer_helpers() {
+        let response = Answer::from("blue");
+        assert_eq!(Answer::RESPONSE(String::from("blue")), response);
+        assert_eq!("blue", response.as_str());
+        assert_eq!("blue", response.to_string());
+        assert_eq!(String::from("blue"), response.clone().into_string());
+        assert!(!bool::from(response));
+        assert!(Answer::YES.is_yes() && !Answer::YES.is_no());
+        assert!(Answer::NO.is_no() && !Answer::NO.is_yes());
+        assert!(bool::from(Answer::YES));
+        assert!(!bool::from(Answer::NO));
+        assert_eq!(
+            "yes no ",
+            format!("{} {} {}", Answer::YES, Answer::NO, Answer::SKIPPED)
+        );
+        assert_eq!(
+            Answer::RESPONSE(String::from("x")),
+            String::from("x").into()
+        );
+    }
+
+    #[test]
+    fn cancelled() {
+        assert!(!bool::from(Answer::CANCELLED));
+        assert_eq!("", Answer::CANCELLED.as_str());
+        assert_eq!(AnswerRef::CANCELLED, Answer::CANCELLED.as_borrowed());
+
+        let input = Cursor::new(b"maybe\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        assert_eq!(Answer::CANCELLED, q.confirm());
+
+        let input = Cursor::new(b"y\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        let token = CancelToken::new();
+        token.cancel();
+        assert_eq!(Answer::CANCELLED, q.cancel_on(&token).confirm());
+
+        let input = Cursor::new(b"maybe\nn\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        assert_eq!(Answer::NO, q.confirm());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
+    fn default_constructor() {
+        let question = "Continue?";
+        let q = Question::new(question);
+        assert_eq!(question, q.question);
+        assert_eq!("Continue? ", q.build_prompt(None));
+        assert_eq!(None, q.default);
+        assert_eq!(None, q.skip);
+        assert_eq!(None, q.acceptable);
+        assert_eq!(None, q.valid_responses);
+        assert_eq!(None, q.clarification);
+        assert_eq!(None, q.tries);
+        assert_eq!(false, q.until_acceptable);
+        assert_eq!(false, q.show_defaults);
+        assert_eq!(false, q.yes_no);
+        assert_eq!(false, q.is_case_sensitive());
+    }
+
+    #[test]
+    fn set_default() {
+        macro_rules! default {
+            ($question:expr, $set:expr, $expected:expr) => {
+                let mut q = Question::new($question);
+                q.default($set);
+                assert_eq!($expected, q.default.unwrap());
+            };
+        }
+        let set = String::from("Yes Please!");
+        let response = String::from("Yes Please!");
+        default!("Continue?", Answer::NO, Answer::NO);
+        default!("Continue?", Answer::YES, Answer::YES);
+        default!(
+            "Continue?",
+            Answer::RESPONSE(set),
+            Answer::RESPONSE(response)
+        );
+    }
+
+    #[test]
+    fn accept() {
+        let mut q = Question::new("Continue?");
+
+        q.accept("y");
+        assert_eq!(vec!["y"], q.acceptable.unwrap());
+
+        let mut q = Question::new("Continue?");
+        q.accept("y");
+        q.accept("yes");
+        assert_eq!(vec!["y", "yes"], q.acceptable.unwrap());
+    }
+
+    #[test]
+    fn accept_ask() {
+        let response = String::from("y");
+        let input = Cursor::new(response.into_bytes());
+        let output = Cursor::new(Vec::new());
+        let actual = Question::with_cursor("y", input, output)
+            .accept("y")
+            .until_acceptable()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("y"))), actual);
+    }
+
+    #[test]
+    fn acceptable() {
+        let mut q = Question::new("Continue?");
+
+        q.acceptable(vec!["y"]);
+        assert_eq!(vec!["y"], q.acceptable.unwrap());
+
+        let mut q = Question::new("Continue?");
+        q.accept("y");
+        q.acceptable(vec!["yes", "n", "no"]);
+        assert_eq!(vec!["y", "yes", "n", "no"], q.acceptable.unwrap());
+    }
+
+    #[test]
+    fn owned_strings() {
+        let names = vec![String::from("alice"), String::from("bob")];
+        let mut q = Question::new(format!("Which of {} users?", names.len()));
+        q.acceptable(names)
+            .accept(String::from("carol"))
+            .clarification(format!("Enter one of {} names", 3));
+        assert_eq!("Which of 2 users?", q.question);
+        assert_eq!(vec!["alice", "bob", "carol"], q.acceptable.unwrap());
+        assert_eq!(Some("Enter one of 3 names"), q.clarification.as_deref());
+    }
+
+    #[test]
+    fn prompt() {
+        macro_rules! prompt {
+            ($question:expr, $user_input:expr) => {
+                let response = String::from($user_input);
+                let input = Cursor::new(response.clone().into_bytes());
+                let mut displayed_output = Cursor::new(Vec::new());
+                let result;
+
+                {
+                    let mut q = Question::with_cursor($question, input, &mut displayed_output);
+                    result = q.prompt_user($question).unwrap();
+                } // end borrow of output before using it
+
+                let output =
+                    String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+                assert_eq!($question, output);
+                assert_eq!(response, result);
+            };
+        }
+        prompt!("what is the meaning to life", "42");
+        prompt!("the universe", "42");
+        prompt!("everything", "42");
+        prompt!("Continue", "yes");
+        prompt!(
+            "What is the only manmade object visable from the moon?",
+            "The Great Wall of China"
+        );
+    }
+
+    #[test]
+    fn basic_confirm() {
+        macro_rules! confirm {
+            ($i:expr, $q:expr, $expected:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor($q, input, output).confirm();
+                assert_eq!($expected, actual);
+            };
+        }
+        confirm!("y", "Continue?", Answer::YES);
+        confirm!("yes", "Continue?", Answer::YES);
+        confirm!("n", "Continue?", Answer::NO);
+        confirm!("no", "Continue?", Answer::NO);
+    }
+
+    #[test]
+    fn basic_ask() {
+        macro_rules! ask {
+            ($i:expr, $q:expr, $expected:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor($q, input, output).ask();
+                assert_eq!(Some(Answer::RESPONSE(String::from($expected))), actual);
+            };
+        }
+
+        ask!("y\n", "Continue?", "y");
+        ask!("yes\n", "Continue?", "yes");
+        ask!("n\n", "Continue?", "n");
+        ask!("no\n", "Continue?", "no");
+        ask!("the universe,\n", "42", "the universe,");
+        ask!("and everything\n", "42", "and everything");
+        ask!(
+            "what is the meaning to life,\n",
+            "42",
+            "what is the meaning to life,"
+        );
+
+        ask!("y", "Continue?", "y");
+        ask!("yes", "Continue?", "yes");
+        ask!("n", "Continue?", "n");
+        ask!("no", "Continue?", "no");
+        ask!("the universe,", "42", "the universe,");
+        ask!("and everything", "42", "and everything");
+        ask!(
+            "what is the meaning to life,",
+            "42",
+            "what is the meaning to life,"
+        );
+    }
+
+    #[test]
+    fn yes_no_words() {
+        macro_rules! words {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Continue?", input, Vec::new())
+                    .yes_words(&["ja", "да", "はい"])
+                    .no_words(&["nein", "нет", "いいえ"])
+                    .until_acceptable()
+                    .confirm();
+                assert_eq!($expected, actual);
+            };
+        }
+        words!("ja\n", Answer::YES);
+        words!("ДА\n", Answer::YES);
+        words!("はい\n", Answer::YES);
+        words!("Nein\n", Answer::NO);
+        words!("нет\n", Answer::NO);
+        words!("いいえ\n", Answer::NO);
+        words!("oui\ny\n", Answer::YES);
+    }
+
+    #[test]
+    fn hint_format() {
+        macro_rules! hint {
+            ($default:expr, $expected:expr) => {
+                let input = Cursor::new(String::from("\n").into_bytes());
+                let mut output = Vec::new();
+                Question::with_cursor("Continue?", input, &mut output)
+                    .default($default)
+                    .show_defaults()
+                    .hint_format(" [{default}]")
+                    .ask();
+                assert_eq!($expected, String::from_utf8(output).unwrap());
+            };
+        }
+        hint!(Answer::YES, "Continue? [Y/n] ");
+        hint!(Answer::NO, "Continue? [y/N] ");
+        hint!(Answer::RESPONSE(String::from("42")), "Continue? [42] ");
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let mut output = Vec::new();
+        Question::with_cursor("Threads?", input, &mut output)
+            .default(Answer::RESPONSE(String::from("42")))
+            .show_defaults()
+            .hint_with(|hint| format!(" «{}»", hint))
+            .ask();
+        assert_eq!("Threads? «42» ", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn trim() {
+        macro_rules! trim {
+            ($trim:expr, $expected:expr) => {
+                let input = Cursor::new(String::from("  indented \r\n").into_bytes());
+                let actual = Question::with_cursor("Line?", input, Vec::new())
+                    .trim($trim)
+                    .ask();
+                assert_eq!(Some(Answer::RESPONSE(String::from($expected))), actual);
+            };
+        }
+        trim!(Trim::Both, "indented");
+        trim!(Trim::Start, "indented ");
+        trim!(Trim::End, "  indented");
+        trim!(Trim::LineEnding, "  indented ");
+
+        let input = Cursor::new(String::from(" \n").into_bytes());
+        let actual = Question::with_cursor("Passphrase?", input, Vec::new())
+            .default(Answer::RESPONSE(String::from("default")))
+            .preserve_whitespace()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from(" "))), actual);
+    }
+
+    #[test]
+    fn required() {
+        let input = Cursor::new(String::from("\n  \nquestion\n").into_bytes());
+        let mut output = Vec::new();
+        let actual = Question::with_cursor("Name?", input, &mut output)
+            .required()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("question"))), actual);
+        let expected = "Name? This field is required\nName? This field is required\nName? ";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let actual = Question::with_cursor("Name?", input, Vec::new())
+            .required()
+            .default(Answer::RESPONSE(String::from("question")))
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("question"))), actual);
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let actual = Question::with_cursor("Name?", input, Vec::new())
+            .required()
+            .ask();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn length() {
+        let input = Cursor::new(String::from("jo\nquestion-asker\nquestion\n").into_bytes());
+        let mut output = Vec::new();
+        let actual = Question::with_cursor("Username?", input, &mut output)
+            .min_len(3)
+            .max_len(10)
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("question"))), actual);
+        let expected = "Username? Please enter at least 3 characters\n\
+                        Username? Please enter at most 10 characters\n\
+                        Username? ";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+
+        let input = Cursor::new(String::from("ab\nx\nabc\n").into_bytes());
+        let actual = Question::with_cursor("Code?", input, Vec::new())
+            .min_len(3)
+            .tries(2)
+            .ask();
+        assert_eq!(None, actual);
+
+        let input = Cursor::new(String::from("ßü\n").into_bytes());
+        let actual = Question::with_cursor("Code?", input, Vec::new())
+            .max_len(2)
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("ßü"))), actual);
+    }
+
+    #[test]
+    fn map() {
+        macro_rules! map {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Project?", input, Vec::new())
+                    .acceptable(vec!["My Project", "Other"])
+                    .default(Answer::RESPONSE(String::from("Default Project")))
+                    .skip_token("-")
+                    .map(|name| name.to_lowercase().replace(' ', "-"))
+                    .ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        map!(
+            "my project\n",
+            Some(Answer::RESPONSE(String::from("my-project")))
+        );
+        map!(
+            "\n",
+            Some(Answer::RESPONSE(String::from("default-project")))
+        );
+        map!("-\n", Some(Answer::SKIPPED));
+        map!("none\n", None);
+    }
+
+    #[test]
+    fn on_invalid() {
+        let rejected = Arc::new(Mutex::new(Vec::new()));
+        let log = rejected.clone();
+        let input = Cursor::new(String::from("22\n\n443\n").into_bytes());
+        let actual = Question::with_cursor("Port?", input, Vec::new())
+            .acceptable(vec!["80", "443"])
+            .required()
+            .on_invalid(move |attempt, response| {
+                log.lock().unwrap().push((attempt, response.to_string()))
+            })
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("443"))), actual);
+        let expected = vec![(1, String::from("22")), (2, String::new())];
+        assert_eq!(expected, *rejected.lock().unwrap());
+    }
+
+    #[test]
+    fn clarification_template() {
+        let input = Cursor::new(String::from("22\n{attempt}\n443\n").into_bytes());
+        let mut output = Vec::new();
+        let actual = Question::with_cursor("Port?", input, &mut output)
+            .acceptable(vec!["80", "443"])
+            .tries(3)
+            .clarification("'{input}' is not valid ({remaining} of {tries} left)")
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("443"))), actual);
+        let expected = "Port? '22' is not valid (2 of 3 left)\n\
+                        Port? '{attempt}' is not valid (1 of 3 left)\n\
+                        Port? ";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn around_prompt() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (before, after) = (events.clone(), events.clone());
+        let input = Cursor::new(String::from("maybe\ny\n").into_bytes());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.around_prompt(
+            move || before.lock().unwrap().push("before"),
+            move || after.lock().unwrap().push("after"),
+        );
+        assert_eq!(Answer::YES, q.confirm());
+        assert_eq!(vec!["before", "after"], *events.lock().unwrap());
+
+        events.lock().unwrap().clear();
+        assert_eq!(Answer::YES, q.layer(AssumeYes).confirm());
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn until_acceptable_without_validation() {
+        let input = Cursor::new(String::from("anything\n").into_bytes());
+        let actual = Question::with_cursor("Name?", input, Vec::new())
+            .until_acceptable()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("anything"))), actual);
+
+        let input = Cursor::new(String::from("b\na\n").into_bytes());
+        let actual = Question::with_cursor("Letter?", input, Vec::new())
+            .accept("a")
+            .until_acceptable()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("a"))), actual);
+    }
+
+    #[test]
+    fn set_clarification() {
+        macro_rules! confirm_clarification {
+            ($i:expr, $q:expr, $clarification:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor($q, input, output);
+                q.clarification($clarification);
+                assert_eq!($clarification, q.clarification.unwrap());
+            };
+        }
+        confirm_clarification!("what is the meaning to life", "42", "14*3");
+        confirm_clarification!("Continue?", "wat", "Please respond with yes/no");
+    }
+
+    #[test]
+    fn set_max_tries() {
+        macro_rules! confirm_max_tries {
+            ($i:expr, $q:expr, $max_tries:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor($q, input, output);
+                q.tries($max_tries);
+                assert_eq!($max_tries, q.tries.unwrap());
+            };
+        }
+        confirm_max_tries!("what is the meaning to life", "42", 42);
+        confirm_max_tries!("Continue?", "wat", 0x79);
+    }
+
+    #[test]
+    fn set_until_acceptable() {
+        macro_rules! confirm_until_acceptable {
+            ($i:expr, $q:expr, $until_acceptable:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor($q, input, output);
+                q.until_acceptable();
+                assert_eq!($until_acceptable, q.until_acceptable);
+            };
+        }
+        confirm_until_acceptable!("what is the meaning to life", "42", true);
+    }
+
+    #[test]
+    fn set_show_defaults() {
+        macro_rules! confirm_show_defaults {
+            ($i:expr, $q:expr, $show_defaults:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor($q, input, output);
+                q.show_defaults();
+                assert_eq!($show_defaults, q.show_defaults);
+            };
+        }
+        confirm_show_defaults!("what is the meaning to life", "42", true);
+    }
+
+    #[test]
+    fn set_yes_no() {
+        macro_rules! confirm_yes_no {
+            ($i:expr, $q:expr, $yes_no:expr) => {
+                let response = String::from($i);
+                let input = Cursor::new(response.into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor($q, input, output);
+                q.yes_no();
+                assert_eq!($yes_no, q.yes_no);
+            };
+        }
+        confirm_yes_no!("what is the meaning to life", "42", true);
+    }
+
+    #[test]
+    fn prompt_is_not_mutated() {
+        let input = Cursor::new(String::from("maybe\nperhaps\ny\nn\n").into_bytes());
+        let mut displayed_output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Continue?", input, &mut displayed_output);
+            q.yes_no()
+                .until_acceptable()
+                .default(Answer::YES)
+                .show_defaults()
+                .clarification("Please enter either 'yes' or 'no'");
+            assert_eq!(Some(Answer::YES), q.ask());
+            assert_eq!(Some(Answer::NO), q.ask());
+        }
+        let output = String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+        let first = "Continue? (Y/n) ";
+        let retry = "Please enter either 'yes' or 'no'\nContinue? (Y/n) ";
+        assert_eq!([first, retry, retry, first].concat(), output);
+    }
+
+    #[test]
+    fn max_tries_reprompts_with_clarification() {
+        let input = Cursor::new(String::from("a\nb\nc\n").into_bytes());
+        let mut displayed_output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Continue?", input, &mut displayed_output);
+            q.yes_no().tries(2).clarification("yes or no");
+            assert_eq!(None, q.ask());
+        }
+        let output = String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+        assert_eq!("Continue? yes or no\nContinue? ", output);
+    }
+
+    #[test]
+    fn skip_token() {
+        macro_rules! skip {
+            ($i:expr, $token:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor("Middle name?", input, output)
+                    .default(Answer::RESPONSE(String::from("none")))
+                    .skip_token($token)
+                    .ask();
+                assert_eq!(Some($expected), actual);
+            };
+        }
+        skip!("-\n", "-", Answer::SKIPPED);
+        skip!("\n", "-", Answer::RESPONSE(String::from("none")));
+        skip!("\n", "", Answer::SKIPPED);
+        skip!("Jo\n", "-", Answer::RESPONSE(String::from("Jo")));
+
+        let input = Cursor::new(String::from("-\n").into_bytes());
+        let output = Cursor::new(Vec::new());
+        let actual = Question::with_cursor("Continue?", input, output)
+            .skip_token("-")
+            .confirm();
+        assert_eq!(Answer::SKIPPED, actual);
+    }
+
+    #[test]
+    fn acceptable_constrains_ask() {
+        macro_rules! ask {
+            ($i:expr, $tries:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor("Environment?", input, output)
+                    .acceptable(vec!["staging", "production"])
+                    .tries($tries)
+                    .ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        let staging = Some(Answer::RESPONSE(String::from("staging")));
+        ask!("staging\n", 1, staging.clone());
+        ask!("Staging\n", 1, staging.clone());
+        ask!("dev\n", 1, None);
+        ask!("dev\nstaging\n", 2, staging.clone());
+        ask!("dev\nqa\n", 2, None);
+        ask!("dev\nqa\nstaging\n", 0, staging);
+    }
+
+    #[test]
+    fn yes_no_with_acceptable() {
+        let input = Cursor::new(String::from("maybe\ny\n").into_bytes());
+        let output = Cursor::new(Vec::new());
+        let mut q = Question::with_cursor("Continue?", input, output);
+        q.yes_no().accept("maybe").until_acceptable();
+        assert_eq!(Some(Answer::RESPONSE(String::from("maybe"))), q.ask());
+        assert_eq!(Some(Answer::YES), q.ask());
+    }
+
+    #[test]
+    fn map_response() {
+        macro_rules! map_response {
+            ($i:expr, $expected:expr) => {
+                let always = Answer::RESPONSE(String::from("always"));
+                let input = Cursor::new(String::from($i).into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor("Overwrite?", input, output)
+                    .yes_no()
+                    .map_response("a", always.clone())
+                    .map_response("always", always)
+                    .until_acceptable()
+                    .ask();
+                assert_eq!(Some($expected), actual);
+            };
+        }
+        map_response!("a\n", Answer::RESPONSE(String::from("always")));
+        map_response!("always\n", Answer::RESPONSE(String::from("always")));
+        map_response!("v\nAlways\n", Answer::RESPONSE(String::from("always")));
+        map_response!("y\n", Answer::YES);
+    }
+
+    #[test]
+    fn toggle_confirm() {
+        macro_rules! toggle {
+            ($i:expr, $default:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor("Continue?", input, output);
+                q.keys = $keys;
+                q.default($default).toggle();
+                assert_eq!($expected, q.confirm());
+            };
+        }
+        toggle!(b"\r", Answer::YES, true, Answer::YES);
+        toggle!(b"\r", Answer::NO, true, Answer::NO);
+        toggle!(b"\x1b[C\r", Answer::YES, true, Answer::NO);
+        toggle!(b"\x1b[C\x1b[D\r", Answer::YES, true, Answer::YES);
+        toggle!(b"n\r", Answer::YES, true, Answer::NO);
+        toggle!(b"no\n", Answer::YES, false, Answer::NO);
+    }
+
+    #[test]
+    fn flush_policy() {
+        struct Flushes {
+            writes: usize,
+            flushes: usize,
+        }
+        impl Write for Flushes {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.writes += 1;
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.flushes += 1;
+                Ok(())
+            }
         }
-        None
+        macro_rules! flush {
+            ($flush:expr, $writes:expr, $flushes:expr) => {
+                let input = Cursor::new(b"\x1b[C\r".to_vec());
+                let mut output = Flushes {
+                    writes: 0,
+                    flushes: 0,
+                };
+                {
+                    let mut q = Question::with_cursor("Continue?", input, &mut output);
+                    q.keys = true;
+                    q.toggle().flush($flush).confirm();
+                }
+                assert_eq!($writes, output.writes);
+                assert_eq!($flushes, output.flushes);
+            };
+        }
+        flush!(Flush::Always, 3, 3);
+        flush!(Flush::Prompt, 3, 2);
+        flush!(Flush::Manual, 3, 0);
     }
 
-    fn until_valid(&mut self) -> Answer {
-        if self.valid_responses.is_some() {
-            loop {
-                match self.get_valid_response() {
-                    Some(answer) => return answer,
-                    None => {
-                        self.build_clarification();
-                        continue;
-                    }
+    #[test]
+    fn case_sensitivity() {
+        macro_rules! case {
+            ($i:expr, $sensitive:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let output = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor("Overwrite?", input, output);
+                q.yes_no()
+                    .map_response("Y", Answer::RESPONSE(String::from("all")))
+                    .accept("Skip")
+                    .tries(2);
+                if $sensitive {
+                    q.case_sensitive();
                 }
+                assert_eq!($expected, q.ask());
+            };
+        }
+        let all = Some(Answer::RESPONSE(String::from("all")));
+        let skip = Some(Answer::RESPONSE(String::from("Skip")));
+        case!("Y\n", true, all);
+        case!("y\n", true, Some(Answer::YES));
+        case!("skip\nSkip\n", true, skip.clone());
+        case!("YES\nyes\n", true, Some(Answer::YES));
+        case!("skip\n", false, skip);
+        case!("YES\n", false, Some(Answer::YES));
+    }
+
+    #[test]
+    fn layers() {
+        use std::sync::Mutex;
+
+        struct Record(Arc<Mutex<Vec<String>>>, &'static str);
+        impl Layer for Record {
+            fn ask(&self, context: &Context, next: Next) -> Option<Answer> {
+                let question = format!("{} {}", self.1, context.question());
+                self.0.lock().unwrap().push(question);
+                let answer = next.ask();
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {:?}", self.1, answer));
+                answer
             }
         }
-        if self.acceptable.is_some() {
-            loop {
-                match self.get_acceptable_response() {
-                    Some(answer) => return answer,
-                    None => {
-                        self.build_clarification();
-                        continue;
-                    }
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let input = Cursor::new(String::from("blue\n").into_bytes());
+        let output = Cursor::new(Vec::new());
+        let actual = Question::with_cursor("Color?", input, output)
+            .layer(Record(log.clone(), "outer"))
+            .layer(Record(log.clone(), "inner"))
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("blue"))), actual);
+        let expected = vec![
+            "outer Color?",
+            "inner Color?",
+            "inner Some(RESPONSE(\"blue\"))",
+            "outer Some(RESPONSE(\"blue\"))",
+        ];
+        assert_eq!(expected, *log.lock().unwrap());
+
+        let input = Cursor::new(Vec::new());
+        let output = Cursor::new(Vec::new());
+        let actual = Question::with_cursor("Continue?", input, output)
+            .layer(AssumeYes)
+            .confirm();
+        assert_eq!(Answer::YES, actual);
+    }
+
+    #[test]
+    fn accept_prefixes() {
+        macro_rules! prefix {
+            ($i:expr, $expected:expr, $output:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut displayed_output = Cursor::new(Vec::new());
+                {
+                    let actual = Question::with_cursor("Next?", input, &mut displayed_output)
+                        .acceptable(vec!["install", "info", "quit"])
+                        .accept_prefixes()
+                        .tries(2)
+                        .ask();
+                    assert_eq!($expected, actual);
+                }
+                let output = String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+                assert_eq!($output, output);
+            };
+        }
+        let install = Some(Answer::RESPONSE(String::from("install")));
+        prefix!("ins\n", install.clone(), "Next? ");
+        prefix!(
+            "q\n",
+            Some(Answer::RESPONSE(String::from("quit"))),
+            "Next? "
+        );
+        prefix!(
+            "in\nins\n",
+            install,
+            "Next? 'in' could be any of: info, install\nNext? "
+        );
+        prefix!("x\nxy\n", None, "Next? Next? ");
+
+        let input = Cursor::new(String::from("ye\n").into_bytes());
+        let output = Cursor::new(Vec::new());
+        let actual = Question::with_cursor("Continue?", input, output)
+            .accept_prefixes()
+            .confirm();
+        assert_eq!(Answer::YES, actual);
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(0, edit_distance("staging", "staging"));
+        assert_eq!(2, edit_distance("stagign", "staging"));
+        assert_eq!(1, edit_distance("prod", "prd"));
+        assert_eq!(3, edit_distance("", "abc"));
+        assert_eq!(1, edit_distance("é", "e"));
+    }
+
+    #[test]
+    fn suggest_on_typo() {
+        macro_rules! suggest {
+            ($i:expr, $output:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut displayed_output = Cursor::new(Vec::new());
+                {
+                    Question::with_cursor("Env?", input, &mut displayed_output)
+                        .acceptable(vec!["staging", "stage", "stale", "production"])
+                        .suggest_on_typo(2)
+                        .tries(2)
+                        .ask();
                 }
+                let output = String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+                assert_eq!($output, output);
+            };
+        }
+        suggest!("stagign\nstaging\n", "Env? Did you mean 'staging'?\nEnv? ");
+        suggest!(
+            "stace\nstaging\n",
+            "Env? Did you mean 'stage' or 'stale'?\nEnv? "
+        );
+        suggest!("dev\nstaging\n", "Env? Env? ");
+
+        let input = Cursor::new(b"stace\nstage\n".to_vec());
+        let mut q = Question::with_cursor("Env?", input, Vec::new());
+        q.acceptable(vec!["stage", "stale", "stage"])
+            .suggest_on_typo(2)
+            .tries(2)
+            .ask();
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Env? Did you mean 'stage' or 'stale'?\nEnv? ", output);
+    }
+
+    #[test]
+    fn help() {
+        let input = Cursor::new(String::from("?\nmaybe\nHELP\ny\n").into_bytes());
+        let mut displayed_output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Continue?", input, &mut displayed_output)
+                .yes_no()
+                .help("Say yes or no")
+                .tries(2)
+                .ask();
+            assert_eq!(Some(Answer::YES), actual);
+        }
+        let output = String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
+        let expected = "Continue? Say yes or no\nContinue? Continue? Say yes or no\nContinue? ";
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn prefill() {
+        macro_rules! prefill {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Name?", input, Vec::new());
+                q.keys = $keys;
+                let actual = q
+                    .default(Answer::RESPONSE(String::from("my-app")))
+                    .show_defaults()
+                    .prefill()
+                    .ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        let response = |s: &str| Some(Answer::RESPONSE(String::from(s)));
+        prefill!(b"\r", true, response("my-app"));
+        prefill!(b"\x7f\x7f\x7fcli\r", true, response("my-cli"));
+        prefill!(b"\x01new-\r", true, response("new-my-app"));
+        prefill!(b"\x7f\x7f\x7f\x7f\x7f\x7f\r", true, response("my-app"));
+        prefill!(b"", true, response("my-app"));
+        prefill!(b"\x7fx", true, response("my-app"));
+        prefill!(b"\n", false, response("my-app"));
+        prefill!(b"web\n", false, response("web"));
+
+        let input = Cursor::new(b"\r".to_vec());
+        let mut q = Question::with_cursor("Name?", input, Vec::new());
+        q.keys = true;
+        q.default(Answer::RESPONSE(String::from("app")))
+            .show_defaults()
+            .prefill()
+            .ask();
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("\r\x1b[2KName? app\r\x1b[9C\n", output);
+    }
+
+    #[test]
+    fn strict() {
+        macro_rules! strict {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Continue?", input, Vec::new())
+                    .yes_no()
+                    .tries(3)
+                    .strict()
+                    .try_ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        strict!("y\n", Ok(Some(Answer::YES)));
+        strict!("maybe\ny\n", Err(AskError::Rejected(String::from("maybe"))));
+        strict!("", Err(AskError::EndOfInput));
+
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let actual = Question::with_cursor("Continue?", input, Vec::new())
+            .yes_no()
+            .tries(3)
+            .try_ask();
+        assert_eq!(Ok(Some(Answer::YES)), actual);
+    }
+
+    #[test]
+    fn invalid_encoding() {
+        let input = Cursor::new(b"\xff\xfe\nJo\n".to_vec());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Name?", input, &mut output);
+            assert_eq!(Ok(Some(Answer::RESPONSE(String::from("Jo")))), q.try_ask());
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Name? The response was not valid UTF-8, please try again\nName? ",
+            output
+        );
+
+        let input = Cursor::new(b"\xff\ny\n".to_vec());
+        let actual = Question::with_cursor("Continue?", input, Vec::new())
+            .yes_no()
+            .until_acceptable()
+            .strict()
+            .try_ask();
+        assert_eq!(Err(AskError::InvalidEncoding), actual);
+    }
+
+    #[test]
+    fn dry_run() {
+        let mut output = Cursor::new(Vec::new());
+        {
+            let input = Cursor::new(b"n\n".to_vec());
+            let mut q = Question::with_cursor("Continue?", input, &mut output);
+            q.default(Answer::YES).show_defaults().dry_run();
+            assert_eq!(Answer::YES, q.confirm());
+            let input = Cursor::new(b"x\n".to_vec());
+            let mut q = Question::with_cursor("Name?", input, &mut output);
+            q.dry_run();
+            assert_eq!(Err(AskError::NoDefault), q.try_ask());
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!("Continue? (Y/n) yes\nName? (no default)\n", output);
+    }
+
+    #[test]
+    fn default_with() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let input = Cursor::new(b"\n\n".to_vec());
+        let mut q = Question::with_cursor("Author?", input, Vec::new());
+        q.default_with(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Answer::RESPONSE(String::from("Arthur"))
+        })
+        .show_defaults();
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+        let arthur = Some(Answer::RESPONSE(String::from("Arthur")));
+        assert_eq!(arthur, q.ask());
+        assert_eq!(arthur, q.ask());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Author? (Arthur) Author? (Arthur) ", output);
+
+        let input = Cursor::new(b"\n".to_vec());
+        let actual = Question::with_cursor("Author?", input, Vec::new())
+            .default_with(|| panic!("replaced"))
+            .default(Answer::NO)
+            .ask();
+        assert_eq!(Some(Answer::NO), actual);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn theme() {
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no()
+            .until_acceptable()
+            .default(Answer::NO)
+            .show_defaults()
+            .clarification("Please enter yes or no")
+            .theme(Theme::default());
+        assert_eq!(Some(Answer::YES), q.ask());
+        let output = String::from_utf8(q.writer.clone()).expect("Not UTF-8");
+        assert_eq!(
+            "Continue? (y/N) Please enter yes or no\nContinue? (y/N) ",
+            output
+        );
+
+        q.color = true;
+        let retry = Retry {
+            invalid: Invalid::Unacceptable,
+            remaining: None,
+        };
+        assert_eq!(
+            "\x1b[31mPlease enter yes or no\x1b[0m\n\x1b[1mContinue?\x1b[0m\x1b[2m (y/N)\x1b[0m ",
+            q.build_prompt(Some(&retry))
+        );
+    }
+
+    #[test]
+    fn allow_chars() {
+        macro_rules! allow_chars {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Port?", input, Vec::new());
+                q.keys = $keys;
+                let actual = q.allow_chars(|c| c.is_ascii_digit()).ask();
+                assert_eq!(Some(Answer::RESPONSE(String::from($expected))), actual);
+            };
+        }
+        allow_chars!(b"80a8 0\r", true, "8080");
+        allow_chars!(b"1x\x7f2\r", true, "2");
+        allow_chars!(b"80a8\n", false, "80a8");
+    }
+
+    #[test]
+    fn formatter() {
+        struct Bracketed;
+
+        impl PromptFormatter for Bracketed {
+            fn question(&self, question: &str) -> String {
+                format!("? {}", question)
+            }
+
+            fn choices(&self, choices: &[&str]) -> String {
+                format!(" [{}]", choices.join("/"))
+            }
+
+            fn default_hint(&self, hint: &str) -> String {
+                format!(" <{}>", hint)
+            }
+
+            fn separator(&self) -> String {
+                String::from(": ")
+            }
+
+            fn error(&self, error: &str) -> String {
+                format!("! {}", error)
             }
         }
-        panic!("Valid responses must be defined for `until_acceptable()`")
+
+        let input = Cursor::new(b"dev\nstaging\n".to_vec());
+        let mut q = Question::with_cursor("Environment?", input, Vec::new());
+        q.acceptable(vec!["staging", "production"])
+            .default(Answer::RESPONSE(String::from("staging")))
+            .show_defaults()
+            .clarification("Unknown environment")
+            .until_acceptable()
+            .formatter(Bracketed);
+        assert_eq!(Some(Answer::RESPONSE(String::from("staging"))), q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        let prompt = "? Environment? [staging/production] <staging>: ";
+        assert_eq!(
+            format!("{}! Unknown environment\n{}", prompt, prompt),
+            output
+        );
     }
 
-    fn build_prompt(&mut self) {
-        if self.show_defaults {
-            match self.default {
-                Some(Answer::YES) => self.prompt += " (Y/n)",
-                Some(Answer::NO) => self.prompt += " (y/N)",
-                Some(Answer::RESPONSE(ref s)) => {
-                    self.prompt += " (";
-                    self.prompt += s;
-                    self.prompt += ")";
-                }
-                None => self.prompt += " (y/n)",
-            }
+    #[test]
+    fn ask_ref() {
+        let input = Cursor::new(b"y\nyes\nmaybe\nn\n".to_vec());
+        let mut q = Question::with_cursor("Next?", input, Vec::new());
+        q.yes_no().until_acceptable();
+        let mut count = 0;
+        while let Some(AnswerRef::YES) = q.ask_ref() {
+            count += 1;
         }
-        self.prompt += " ";
+        assert_eq!(2, count);
+
+        let input = Cursor::new(b"  Arthur \n".to_vec());
+        let mut q = Question::with_cursor("Name?", input, Vec::new());
+        assert_eq!(Some(AnswerRef::RESPONSE("Arthur")), q.ask_ref());
+        let answer = q.ask_ref().map(AnswerRef::to_answer);
+        assert_eq!(Some(Answer::RESPONSE(String::new())), answer);
+        assert_eq!(AnswerRef::NO, Answer::NO.as_borrowed());
     }
 
-    fn build_clarification(&mut self) {
-        if let Some(clarification) = self.clarification.clone() {
-            self.prompt = clarification;
-            self.prompt += "\n";
-            self.prompt += &self.question;
-            self.build_prompt();
-        }
+    #[test]
+    fn wrap() {
+        let input = Cursor::new(b"y\n".to_vec());
+        let mut q = Question::with_cursor(
+            "Would you like to send anonymous crash reports?",
+            input,
+            Vec::new(),
+        );
+        q.yes_no().default(Answer::YES).show_defaults().wrap(24);
+        assert_eq!(
+            "Would you like to send\n  anonymous crash\n  reports? (Y/n) ",
+            q.build_prompt(None)
+        );
     }
 
-    fn prompt_user(&mut self, question: &str) -> Result<String, std::io::Error> {
-        let mut input = BufReader::new(&mut self.reader);
-        write!(&mut self.writer, "{}", question)?;
-        std::io::stdout().flush()?;
-        let mut s = String::new();
-        input.read_line(&mut s)?;
-        Ok(String::from(s.trim()))
+    #[test]
+    fn redraw() {
+        let input = Cursor::new(b"maybe\nsure\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.keys = true;
+        q.yes_no()
+            .until_acceptable()
+            .clarification("Please enter yes or no")
+            .redraw();
+        assert_eq!(Some(Answer::YES), q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        let retry = "Please enter yes or no\nContinue? ";
+        let expected = format!("Continue? \x1b[1A\r\x1b[J{}\x1b[2A\r\x1b[J{}", retry, retry);
+        assert_eq!(expected, output);
     }
-}
 
-/// An answer, the result of asking a `Question`.
-#[derive(Eq, PartialEq, Hash, Clone, Debug)]
-pub enum Answer {
-    /// A more complicated `RESPONSE(String)` that
-    /// can be evaluated in the context of the
-    /// application.
-    RESPONSE(String),
+    #[test]
+    fn inline_errors() {
+        let input = Cursor::new(b"maybe\nsure\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.keys = true;
+        q.yes_no()
+            .until_acceptable()
+            .clarification("Please enter yes or no")
+            .inline_errors();
+        assert_eq!(Some(Answer::YES), q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        let retry = "\nPlease enter yes or no\x1b[1A\rContinue? \x1b[J";
+        let expected = format!("Continue? \x1b[1A\r\x1b[J{}\x1b[1A\r\x1b[J{}", retry, retry);
+        assert_eq!(expected, output);
 
-    /// A "yes" answer.
-    ///
-    /// Used to represent any answers that are acceptable
-    /// as a "yes" when asking a yes/no question.
-    YES,
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no()
+            .until_acceptable()
+            .clarification("Please enter yes or no")
+            .inline_errors();
+        assert_eq!(Some(Answer::YES), q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Continue? Please enter yes or no\nContinue? ", output);
+    }
 
-    /// A "no" answer.
-    ///
-    /// Used to represent any answers that are acceptable
-    /// as a "no" when asking a yes/no question.
-    NO,
-}
+    #[test]
+    fn bell() {
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no()
+            .until_acceptable()
+            .bell_on_prompt()
+            .bell_on_invalid();
+        assert_eq!(Some(Answer::YES), q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("\x07Continue? \x07Continue? ", output);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
+        let input = Cursor::new(b"maybe\n1\n".to_vec());
+        let mut q = Question::with_cursor("Port?", input, Vec::new());
+        assert_eq!(Some(1), q.tries(2).bell_on_invalid().ask_as::<u16>());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!(1, output.matches('\x07').count());
+    }
 
     #[test]
-    fn default_constructor() {
-        let question = "Continue?";
-        let q = Question::new(question);
-        assert_eq!(question, q.question);
-        assert_eq!(question, q.prompt);
-        assert_eq!(None, q.default);
-        assert_eq!(None, q.acceptable);
-        assert_eq!(None, q.valid_responses);
-        assert_eq!(None, q.clarification);
-        assert_eq!(None, q.tries);
-        assert_eq!(false, q.until_acceptable);
-        assert_eq!(false, q.show_defaults);
-        assert_eq!(false, q.yes_no);
+    fn show_remaining_tries() {
+        let input = Cursor::new(b"maybe\nsure\nokay\n".to_vec());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.yes_no()
+            .tries(3)
+            .clarification("Please enter yes or no")
+            .show_remaining_tries();
+        assert_eq!(None, q.ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        let expected = "Continue? \
+                        Please enter yes or no (2 attempts left)\nContinue? \
+                        Please enter yes or no (1 attempt left)\nContinue? ";
+        assert_eq!(expected, output);
     }
 
     #[test]
-    fn set_default() {
-        macro_rules! default {
-            ($question:expr, $set:expr, $expected:expr) => {
-                let mut q = Question::new($question);
-                q.default($set);
-                assert_eq!($expected, q.default.unwrap());
+    fn retry_delay() {
+        use std::time::Instant;
+
+        macro_rules! retry_delay {
+            ($i:expr, $backoff:expr, $min:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Continue?", input, Vec::new());
+                q.yes_no().tries(4).retry_delay(Duration::from_millis(10));
+                if $backoff {
+                    q.exponential_backoff();
+                }
+                let start = Instant::now();
+                q.ask();
+                assert!(start.elapsed() >= Duration::from_millis($min));
             };
         }
-        let set = String::from("Yes Please!");
-        let response = String::from("Yes Please!");
-        default!("Continue?", Answer::NO, Answer::NO);
-        default!("Continue?", Answer::YES, Answer::YES);
-        default!(
-            "Continue?",
-            Answer::RESPONSE(set),
-            Answer::RESPONSE(response)
-        );
+        retry_delay!("y\n", false, 0);
+        retry_delay!("a\nb\ny\n", false, 20);
+        retry_delay!("a\nb\nc\ny\n", true, 70);
     }
 
     #[test]
-    fn accept() {
-        let mut q = Question::new("Continue?");
+    fn env_override() {
+        std::env::set_var("QUESTION_TEST_REGION", "eu-west-1");
+        std::env::set_var("QUESTION_TEST_BAD_REGION", "mars");
+        macro_rules! env_override {
+            ($name:expr, $i:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Region?", input, Vec::new());
+                q.acceptable(vec!["us-east-1", "eu-west-1"])
+                    .until_acceptable()
+                    .clarification("Unknown region")
+                    .env_override($name);
+                for _ in 0..2 {
+                    assert_eq!($expected.map(Answer::from), q.ask());
+                }
+                let output = String::from_utf8(q.writer).expect("Not UTF-8");
+                assert_eq!($shown, output);
+            };
+        }
+        env_override!("QUESTION_TEST_REGION", "us-east-1\n", Some("eu-west-1"), "");
+        env_override!(
+            "QUESTION_TEST_UNSET_REGION",
+            "us-east-1\nus-east-1\n",
+            Some("us-east-1"),
+            "Region? Region? "
+        );
+        env_override!("QUESTION_TEST_BAD_REGION", "us-east-1\n", None::<&str>, "");
 
-        q.accept("y");
-        assert_eq!(vec!["y"], q.acceptable.unwrap());
+        let input = Cursor::new(b"us-east-1\n".to_vec());
+        let mut q = Question::with_cursor("Region?", input, Vec::new());
+        q.acceptable(vec!["us-east-1", "eu-west-1"])
+            .tries(3)
+            .env_override("QUESTION_TEST_BAD_REGION");
+        let error = AskError::Rejected(String::from("mars"));
+        assert_eq!(Err(error), q.try_ask());
+        assert!(q.writer.is_empty());
 
-        let mut q = Question::new("Continue?");
-        q.accept("y");
-        q.accept("yes");
-        assert_eq!(vec!["y", "yes"], q.acceptable.unwrap());
+        let input = Cursor::new(Vec::new());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.keys = true;
+        std::env::set_var("QUESTION_TEST_CONTINUE", "y");
+        q.toggle().env_override("QUESTION_TEST_CONTINUE");
+        assert_eq!(Answer::YES, q.confirm());
     }
 
     #[test]
-    fn accept_ask() {
-        let response = String::from("y");
-        let input = Cursor::new(response.into_bytes());
-        let output = Cursor::new(Vec::new());
-        let actual = Question::with_cursor("y", input, output)
-            .accept("y")
-            .until_acceptable()
-            .ask();
-        assert_eq!(Some(Answer::RESPONSE(String::from("y"))), actual);
+    fn confirm_twice() {
+        macro_rules! confirm_twice {
+            ($i:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Sure?", input, Vec::new());
+                q.default(Answer::YES).show_defaults();
+                assert_eq!($expected, q.confirm_twice("Really delete 42 items?"));
+                assert_eq!("Sure?", q.question);
+                assert_eq!(Some(Answer::YES), q.default);
+                let output = String::from_utf8(q.writer).expect("Not UTF-8");
+                assert_eq!($shown, output);
+            };
+        }
+        confirm_twice!(
+            "y\nyes\n",
+            Answer::YES,
+            "Sure? (Y/n) Really delete 42 items? (y/n) "
+        );
+        confirm_twice!("n\n", Answer::NO, "Sure? (Y/n) ");
+        confirm_twice!(
+            "\n\nn\n",
+            Answer::NO,
+            "Sure? (Y/n) Really delete 42 items? (y/n) Really delete 42 items? (y/n) "
+        );
+        confirm_twice!(
+            "y\n",
+            Answer::CANCELLED,
+            "Sure? (Y/n) Really delete 42 items? (y/n) "
+        );
     }
 
     #[test]
-    fn acceptable() {
-        let mut q = Question::new("Continue?");
-
-        q.acceptable(vec!["y"]);
-        assert_eq!(vec!["y"], q.acceptable.unwrap());
-
-        let mut q = Question::new("Continue?");
-        q.accept("y");
-        q.acceptable(vec!["yes", "n", "no"]);
-        assert_eq!(vec!["y", "yes", "n", "no"], q.acceptable.unwrap());
+    fn on_eof() {
+        macro_rules! on_eof {
+            ($i:expr, $on_eof:expr, $asked:expr, $confirmed:expr, $error:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Deploy?", input, Vec::new());
+                q.on_eof($on_eof).yes_no().default(Answer::YES);
+                q.until_acceptable();
+                assert_eq!($asked, q.ask());
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Deploy?", input, Vec::new());
+                q.on_eof($on_eof).default(Answer::YES);
+                assert_eq!($confirmed, q.confirm());
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Deploy?", input, Vec::new());
+                q.on_eof($on_eof).default(Answer::YES);
+                assert_eq!($error, q.try_ask().err());
+            };
+        }
+        let eof = Some(AskError::EndOfInput);
+        on_eof!("", OnEof::Default, Some(Answer::YES), Answer::YES, None);
+        on_eof!("", OnEof::No, Some(Answer::NO), Answer::NO, None);
+        on_eof!("", OnEof::Error, None, Answer::CANCELLED, eof.clone());
+        on_eof!("", OnEof::Abort, None, Answer::CANCELLED, eof.clone());
+        on_eof!("maybe\n", OnEof::No, Some(Answer::NO), Answer::NO, None);
+        on_eof!("y\n", OnEof::Error, Some(Answer::YES), Answer::YES, None);
     }
 
     #[test]
-    fn prompt() {
-        macro_rules! prompt {
-            ($question:expr, $user_input:expr) => {
-                let response = String::from($user_input);
-                let input = Cursor::new(response.clone().into_bytes());
-                let mut displayed_output = Cursor::new(Vec::new());
-                let result;
+    fn placeholders() {
+        let input = Cursor::new(b"prod\n\n".to_vec());
+        let mut q = Question::with_cursor("Deploy to {default} ({choices})?", input, Vec::new());
+        q.acceptable(vec!["staging", "live"])
+            .default(Answer::RESPONSE(String::from("staging")))
+            .clarification("Attempt {attempt} failed")
+            .tries(2);
+        assert_eq!(Some(Answer::RESPONSE(String::from("staging"))), q.ask());
+        assert_eq!("Deploy to {default} ({choices})?", q.question);
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!(
+            "Deploy to staging (staging, live)? \
+             Attempt 1 failed\nDeploy to staging (staging, live)? ",
+            output
+        );
 
-                {
-                    let mut q = Question::with_cursor($question, input, &mut displayed_output);
-                    result = q.prompt_user($question).unwrap();
-                } // end borrow of output before using it
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let mut q = Question::with_cursor("Attempt {attempt}: continue?", input, Vec::new());
+        q.default(Answer::NO);
+        assert_eq!(Answer::YES, q.confirm());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Attempt 1: continue? Attempt 2: continue? ", output);
+    }
 
-                let output =
-                    String::from_utf8(displayed_output.into_inner()).expect("Not UTF-8");
-                assert_eq!($question, output);
-                assert_eq!(response, result);
+    #[test]
+    fn stalled() {
+        macro_rules! stalled {
+            ($i:expr, $expected:expr, $error:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Continue?", input, Vec::new());
+                assert_eq!($expected, q.confirm());
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Continue?", input, Vec::new());
+                q.yes_no().until_acceptable();
+                assert_eq!($error, q.try_ask().err());
             };
         }
-        prompt!("what is the meaning to life", "42");
-        prompt!("the universe", "42");
-        prompt!("everything", "42");
-        prompt!("Continue", "yes");
-        prompt!(
-            "What is the only manmade object visable from the moon?",
-            "The Great Wall of China"
+        let stalled = Some(AskError::NoInteractiveInput);
+        let maybe = "maybe\n".repeat(5);
+        stalled!(maybe.clone() + "y\n", Answer::CANCELLED, stalled.clone());
+        stalled!("maybe\n".repeat(4) + "y\n", Answer::YES, None);
+        stalled!("maybe\nperhaps\n".repeat(3) + "y\n", Answer::YES, None);
+        stalled!("\n".repeat(5) + "y\n", Answer::CANCELLED, stalled.clone());
+
+        let input = Cursor::new((maybe.clone() + "y\n").into_bytes());
+        let mut q = Question::with_cursor("Continue?", input, Vec::new());
+        q.tty = true;
+        assert_eq!(Answer::YES, q.confirm());
+
+        let input = Cursor::new((maybe + "8\n").into_bytes());
+        let mut q = Question::with_cursor("Workers?", input, Vec::new());
+        q.until_acceptable();
+        assert_eq!(
+            None,
+            q.ask_parsed(|r| r.parse::<u8>().map_err(|e| e.to_string()))
         );
+        assert_eq!(Some(AskError::NoInteractiveInput), q.error);
     }
 
     #[test]
-    fn basic_confirm() {
-        macro_rules! confirm {
-            ($i:expr, $q:expr, $expected:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let actual = Question::with_cursor($q, input, output).confirm();
-                assert_eq!($expected, actual);
+    fn yes_no_all() {
+        macro_rules! yes_no_all {
+            ($i:expr, $default:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Overwrite a.txt?", input, Vec::new());
+                q.yes_no_all().show_defaults();
+                if let Some(default) = $default {
+                    q.default(default);
+                }
+                assert_eq!($expected, q.confirm());
+                let output = String::from_utf8(q.writer).expect("Not UTF-8");
+                assert_eq!($shown, output);
             };
         }
-        confirm!("y", "Continue?", Answer::YES);
-        confirm!("yes", "Continue?", Answer::YES);
-        confirm!("n", "Continue?", Answer::NO);
-        confirm!("no", "Continue?", Answer::NO);
+        yes_no_all!("y\n", None, Answer::YES, "Overwrite a.txt? (y/n/a/q) ");
+        yes_no_all!("a\n", None, Answer::ALL, "Overwrite a.txt? (y/n/a/q) ");
+        yes_no_all!("all\n", None, Answer::ALL, "Overwrite a.txt? (y/n/a/q) ");
+        yes_no_all!("q\n", None, Answer::NEVER, "Overwrite a.txt? (y/n/a/q) ");
+        yes_no_all!(
+            "never\n",
+            Some(Answer::YES),
+            Answer::NEVER,
+            "Overwrite a.txt? (Y/n/a/q) "
+        );
+        assert!(Answer::ALL.is_yes());
+        assert!(Answer::NEVER.is_no());
+        assert!(!bool::from(Answer::NEVER));
     }
 
     #[test]
-    fn basic_ask() {
-        macro_rules! ask {
-            ($i:expr, $q:expr, $expected:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let actual = Question::with_cursor($q, input, output).ask();
-                assert_eq!(Some(Answer::RESPONSE(String::from($expected))), actual);
+    fn confirm_phrase() {
+        macro_rules! confirm_phrase {
+            ($i:expr, $insensitive:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Type 'prod-db':", input, Vec::new());
+                q.tries(2).clarification("Nothing was deleted");
+                if $insensitive {
+                    q.case_insensitive();
+                }
+                assert_eq!($expected, q.confirm_phrase("prod-db"));
             };
         }
+        confirm_phrase!("prod-db\n", false, Answer::YES);
+        confirm_phrase!("PROD-DB\nprod\n", false, Answer::NO);
+        confirm_phrase!("prod\nprod-db\n", false, Answer::YES);
+        confirm_phrase!("PROD-DB\n", true, Answer::YES);
+        confirm_phrase!("y\nn\n", false, Answer::NO);
+        confirm_phrase!("y\n", false, Answer::CANCELLED);
 
-        ask!("y\n", "Continue?", "y");
-        ask!("yes\n", "Continue?", "yes");
-        ask!("n\n", "Continue?", "n");
-        ask!("no\n", "Continue?", "no");
-        ask!("the universe,\n", "42", "the universe,");
-        ask!("and everything\n", "42", "and everything");
-        ask!(
-            "what is the meaning to life,\n",
-            "42",
-            "what is the meaning to life,"
-        );
+        let input = Cursor::new(b"prod\n".to_vec());
+        let mut q = Question::with_cursor("Type 'prod-db':", input, Vec::new());
+        q.clarification("Nothing was deleted").layer(AssumeYes);
+        assert_eq!(Answer::NO, q.confirm_phrase("prod-db"));
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Type 'prod-db': Nothing was deleted\n", output);
+    }
 
-        ask!("y", "Continue?", "y");
-        ask!("yes", "Continue?", "yes");
-        ask!("n", "Continue?", "n");
-        ask!("no", "Continue?", "no");
-        ask!("the universe,", "42", "the universe,");
-        ask!("and everything", "42", "and everything");
-        ask!(
-            "what is the meaning to life,",
-            "42",
-            "what is the meaning to life,"
-        );
+    #[test]
+    fn countdown() {
+        let input = Cursor::new(b"\n".to_vec());
+        let mut q = Question::with_cursor("Rebooting in {seconds}s, continue?", input, Vec::new());
+        q.default(Answer::YES)
+            .show_defaults()
+            .countdown(Duration::from_secs(10));
+        assert_eq!(Answer::YES, q.confirm());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Rebooting in 10s, continue? (Y/n) ", output);
     }
 
     #[test]
-    fn set_clarification() {
-        macro_rules! confirm_clarification {
-            ($i:expr, $q:expr, $clarification:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let mut q = Question::with_cursor($q, input, output);
-                q.clarification($clarification);
-                assert_eq!($clarification, q.clarification.unwrap());
+    fn wait_for_key() {
+        macro_rules! wait_for_key {
+            ($i:expr, $keys:expr, $output:expr, $rest:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Press any key...", input, Vec::new());
+                q.keys = $keys;
+                q.wait_for_key();
+                let mut rest = String::new();
+                q.reader.read_to_string(&mut rest).unwrap();
+                assert_eq!($rest, rest);
+                let output = String::from_utf8(q.writer).expect("Not UTF-8");
+                assert_eq!($output, output);
             };
         }
-        confirm_clarification!("what is the meaning to life", "42", "14*3");
-        confirm_clarification!("Continue?", "wat", "Please respond with yes/no");
+        wait_for_key!(b"xyz", true, "Press any key... \n", "yz");
+        wait_for_key!(b"\x1b[Ax", true, "Press any key... \n", "x");
+        wait_for_key!(b"xyz\nnext", false, "Press any key... ", "next");
     }
 
     #[test]
-    fn set_max_tries() {
-        macro_rules! confirm_max_tries {
-            ($i:expr, $q:expr, $max_tries:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let mut q = Question::with_cursor($q, input, output);
-                q.tries($max_tries);
-                assert_eq!($max_tries, q.tries.unwrap());
+    fn instant() {
+        macro_rules! instant {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Proceed?", input, Vec::new());
+                q.keys = $keys;
+                let actual = q.default(Answer::YES).instant().confirm();
+                assert_eq!($expected, actual);
             };
         }
-        confirm_max_tries!("what is the meaning to life", "42", 42);
-        confirm_max_tries!("Continue?", "wat", 0x79);
+        instant!(b"n", true, Answer::NO);
+        instant!(b"xY", true, Answer::YES);
+        instant!(b"\r", true, Answer::YES);
+        instant!(b"n\n", false, Answer::NO);
+
+        let input = Cursor::new(b"qn".to_vec());
+        let mut q = Question::with_cursor("Proceed?", input, Vec::new());
+        q.keys = true;
+        q.show_defaults().instant().confirm();
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Proceed? (y/n) n\n", output);
     }
 
     #[test]
-    fn set_until_acceptable() {
-        macro_rules! confirm_until_acceptable {
-            ($i:expr, $q:expr, $until_acceptable:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let mut q = Question::with_cursor($q, input, output);
-                q.until_acceptable();
-                assert_eq!($until_acceptable, q.until_acceptable);
+    fn multiline() {
+        macro_rules! multiline {
+            ($i:expr, $terminator:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Notes?", input, Vec::new())
+                    .multiline_until($terminator)
+                    .ask();
+                assert_eq!(Some(Answer::RESPONSE(String::from($expected))), actual);
             };
         }
-        confirm_until_acceptable!("what is the meaning to life", "42", true);
+        multiline!("one\n  two\n\nthree\n", "", "one\n  two");
+        multiline!("one\ntwo", "", "one\ntwo");
+        multiline!("one\n\ntwo\n.\nthree\n", ".", "one\n\ntwo");
+        multiline!("\none\n", "", "");
     }
 
     #[test]
-    fn set_show_defaults() {
-        macro_rules! confirm_show_defaults {
-            ($i:expr, $q:expr, $show_defaults:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let mut q = Question::with_cursor($q, input, output);
-                q.show_defaults();
-                assert_eq!($show_defaults, q.show_defaults);
+    fn ask_many() {
+        macro_rules! many {
+            ($i:expr, $sentinel:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual =
+                    Question::with_cursor("Tag?", input, Vec::new()).ask_many_until($sentinel);
+                let expected: Vec<Answer> = $expected
+                    .iter()
+                    .map(|s: &&str| Answer::RESPONSE(s.to_string()))
+                    .collect();
+                assert_eq!(expected, actual);
             };
         }
-        confirm_show_defaults!("what is the meaning to life", "42", true);
+        many!("a\nb\n\nc\n", "", ["a", "b"]);
+        many!("a\nb", "", ["a", "b"]);
+        many!("a\n\nb\ndone\nc\n", "done", ["a", "", "b"]);
+        many!("a\n", "done", ["a"]);
+        many!("", "", [] as [&str; 0]);
+
+        let input = Cursor::new(String::from("yes\nmaybe\nno\n\n").into_bytes());
+        let mut q = Question::with_cursor("Again?", input, Vec::new());
+        q.yes_no().until_acceptable().skip_token("-");
+        assert_eq!(vec![Answer::YES, Answer::NO], q.ask_many());
+        assert_eq!(Some(String::from("-")), q.skip);
     }
 
     #[test]
-    fn set_yes_no() {
-        macro_rules! confirm_yes_no {
-            ($i:expr, $q:expr, $yes_no:expr) => {
-                let response = String::from($i);
-                let input = Cursor::new(response.into_bytes());
-                let output = Cursor::new(Vec::new());
-                let mut q = Question::with_cursor($q, input, output);
-                q.yes_no();
-                assert_eq!($yes_no, q.yes_no);
-            };
+    fn format() {
+        let input = Cursor::new(String::from("jo\n\njo@example.com\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Email?", input, &mut output)
+                .format(Format::Email)
+                .until_acceptable()
+                .ask();
+            assert_eq!(
+                Some(Answer::RESPONSE(String::from("jo@example.com"))),
+                actual
+            );
         }
-        confirm_yes_no!("what is the meaning to life", "42", true);
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Email? 'jo' is not a valid email address\nEmail? '' is not a valid email address\nEmail? ",
+            output
+        );
+
+        let input = Cursor::new(String::from("ftp://host\n").into_bytes());
+        let actual = Question::with_cursor("Mirror?", input, Vec::new())
+            .accept("ftp://host")
+            .accept("mirror")
+            .format(Format::Url)
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("ftp://host"))), actual);
+
+        let input = Cursor::new(String::from("mirror\n").into_bytes());
+        let actual = Question::with_cursor("Mirror?", input, Vec::new())
+            .accept("ftp://host")
+            .accept("mirror")
+            .format(Format::Url)
+            .ask();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn completions() {
+        let input = Cursor::new(String::from("ma\n").into_bytes());
+        let actual = Question::with_cursor("Branch?", input, Vec::new())
+            .completions(&["main", "master"])
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("ma"))), actual);
+    }
+
+    #[test]
+    fn complete_with() {
+        let input = Cursor::new(String::from("b\n").into_bytes());
+        let mut q = Question::with_cursor("Branch?", input, Vec::new());
+        q.complete_with(|text| vec![format!("{}-1", text)]);
+        let completions = q.completions.clone().unwrap();
+        assert_eq!(vec![String::from("b-1")], completions("b"));
+        assert_eq!(Some(Answer::RESPONSE(String::from("b"))), q.ask());
     }
 }
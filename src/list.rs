@@ -0,0 +1,152 @@
+use std::io::{Read, Write};
+
+use {Answer, AnswerRef, AskError, Invalid, Question, Retry};
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for a list of items in a single response, such as
+    /// "red, green, blue", split on `delimiter` and trimmed.
+    ///
+    /// When acceptable responses are set each item is matched on
+    /// its own, and the items that match none of them are named
+    /// when the question is asked again, once or as set by
+    /// `tries` or `until_acceptable`. An empty response uses the
+    /// default, if it is a `RESPONSE`, split the same way. `None`
+    /// is returned if no acceptable list is given or input ends.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let colors = Question::new("Colors?")
+    ///     .acceptable(vec!["red", "green", "blue"])
+    ///     .until_acceptable()
+    ///     .delimited(',');
+    /// ```
+    pub fn delimited(&mut self, delimiter: char) -> Option<Vec<String>> {
+        self.resolve_default();
+        self.drawn = 0;
+        let validate = self.valid_responses.is_some() || self.acceptable.is_some();
+        let tries = self.attempts();
+        let mut retry = None;
+        for attempt in 0..tries {
+            let prompt = self.build_prompt(retry.as_ref());
+            self.ended = false;
+            let mut response = self.prompt_user(&prompt).ok()?;
+            if self.ended {
+                return None;
+            }
+            if response.is_empty() {
+                if let Some(Answer::RESPONSE(ref default)) = self.default {
+                    response = default.clone();
+                }
+            }
+            let items = response
+                .split(delimiter)
+                .map(str::trim)
+                .filter(|item| !item.is_empty());
+            if !validate {
+                return Some(items.map(String::from).collect());
+            }
+            let mut accepted = Vec::new();
+            let mut rejected = Vec::new();
+            for item in items {
+                match self.match_response(item) {
+                    Ok(AnswerRef::RESPONSE(s)) => accepted.push(s.to_string()),
+                    Ok(_) => accepted.push(item.to_string()),
+                    Err(_) => rejected.push(item.to_string()),
+                }
+            }
+            if rejected.is_empty() {
+                return Some(accepted);
+            }
+            self.fail(AskError::Rejected(response));
+            if self.asks_once() || attempt + 1 == tries {
+                break;
+            }
+            self.wait_before_retry(attempt);
+            retry = Some(Retry {
+                invalid: Invalid::Items(rejected),
+                remaining: Some(tries - attempt - 1).filter(|_| !self.until_acceptable),
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use {Answer, Question};
+
+    #[test]
+    fn delimited() {
+        macro_rules! delimited {
+            ($i:expr, $delimiter:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual =
+                    Question::with_cursor("Colors?", input, Vec::new()).delimited($delimiter);
+                let expected: Option<Vec<&str>> = $expected;
+                let expected = expected.map(|v| v.iter().map(|s| s.to_string()).collect());
+                assert_eq!(expected, actual);
+            };
+        }
+        delimited!(
+            "red, green ,blue\n",
+            ',',
+            Some(vec!["red", "green", "blue"])
+        );
+        delimited!("a;;b; \n", ';', Some(vec!["a", "b"]));
+        delimited!("\n", ',', Some(vec![]));
+        delimited!("", ',', None);
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let actual = Question::with_cursor("Colors?", input, Vec::new())
+            .default(Answer::RESPONSE(String::from("red,blue")))
+            .delimited(',');
+        assert_eq!(
+            Some(vec![String::from("red"), String::from("blue")]),
+            actual
+        );
+    }
+
+    #[test]
+    fn delimited_validation() {
+        let input = Cursor::new(String::from("Red, purple, pink\nred, GREEN\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Colors?", input, &mut output)
+                .acceptable(vec!["red", "green", "blue"])
+                .until_acceptable()
+                .show_defaults()
+                .delimited(',');
+            assert_eq!(
+                Some(vec![String::from("red"), String::from("green")]),
+                actual
+            );
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert!(
+            output.contains("Not valid: 'purple', 'pink'\n"),
+            "{}",
+            output
+        );
+
+        let input = Cursor::new(String::from("red, purple\n").into_bytes());
+        let actual = Question::with_cursor("Colors?", input, Vec::new())
+            .acceptable(vec!["red", "green"])
+            .delimited(',');
+        assert_eq!(None, actual);
+
+        let input = Cursor::new(String::from("purple\n").into_bytes());
+        let actual = Question::with_cursor("Colors?", input, Vec::new())
+            .acceptable(vec!["red", "green"])
+            .default(Answer::RESPONSE(String::from("red")))
+            .until_acceptable()
+            .delimited(',');
+        assert_eq!(None, actual);
+    }
+}
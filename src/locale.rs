@@ -0,0 +1,435 @@
+//! The words and messages built into every prompt, and with the
+//! `i18n` feature, translations of them.
+
+#[cfg(feature = "i18n")]
+use std::collections::HashMap;
+#[cfg(feature = "i18n")]
+use std::error::Error;
+#[cfg(feature = "i18n")]
+use std::fmt;
+#[cfg(feature = "i18n")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "i18n")]
+use {Answer, Question};
+
+/// The English text of every built in message, by id. Arguments
+/// are written as Fluent placeables, such as `{ $count }`.
+const ENGLISH: &[(&str, &str)] = &[
+    ("yes", "yes"),
+    ("yes-short", "y"),
+    ("no", "no"),
+    ("no-short", "n"),
+    ("all", "all"),
+    ("never", "never"),
+    ("hint-yes", "Y/n"),
+    ("hint-no", "y/N"),
+    ("hint-yes-no", "y/n"),
+    ("hint-all", "a/q"),
+    ("hint-skip", "skip"),
+    ("attempts-left-one", "1 attempt left"),
+    ("attempts-left", "{ $count } attempts left"),
+    (
+        "ambiguous",
+        "'{ $response }' could be any of: { $candidates }",
+    ),
+    ("did-you-mean-one", "Did you mean { $suggestion }?"),
+    (
+        "did-you-mean",
+        "Did you mean { $suggestions } or { $last }?",
+    ),
+    ("not-valid", "Not valid: { $items }"),
+    ("required", "This field is required"),
+    ("too-short", "Please enter at least { $min } characters"),
+    ("too-long", "Please enter at most { $max } characters"),
+    (
+        "too-weak",
+        "That passphrase is { $strength }, please choose a stronger one",
+    ),
+    (
+        "invalid-encoding",
+        "The response was not valid UTF-8, please try again",
+    ),
+    ("preview-skipped", "(skipped)"),
+    ("preview-no-default", "(no default)"),
+    ("review-which", "Which answer would you like to change?"),
+    ("not-parsed", "'{ $response }' is not valid: { $reason }"),
+    ("not-format", "'{ $response }' is not a valid { $format }"),
+    ("format-email", "email address"),
+    ("format-hostname", "hostname"),
+    ("format-url", "URL"),
+    (
+        "not-host-port",
+        "'{ $response }' is not in the form host:port",
+    ),
+    ("not-port", "'{ $port }' is not a port number"),
+    (
+        "port-out-of-range",
+        "Port { $port } is out of range (0-65535)",
+    ),
+    ("host-missing", "The host is missing"),
+    ("not-resolved", "'{ $host }' could not be resolved"),
+    (
+        "not-duration",
+        "'{ $response }' is not a duration like 90s, 5m or 2h30m",
+    ),
+    ("duration-between", "Must be between { $min } and { $max }"),
+    ("path-required", "Please enter a path"),
+    ("grid-numbers", "Please enter { $count } numbers"),
+    ("choice-default", "(default)"),
+    (
+        "choice-page",
+        "{ $first }-{ $last } of { $count }, n=next, p=prev",
+    ),
+    (
+        "choice-required",
+        "Please enter a number from 1 to { $count } or one of the choices",
+    ),
+    ("no-matches", "(no matches)"),
+    ("matches-shown", "({ $shown } of { $count })"),
+];
+
+/// The English text of the message `id`.
+pub(crate) fn english(id: &str) -> &'static str {
+    ENGLISH
+        .iter()
+        .find(|&&(key, _)| key == id)
+        .map(|&(_, text)| text)
+        .unwrap_or_else(|| panic!("no built in message '{}'", id))
+}
+
+/// Replace each `{ $name }` placeable in `text` with its argument.
+pub(crate) fn fill(text: &str, args: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        filled += &rest[..start];
+        let name = rest[start + 1..end].trim().trim_start_matches('$');
+        match args.iter().find(|&&(key, _)| key == name) {
+            Some(&(_, value)) => filled += value,
+            None => filled += &rest[start..=end],
+        }
+        rest = &rest[end + 1..];
+    }
+    filled + rest
+}
+
+/// The built in messages of a question, held apart from it so they
+/// can be looked up while it is being asked.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Texts {
+    #[cfg(feature = "i18n")]
+    pub(crate) locale: Option<Locale>,
+}
+
+impl Texts {
+    /// The message `id`, with its arguments filled in.
+    pub(crate) fn text(&self, id: &str, args: &[(&str, &str)]) -> String {
+        #[cfg(feature = "i18n")]
+        {
+            if let Some(ref locale) = self.locale {
+                return fill(locale.text(id), args);
+            }
+        }
+        fill(english(id), args)
+    }
+}
+
+/// Translations of the built in words and messages, such as
+/// "yes", the "Y/n" hint and "Did you mean ...?".
+///
+/// Messages are looked up by id, falling back to English for any
+/// a locale does not supply. The ids, with their English text,
+/// are:
+///
+/// | Id                   | English                                               |
+/// |----------------------|-------------------------------------------------------|
+/// | `yes`, `yes-short`   | `yes`, `y`                                            |
+/// | `no`, `no-short`     | `no`, `n`                                             |
+/// | `all`, `never`       | `all`, `never`                                        |
+/// | `hint-yes`           | `Y/n`                                                 |
+/// | `hint-no`            | `y/N`                                                 |
+/// | `hint-yes-no`        | `y/n`                                                 |
+/// | `hint-all`           | `a/q`                                                 |
+/// | `hint-skip`          | `skip`                                                |
+/// | `attempts-left-one`  | `1 attempt left`                                      |
+/// | `attempts-left`      | `{ $count } attempts left`                            |
+/// | `ambiguous`          | `'{ $response }' could be any of: { $candidates }`    |
+/// | `did-you-mean-one`   | `Did you mean { $suggestion }?`                       |
+/// | `did-you-mean`       | `Did you mean { $suggestions } or { $last }?`         |
+/// | `not-valid`          | `Not valid: { $items }`                               |
+/// | `required`           | `This field is required`                              |
+/// | `too-short`          | `Please enter at least { $min } characters`           |
+/// | `too-long`           | `Please enter at most { $max } characters`            |
+/// | `too-weak`           | `That passphrase is { $strength }, please choose ...` |
+/// | `invalid-encoding`   | `The response was not valid UTF-8, please try again`  |
+/// | `preview-skipped`    | `(skipped)`                                           |
+/// | `preview-no-default` | `(no default)`                                        |
+/// | `review-which`       | `Which answer would you like to change?`              |
+/// | `not-parsed`         | `'{ $response }' is not valid: { $reason }`           |
+/// | `not-format`         | `'{ $response }' is not a valid { $format }`          |
+/// | `format-email`       | `email address`                                       |
+/// | `format-hostname`    | `hostname`                                            |
+/// | `format-url`         | `URL`                                                 |
+/// | `not-host-port`      | `'{ $response }' is not in the form host:port`        |
+/// | `not-port`           | `'{ $port }' is not a port number`                    |
+/// | `port-out-of-range`  | `Port { $port } is out of range (0-65535)`            |
+/// | `host-missing`       | `The host is missing`                                 |
+/// | `not-resolved`       | `'{ $host }' could not be resolved`                   |
+/// | `not-duration`       | `'{ $response }' is not a duration like 90s, ...`     |
+/// | `duration-between`   | `Must be between { $min } and { $max }`               |
+/// | `path-required`      | `Please enter a path`                                 |
+/// | `grid-numbers`       | `Please enter { $count } numbers`                     |
+/// | `choice-default`     | `(default)`                                           |
+/// | `choice-page`        | `{ $first }-{ $last } of { $count }, n=next, p=prev`  |
+/// | `choice-required`    | `Please enter a number from 1 to { $count } or ...`   |
+/// | `no-matches`         | `(no matches)`                                        |
+/// | `matches-shown`      | `({ $shown } of { $count })`                          |
+///
+/// The localized yes and no words are accepted by `yes_no`
+/// questions alongside the English ones, so scripted input keeps
+/// working in every language.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Locale, Question};
+/// let german = Locale::from_ftl(
+///     "yes = ja\n\
+///      yes-short = j\n\
+///      no = nein\n\
+///      hint-yes = J/n\n\
+///      hint-no = j/N\n\
+///      hint-yes-no = j/n\n",
+/// )
+/// .unwrap();
+///
+/// Question::new("Fortfahren?")
+///     .locale(german)
+///     .show_defaults()
+///     .confirm();
+/// ```
+#[cfg(feature = "i18n")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Locale {
+    messages: HashMap<String, String>,
+}
+
+#[cfg(feature = "i18n")]
+impl Locale {
+    /// Create a locale supplying no messages, so every message is
+    /// in English until set with `message`.
+    pub fn new() -> Locale {
+        Locale::default()
+    }
+
+    /// Load a locale from a Fluent resource, such as an `.ftl`
+    /// file, with one `id = text` message per line. Comments
+    /// starting with `#`, blank lines and indented continuation
+    /// lines are supported; terms, attributes and selectors are
+    /// not.
+    pub fn from_ftl(source: &str) -> Result<Locale, LocaleError> {
+        let mut locale = Locale::new();
+        let mut last: Option<String> = None;
+        for (number, line) in source.lines().enumerate() {
+            let number = number + 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with(' ') {
+                match last {
+                    Some(ref id) => {
+                        let text = locale.messages.get_mut(id).expect("continued message");
+                        if !text.is_empty() {
+                            text.push('\n');
+                        }
+                        text.push_str(line.trim());
+                        continue;
+                    }
+                    None => return Err(LocaleError(number, String::from("nothing to continue"))),
+                }
+            }
+            let (id, text) = match line.find('=') {
+                Some(equals) => (line[..equals].trim(), line[equals + 1..].trim()),
+                None => return Err(LocaleError(number, String::from("expected 'id = text'"))),
+            };
+            let valid = id.starts_with(|c: char| c.is_ascii_alphabetic())
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(LocaleError(number, format!("'{}' is not a message id", id)));
+            }
+            locale.message(id, text);
+            last = Some(id.to_string());
+        }
+        Ok(locale)
+    }
+
+    /// Supply the text of the message `id`, replacing any given
+    /// before.
+    pub fn message(&mut self, id: &str, text: &str) -> &mut Locale {
+        self.messages.insert(id.to_string(), text.to_string());
+        self
+    }
+
+    /// The text of the message `id`, if this locale supplies it.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+
+    /// The text of the message `id`, in English if this locale
+    /// does not supply it.
+    pub(crate) fn text(&self, id: &str) -> &str {
+        self.get(id).unwrap_or_else(|| english(id))
+    }
+}
+
+/// Why a Fluent resource could not be loaded as a `Locale`, with
+/// the line number it failed on.
+#[cfg(feature = "i18n")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocaleError(usize, String);
+
+#[cfg(feature = "i18n")]
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid locale on line {}: {}", self.0, self.1)
+    }
+}
+
+#[cfg(feature = "i18n")]
+impl Error for LocaleError {}
+
+#[cfg(feature = "i18n")]
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Show the built in words and messages from `locale`. Yes/no
+    /// questions accept its yes and no words as well as the
+    /// English ones.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Locale, Question};
+    /// let mut french = Locale::new();
+    /// french
+    ///     .message("yes", "oui")
+    ///     .message("yes-short", "o")
+    ///     .message("no", "non")
+    ///     .message("hint-yes-no", "o/n");
+    ///
+    /// Question::new("Continuer ?")
+    ///     .locale(french)
+    ///     .show_defaults()
+    ///     .confirm();
+    /// ```
+    pub fn locale(&mut self, locale: Locale) -> &mut Question<R, W> {
+        self.locale = Some(locale);
+        if self.yes_no {
+            self.yes_no();
+        }
+        self
+    }
+
+    /// Accept the locale's yes and no words, if it has any.
+    pub(crate) fn localize_yes_no(&mut self) {
+        let words = match self.locale {
+            Some(ref locale) => [
+                (locale.text("yes").to_string(), Answer::YES),
+                (locale.text("yes-short").to_string(), Answer::YES),
+                (locale.text("no").to_string(), Answer::NO),
+                (locale.text("no-short").to_string(), Answer::NO),
+            ],
+            None => return,
+        };
+        for (word, answer) in words {
+            self.map_response(&word, answer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill() {
+        let text = english("ambiguous");
+        let args = [("response", "in"), ("candidates", "info, install")];
+        assert_eq!(
+            "'in' could be any of: info, install",
+            super::fill(text, &args)
+        );
+        assert_eq!("{ $unknown } {", super::fill("{ $unknown } {", &[]));
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn from_ftl() {
+        let source = "# German\n\nyes = ja\nno =  nein \nattempts-left =\n    { $count } Versuche\n    übrig\n";
+        let locale = Locale::from_ftl(source).unwrap();
+        assert_eq!(Some("ja"), locale.get("yes"));
+        assert_eq!(Some("nein"), locale.get("no"));
+        assert_eq!(
+            Some("{ $count } Versuche\nübrig"),
+            locale.get("attempts-left")
+        );
+        assert_eq!("y", locale.text("yes-short"));
+
+        let error = Locale::from_ftl("yes = ja\nnein\n").unwrap_err();
+        assert_eq!(
+            "invalid locale on line 2: expected 'id = text'",
+            error.to_string()
+        );
+        assert!(Locale::from_ftl("-term = x\n").is_err());
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn locale() {
+        use std::io::Cursor;
+
+        let mut german = Locale::new();
+        german.message("hint-no", "j/N").message("yes", "ja");
+        let input = Cursor::new(String::from("ja\n").into_bytes());
+        let mut output = Vec::new();
+        let answer = Question::with_cursor("Weiter?", input, &mut output)
+            .default(Answer::NO)
+            .show_defaults()
+            .locale(german)
+            .confirm();
+        assert_eq!(Answer::YES, answer);
+        assert_eq!("Weiter? (j/N) ", String::from_utf8(output).unwrap());
+
+        let mut french = Locale::new();
+        french.message("not-valid", "Non valide : { $items }");
+        let input = Cursor::new(String::from("a,x\na\n").into_bytes());
+        let mut output = Vec::new();
+        Question::with_cursor("Lettres ?", input, &mut output)
+            .locale(french)
+            .acceptable(vec!["a", "b"])
+            .until_acceptable()
+            .delimited(',');
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!("Lettres ? Non valide : 'x'\nLettres ? ", output);
+
+        let mut spanish = Locale::new();
+        spanish.message("not-duration", "'{ $response }' no es una duración");
+        let input = Cursor::new(String::from("pronto\n5m\n").into_bytes());
+        let mut output = Vec::new();
+        Question::with_cursor("¿Espera?", input, &mut output)
+            .locale(spanish)
+            .until_acceptable()
+            .duration();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!("¿Espera? 'pronto' no es una duración\n¿Espera? ", output);
+    }
+}
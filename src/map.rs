@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use {Answer, Question};
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for `KEY=VALUE` entries, such as environment variables
+    /// or labels, until an empty line is entered or input ends,
+    /// returning them as a map.
+    ///
+    /// A line may hold several entries separated by commas or
+    /// spaces, so values cannot contain either. Keys start with a
+    /// letter or underscore followed by letters, digits, `_`, `-`
+    /// or `.`. A line with an invalid entry is rejected as a
+    /// whole, and asked for again as set by `tries` or
+    /// `until_acceptable`; `None` is returned if it is never
+    /// corrected. Later entries replace earlier ones with the same
+    /// key. If no entries are given, a default set with
+    /// `Answer::RESPONSE` is read the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let env = Question::new("Environment variable? (KEY=VALUE, blank to finish)")
+    ///     .until_acceptable()
+    ///     .ask_map();
+    /// ```
+    pub fn ask_map(&mut self) -> Option<HashMap<String, String>> {
+        self.resolve_default();
+        let default = self.default.take();
+        let mut map = HashMap::new();
+        let result = loop {
+            match self.ask_parsed(parse_entries) {
+                Some(ref entries) if entries.is_empty() => break Some(()),
+                Some(entries) => map.extend(entries),
+                None if self.ended => break Some(()),
+                None => break None,
+            }
+        };
+        self.default = default;
+        result?;
+        if map.is_empty() {
+            if let Some(Answer::RESPONSE(ref default)) = self.default {
+                map.extend(parse_entries(default).ok()?);
+            }
+        }
+        Some(map)
+    }
+}
+
+/// Parse the `KEY=VALUE` entries on a line.
+fn parse_entries(line: &str) -> Result<Vec<(String, String)>, String> {
+    line.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (key, value) = match entry.find('=') {
+                Some(i) => (&entry[..i], &entry[i + 1..]),
+                None => return Err(format!("'{}' is not in the form KEY=VALUE", entry)),
+            };
+            if !is_key(key) {
+                return Err(format!("'{}' is not a valid key", key));
+            }
+            Ok((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn is_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn ask_map() {
+        macro_rules! map {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Label?", input, Vec::new())
+                    .tries(2)
+                    .ask_map();
+                let expected: Option<Vec<(&str, &str)>> = $expected;
+                let expected: Option<HashMap<String, String>> = expected.map(|entries| {
+                    entries
+                        .iter()
+                        .map(|&(k, v)| (k.to_string(), v.to_string()))
+                        .collect()
+                });
+                assert_eq!(expected, actual);
+            };
+        }
+        map!("A=1\nB=2\n\nC=3\n", Some(vec![("A", "1"), ("B", "2")]));
+        map!(
+            "A=1, b.c=x y=\n",
+            Some(vec![("A", "1"), ("b.c", "x"), ("y", "")])
+        );
+        map!("A=1\nA=a=b", Some(vec![("A", "a=b")]));
+        map!("A=1\n1A=2\nB=2\n", Some(vec![("A", "1"), ("B", "2")]));
+        map!("A\nB\n", None);
+        map!("", Some(vec![]));
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let actual = Question::with_cursor("Labels?", input, Vec::new())
+            .default(Answer::RESPONSE(String::from("tier=web")))
+            .ask_map();
+        let mut expected = HashMap::new();
+        expected.insert(String::from("tier"), String::from("web"));
+        assert_eq!(Some(expected), actual);
+    }
+
+    #[test]
+    fn entries() {
+        assert_eq!(
+            Err(String::from("'-x' is not a valid key")),
+            parse_entries("-x=1")
+        );
+        assert_eq!(
+            Err(String::from("'FOO' is not in the form KEY=VALUE")),
+            parse_entries("A=1 FOO")
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use {PromptSession, Question};
+
+/// Whether the platform has a clock to read and sleep by. Plain
+/// WebAssembly, outside of WASI, has neither, and reading the time
+/// or sleeping panics there, so questions asked through a
+/// `Prompter` in the browser do without.
+pub(crate) const CLOCK: bool = !cfg!(all(target_arch = "wasm32", target_os = "unknown"));
+
+/// How asking a question went, returned by
+/// `Question::last_outcome`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AskOutcome {
+    attempts: u64,
+    elapsed: Duration,
+    default_used: bool,
+}
+
+impl AskOutcome {
+    /// The number of responses read, including those that were not
+    /// accepted. A question answered by a `Layer` without asking
+    /// took no attempts.
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// How long the question took to answer, or zero on platforms
+    /// without a clock, such as WebAssembly in the browser.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Whether the answer is the default rather than a response
+    /// the user gave: taken because the last response was empty,
+    /// without asking by a `Layer` that took it with
+    /// `Context::take_default`, such as the one installed by
+    /// `NonInteractive::UseDefaults`, or by a dry run.
+    pub fn default_used(&self) -> bool {
+        self.default_used
+    }
+}
+
+/// What has happened so far while a question is being asked.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Tally {
+    attempts: u64,
+    last_empty: bool,
+    default_taken: bool,
+    last_hash: u64,
+    repeats: u64,
+}
+
+impl Tally {
+    /// The number of responses read so far.
+    pub(crate) fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Note that the default was taken without reading a response.
+    pub(crate) fn take_default(&mut self) {
+        self.default_taken = true;
+    }
+
+    /// Count a response read from the user.
+    pub(crate) fn response(&mut self, response: &str) {
+        // Only a hash is kept, so secret responses are not.
+        let mut hasher = DefaultHasher::new();
+        response.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.repeats = if self.attempts > 0 && hash == self.last_hash {
+            self.repeats + 1
+        } else {
+            1
+        };
+        self.attempts += 1;
+        self.last_empty = response.is_empty();
+        self.last_hash = hash;
+    }
+
+    /// How many of the latest responses in a row were the same.
+    pub(crate) fn repeats(&self) -> u64 {
+        self.repeats
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// How the last time this question was asked went: how many
+    /// attempts it took, how long, and whether the default was
+    /// used. `None` until it has been asked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let mut question = Question::new("Install location?");
+    /// question.ask();
+    /// if let Some(outcome) = question.last_outcome() {
+    ///     if outcome.attempts() > 2 {
+    ///         println!("Try /usr/local, or see --help for more options");
+    ///     }
+    /// }
+    /// ```
+    pub fn last_outcome(&self) -> Option<AskOutcome> {
+        self.outcome
+    }
+
+    /// Ask with `ask`, recording the outcome.
+    pub(crate) fn measure<T, F>(&mut self, ask: F) -> Option<T>
+    where
+        F: FnOnce(&mut Question<R, W>) -> Option<T>,
+    {
+        let started = if CLOCK { Some(Instant::now()) } else { None };
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::info_span!("question", question = %self.question).entered();
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!("question asked");
+        self.tally = Tally::default();
+        self.env_read = false;
+        let turn = self.session.as_ref().map(PromptSession::turn);
+        self.shown = Some(false);
+        let answer = ask(self);
+        if self.shown.take() == Some(true) {
+            if let Some(ref after) = self.after_prompt {
+                after();
+            }
+        }
+        drop(turn);
+        self.outcome = Some(AskOutcome {
+            attempts: self.tally.attempts,
+            elapsed: started.map_or(Duration::ZERO, |started| started.elapsed()),
+            default_used: answer.is_some()
+                && self.default.is_some()
+                && (self.tally.last_empty || self.tally.default_taken),
+        });
+        #[cfg(feature = "tracing")]
+        self.trace_outcome(&answer);
+        answer
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Log how the question went.
+    fn trace_outcome<T>(&self, answer: &Option<T>) {
+        let outcome = self.outcome.unwrap();
+        ::tracing::info!(
+            answered = answer.is_some(),
+            attempts = outcome.attempts,
+            elapsed = ?outcome.elapsed,
+            default_used = outcome.default_used,
+            "question finished"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use {Answer, AssumeYes, Context, Layer, Next, Question};
+
+    /// Answers with the default, if any, without asking.
+    struct TakeDefault;
+
+    impl Layer for TakeDefault {
+        fn ask(&self, context: &Context, _: Next) -> Option<Answer> {
+            context.take_default()
+        }
+    }
+
+    #[test]
+    fn outcome() {
+        macro_rules! outcome {
+            ($i:expr, $build:expr, $attempts:expr, $default_used:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Continue?", input, Vec::new());
+                assert_eq!(None, q.last_outcome());
+                $build(&mut q);
+                let outcome = q.last_outcome().unwrap();
+                assert_eq!($attempts, outcome.attempts());
+                assert_eq!($default_used, outcome.default_used());
+            };
+        }
+        type Q = Question<Cursor<Vec<u8>>, Vec<u8>>;
+        outcome!("maybe\nperhaps\ny\n", |q: &mut Q| q.confirm(), 3, false);
+        outcome!(
+            "x\n\n",
+            |q: &mut Q| q.default(Answer::YES).confirm(),
+            2,
+            true
+        );
+        outcome!("", |q: &mut Q| q.layer(AssumeYes).confirm(), 0, false);
+        outcome!(
+            "\n",
+            |q: &mut Q| q
+                .default(Answer::RESPONSE(String::from("80")))
+                .ask_as::<u16>(),
+            1,
+            true
+        );
+        outcome!("Jo\n", |q: &mut Q| q.ask(), 1, false);
+        outcome!(
+            "80\n",
+            |q: &mut Q| q.default(Answer::RESPONSE(String::from("80"))).ask(),
+            1,
+            false
+        );
+        outcome!(
+            "",
+            |q: &mut Q| q.default(Answer::YES).layer(AssumeYes).confirm(),
+            0,
+            false
+        );
+        outcome!(
+            "",
+            |q: &mut Q| q.default(Answer::YES).layer(TakeDefault).confirm(),
+            0,
+            true
+        );
+        outcome!("", |q: &mut Q| q.layer(TakeDefault).confirm(), 0, false);
+        outcome!(
+            "",
+            |q: &mut Q| q.default(Answer::NO).layer(AssumeYes).confirm(),
+            0,
+            false
+        );
+        outcome!(
+            "",
+            |q: &mut Q| q.default(Answer::NO).dry_run().confirm(),
+            0,
+            true
+        );
+        outcome!("", |q: &mut Q| q.dry_run().ask(), 0, false);
+    }
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing() {
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{subscriber, Event, Metadata, Subscriber};
+
+        /// Records the fields of every event as "name=value" lines.
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl Visit for Recorder {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                let line = format!("{}={:?}", field.name(), value);
+                self.0.lock().unwrap().push(line);
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event) {
+                event.record(&mut Recorder(self.0.clone()));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        subscriber::with_default(Recorder(lines.clone()), || {
+            let input = Cursor::new(String::from("hunter2\ncorrect horse battery\n").into_bytes());
+            Question::with_cursor("Passphrase?", input, Vec::new())
+                .min_len(8)
+                .secret()
+                .ask();
+        });
+        let lines = lines.lock().unwrap().join("\n");
+        assert!(!lines.contains("hunter2"), "{}", lines);
+        assert!(!lines.contains("correct horse"), "{}", lines);
+        assert!(lines.contains("message=response rejected"), "{}", lines);
+        assert!(lines.contains("attempts=2"), "{}", lines);
+    }
+}
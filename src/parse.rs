@@ -0,0 +1,271 @@
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::str::FromStr;
+
+use locale::Texts;
+use {Answer, AskError, Invalid, OnEof, Question, Retry, REDACTED};
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask for a value of any type that can be parsed from a
+    /// string, such as a number or an `IpAddr`.
+    ///
+    /// When the response cannot be parsed the reason is shown and
+    /// the question is asked again, once or as set by `tries` or
+    /// `until_acceptable`. An empty response uses the default set
+    /// with `Answer::RESPONSE`. `None` is returned if no value
+    /// could be parsed. When input ends, the answer is as set by
+    /// `on_eof`, with `OnEof::No` parsing "no".
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// use std::net::IpAddr;
+    ///
+    /// let address = Question::new("Bind address?")
+    ///     .until_acceptable()
+    ///     .ask_as::<IpAddr>();
+    /// ```
+    pub fn ask_as<T>(&mut self) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let texts = self.texts();
+        self.ask_parsed(move |response| {
+            response.parse().map_err(|e: T::Err| {
+                let reason = e.to_string();
+                texts.text("not-parsed", &[("response", response), ("reason", &reason)])
+            })
+        })
+    }
+
+    /// Ask for a socket address as `host:port`, like `ask_as`,
+    /// explaining which part is wrong when it is not valid.
+    /// Host names are resolved, and the first address found is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let server = Question::new("Server?")
+    ///     .until_acceptable()
+    ///     .socket_addr();
+    /// ```
+    pub fn socket_addr(&mut self) -> Option<SocketAddr> {
+        let texts = self.texts();
+        self.ask_parsed(move |response| parse_socket_addr(response, &texts))
+    }
+
+    /// Ask until `parse` accepts the response, showing the reason
+    /// it gives when it does not.
+    pub(crate) fn ask_parsed<T, F>(&mut self, parse: F) -> Option<T>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.measure(|q| q.retry_parsed(parse))
+    }
+
+    fn retry_parsed<T, F>(&mut self, parse: F) -> Option<T>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.resolve_default();
+        if self.dry_run {
+            return self
+                .preview()
+                .and_then(|default| parse(default.as_str()).ok());
+        }
+        self.drawn = 0;
+        let tries = self.attempts();
+        let mut retry = None;
+        for attempt in 0..tries {
+            let prompt = self.build_prompt(retry.as_ref());
+            self.ended = false;
+            let mut response = self.prompt_user(&prompt).ok()?;
+            if self.ended {
+                match self.on_eof {
+                    OnEof::Default => {}
+                    OnEof::No => return parse(Answer::NO.as_str()).ok(),
+                    OnEof::Error | OnEof::Abort => return None,
+                }
+            }
+            if response.is_empty() {
+                if let Some(Answer::RESPONSE(ref default)) = self.default {
+                    response = default.clone();
+                }
+            }
+            let reason = match parse(&response) {
+                Ok(value) => return Some(value),
+                // Input has ended, so there is no asking again.
+                Err(_) if self.ended => return None,
+                Err(reason) => reason,
+            };
+            if self.secret {
+                self.fail(AskError::Rejected(String::from(REDACTED)));
+            } else {
+                self.fail(AskError::Rejected(response));
+            }
+            if self.stalled() {
+                self.report(AskError::NoInteractiveInput);
+                break;
+            }
+            if self.asks_once() || attempt + 1 == tries {
+                break;
+            }
+            self.wait_before_retry(attempt);
+            retry = Some(Retry {
+                invalid: Invalid::Reason(reason),
+                remaining: Some(tries - attempt - 1).filter(|_| !self.until_acceptable),
+            });
+        }
+        None
+    }
+}
+
+fn parse_socket_addr(response: &str, texts: &Texts) -> Result<SocketAddr, String> {
+    let (host, port) = match response.rfind(':') {
+        Some(i) => (&response[..i], &response[i + 1..]),
+        None => return Err(texts.text("not-host-port", &[("response", response)])),
+    };
+    if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+        return Err(texts.text("not-port", &[("port", port)]));
+    }
+    let port: u16 = match port.parse() {
+        Ok(port) => port,
+        Err(_) => return Err(texts.text("port-out-of-range", &[("port", port)])),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return Err(texts.text("host-missing", &[]));
+    }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, port));
+    }
+    match (host, port).to_socket_addrs().map(|mut a| a.next()) {
+        Ok(Some(address)) => Ok(address),
+        _ => Err(texts.text("not-resolved", &[("host", host)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn ask_as() {
+        macro_rules! ask_as {
+            ($t:ty, $i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Value?", input, Vec::new())
+                    .tries(2)
+                    .ask_as::<$t>();
+                assert_eq!($expected, actual);
+            };
+        }
+        ask_as!(u32, "42\n", Some(42));
+        ask_as!(u32, "-1\n7\n", Some(7));
+        ask_as!(u32, "x\ny\n", None);
+        ask_as!(IpAddr, "10.0.0.1\n", Some(IpAddr::from([10, 0, 0, 1])));
+        ask_as!(IpAddr, "::1\n", Some("::1".parse::<IpAddr>().unwrap()));
+        ask_as!(IpAddr, "", None);
+
+        let input = Cursor::new(String::from("\n").into_bytes());
+        let actual = Question::with_cursor("Workers?", input, Vec::new())
+            .default(Answer::RESPONSE(String::from("4")))
+            .ask_as::<u8>();
+        assert_eq!(Some(4), actual);
+    }
+
+    #[test]
+    fn ask_as_on_eof() {
+        macro_rules! on_eof {
+            ($t:ty, $on_eof:expr, $expected:expr, $error:expr, $aborted:expr) => {
+                let input = Cursor::new(Vec::new());
+                let mut q = Question::with_cursor("Port?", input, Vec::new());
+                q.on_eof($on_eof)
+                    .default(Answer::RESPONSE(String::from("80")))
+                    .until_acceptable();
+                assert_eq!($expected, q.ask_as::<$t>());
+                assert_eq!($error, q.error);
+                assert_eq!($aborted, q.aborted());
+            };
+        }
+        on_eof!(u16, OnEof::Default, Some(80), None, false);
+        on_eof!(u16, OnEof::No, None, None, false);
+        on_eof!(String, OnEof::No, Some(String::from("no")), None, false);
+        on_eof!(u16, OnEof::Error, None, Some(AskError::EndOfInput), false);
+        on_eof!(u16, OnEof::Abort, None, Some(AskError::EndOfInput), true);
+    }
+
+    #[test]
+    fn ask_as_dry_run() {
+        let input = Cursor::new(b"7\n".to_vec());
+        let mut q = Question::with_cursor("Workers?", input, Vec::new());
+        q.default(Answer::RESPONSE(String::from("4"))).dry_run();
+        assert_eq!(Some(4), q.ask_as::<u32>());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Workers? 4\n", output);
+
+        let input = Cursor::new(b"7\n".to_vec());
+        let mut q = Question::with_cursor("Workers?", input, Vec::new());
+        q.dry_run();
+        assert_eq!(None, q.ask_as::<u32>());
+        assert_eq!(Some(AskError::NoDefault), q.error);
+    }
+
+    #[test]
+    fn secret_rejected() {
+        let input = Cursor::new(b"hunter2\n".to_vec());
+        let mut q = Question::with_cursor("PIN?", input, Vec::new());
+        q.secret().strict();
+        assert_eq!(None, q.ask_as::<u32>());
+        let error = AskError::Rejected(String::from(REDACTED));
+        assert_eq!(Some(error), q.error);
+    }
+
+    #[test]
+    fn socket_addr() {
+        assert_eq!(
+            Ok(SocketAddr::from(([127, 0, 0, 1], 8080))),
+            parse_socket_addr("127.0.0.1:8080", &Texts::default())
+        );
+        assert_eq!(
+            Ok("[::1]:80".parse().unwrap()),
+            parse_socket_addr("[::1]:80", &Texts::default())
+        );
+        assert_eq!(
+            Err(String::from("Port 99999 is out of range (0-65535)")),
+            parse_socket_addr("127.0.0.1:99999", &Texts::default())
+        );
+        assert_eq!(
+            Err(String::from("'http' is not a port number")),
+            parse_socket_addr("example.com:http", &Texts::default())
+        );
+        assert_eq!(
+            Err(String::from("'db' is not in the form host:port")),
+            parse_socket_addr("db", &Texts::default())
+        );
+
+        let input = Cursor::new(String::from("10.0.0.1:70000\n10.0.0.1:7000\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Server?", input, &mut output)
+                .until_acceptable()
+                .socket_addr();
+            assert_eq!(Some(SocketAddr::from(([10, 0, 0, 1], 7000))), actual);
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Server? Port 70000 is out of range (0-65535)\nServer? ",
+            output
+        );
+    }
+}
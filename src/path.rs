@@ -0,0 +1,244 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use Question;
+
+/// A `Question` answered with a path on the filesystem, checked
+/// against constraints such as `must_exist` before it is
+/// returned.
+///
+/// Created with `Question::path`. A leading `~` is expanded to
+/// the user's home directory. When a path does not meet the
+/// constraints the reason is shown and the question is asked
+/// again, once or as set by `tries` or `until_acceptable`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::Question;
+/// let config = Question::new("Config file?")
+///     .tries(3)
+///     .path()
+///     .must_be_file()
+///     .ask();
+/// ```
+pub struct PathQuestion<'a, R, W>
+where
+    R: Read + 'a,
+    W: Write + 'a,
+{
+    question: &'a mut Question<R, W>,
+    constraints: Constraints,
+}
+
+/// What a path must be like to be accepted.
+#[derive(Clone, Copy, Default)]
+struct Constraints {
+    must_exist: bool,
+    must_be_dir: bool,
+    must_be_file: bool,
+    create_if_missing: bool,
+}
+
+impl<'a, R, W> PathQuestion<'a, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    pub(crate) fn new(question: &'a mut Question<R, W>) -> PathQuestion<'a, R, W> {
+        PathQuestion {
+            question,
+            constraints: Constraints::default(),
+        }
+    }
+
+    /// Only accept paths that exist.
+    pub fn must_exist(&mut self) -> &mut PathQuestion<'a, R, W> {
+        self.constraints.must_exist = true;
+        self
+    }
+
+    /// Only accept paths to directories.
+    pub fn must_be_dir(&mut self) -> &mut PathQuestion<'a, R, W> {
+        self.constraints.must_be_dir = true;
+        self
+    }
+
+    /// Only accept paths to files.
+    pub fn must_be_file(&mut self) -> &mut PathQuestion<'a, R, W> {
+        self.constraints.must_be_file = true;
+        self
+    }
+
+    /// Create the directory, and any missing parents, when the
+    /// path given does not exist yet. With `must_be_file`, an empty
+    /// file is created in its place instead. The path is rejected
+    /// if it cannot be created.
+    pub fn create_if_missing(&mut self) -> &mut PathQuestion<'a, R, W> {
+        self.constraints.create_if_missing = true;
+        self
+    }
+
+    /// Ask the question, returning the path given once it meets
+    /// the constraints.
+    ///
+    /// An empty response uses the default set on the question
+    /// with `Answer::RESPONSE`. `None` is returned if no
+    /// acceptable path was given.
+    pub fn ask(&mut self) -> Option<PathBuf> {
+        let constraints = self.constraints;
+        let required = self.question.text("path-required", &[]);
+        self.question.ask_parsed(|response| {
+            if response.is_empty() {
+                return Err(required.clone());
+            }
+            let path = expand_home(response);
+            constraints.check(&path).map(|()| path)
+        })
+    }
+}
+
+impl Constraints {
+    /// Check `path` against the constraints, creating it first if
+    /// asked to, and explain why it is not acceptable.
+    fn check(&self, path: &Path) -> Result<(), String> {
+        let shown = path.display();
+        if !path.exists() && self.create_if_missing {
+            if let Err(e) = self.create(path) {
+                return Err(format!("'{}' could not be created: {}", shown, e));
+            }
+        }
+        if (self.must_exist || self.must_be_dir || self.must_be_file) && !path.exists() {
+            return Err(format!("'{}' does not exist", shown));
+        }
+        if self.must_be_dir && !path.is_dir() {
+            return Err(format!("'{}' is not a directory", shown));
+        }
+        if self.must_be_file && !path.is_file() {
+            return Err(format!("'{}' is not a file", shown));
+        }
+        Ok(())
+    }
+
+    /// Create the missing `path`: an empty file if it must be one,
+    /// or else a directory, along with any missing parents.
+    fn create(&self, path: &Path) -> io::Result<()> {
+        if !self.must_be_file {
+            return fs::create_dir_all(path);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map(drop)
+    }
+}
+
+/// Expand a leading `~` in `path` to the user's home directory,
+/// when it is known.
+fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return PathBuf::from(path),
+    };
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match home {
+        Some(home) => PathBuf::from(home).join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn expand() {
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        assert_eq!(home, expand_home("~"));
+        assert_eq!(home.join("src/app"), expand_home("~/src/app"));
+        assert_eq!(PathBuf::from("~bob/x"), expand_home("~bob/x"));
+        assert_eq!(PathBuf::from("/tmp/~"), expand_home("/tmp/~"));
+    }
+
+    #[test]
+    fn path() {
+        let root = std::env::temp_dir().join(format!("question-path-{}", std::process::id()));
+        let file = root.join("file");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(&file, "").unwrap();
+        let (root_s, file_s) = (root.display().to_string(), file.display().to_string());
+
+        macro_rules! path {
+            ($i:expr, $build:expr, $expected:expr) => {
+                let input = Cursor::new($i.into_bytes());
+                let mut q = Question::with_cursor("Path?", input, Vec::new());
+                q.until_acceptable();
+                let mut p = q.path();
+                $build(&mut p);
+                assert_eq!($expected, p.ask());
+            };
+        }
+        type P<'a> = PathQuestion<'a, Cursor<Vec<u8>>, Vec<u8>>;
+        let missing = root.join("missing");
+        path!(format!("{}\n", file_s), |_: &mut P| {}, Some(file.clone()));
+        path!(
+            format!("{}\n{}\n", missing.display(), file_s),
+            |p: &mut P| {
+                p.must_exist();
+            },
+            Some(file.clone())
+        );
+        path!(
+            format!("{}\n\n{}\n", file_s, root_s),
+            |p: &mut P| {
+                p.must_be_dir();
+            },
+            Some(root.clone())
+        );
+        path!(
+            format!("{}\n{}\n", root_s, file_s),
+            |p: &mut P| {
+                p.must_be_file();
+            },
+            Some(file.clone())
+        );
+        let created = root.join("a/b");
+        path!(
+            format!("{}\n", created.display()),
+            |p: &mut P| {
+                p.must_be_dir().create_if_missing();
+            },
+            Some(created.clone())
+        );
+        assert!(created.is_dir());
+        let created = root.join("c/file");
+        path!(
+            format!("{}\n", created.display()),
+            |p: &mut P| {
+                p.must_be_file().create_if_missing();
+            },
+            Some(created.clone())
+        );
+        assert!(created.is_file());
+
+        let input = Cursor::new(format!("{}\n", missing.display()).into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let actual = Question::with_cursor("Path?", input, &mut output)
+                .tries(2)
+                .path()
+                .must_exist()
+                .ask();
+            assert_eq!(None, actual);
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert!(output.contains(&format!("'{}' does not exist\n", missing.display())));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
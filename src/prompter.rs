@@ -0,0 +1,408 @@
+//! Backends that show questions and read the responses, so the
+//! same `Question` can be asked somewhere other than a terminal.
+
+use std::io::{self, BufRead, Read, Write};
+
+use form::answer_json;
+use json::Json;
+use {read_line, Answer, Trim};
+
+/// Shows questions to the user and reads their responses.
+///
+/// A `Question` reads from and writes to its reader and writer
+/// unless a prompter is set with `Question::prompter`, in which
+/// case everything goes through it instead: a TUI, a bridge to a
+/// GUI or a web socket, or a test double. Interactive widgets
+/// that read single keystrokes are not used with a prompter, and
+/// fall back to reading lines.
+///
+/// # Examples
+///
+/// A prompter answering every question with its default.
+///
+/// ```no_run
+/// # use question::{Answer, Prompt, Prompter, Question};
+/// # use std::io;
+/// struct Defaults;
+///
+/// impl Prompter for Defaults {
+///     fn prompt(&mut self, _prompt: &Prompt) -> io::Result<Option<String>> {
+///         Ok(Some(String::new()))
+///     }
+/// }
+///
+/// Question::new("Continue?")
+///     .default(Answer::YES)
+///     .prompter(Defaults)
+///     .confirm();
+/// ```
+pub trait Prompter {
+    /// Show `prompt` and return the line given in response,
+    /// or `None` if there will be no more responses.
+    fn prompt(&mut self, prompt: &Prompt) -> io::Result<Option<String>>;
+
+    /// Show text that needs no response, such as a list of
+    /// choices or help. It is ignored by default.
+    fn show(&mut self, text: &str) -> io::Result<()> {
+        let _ = text;
+        Ok(())
+    }
+}
+
+/// What a `Prompter` is asked to show.
+pub struct Prompt<'a> {
+    question: &'a str,
+    text: &'a str,
+    default: Option<&'a Answer>,
+    choices: Vec<&'a str>,
+    yes_no: bool,
+}
+
+impl<'a> Prompt<'a> {
+    pub(crate) fn new(
+        question: &'a str,
+        text: &'a str,
+        default: Option<&'a Answer>,
+        choices: Vec<&'a str>,
+        yes_no: bool,
+    ) -> Prompt<'a> {
+        Prompt {
+            question,
+            text,
+            default,
+            choices,
+            yes_no,
+        }
+    }
+
+    /// The question as it was written.
+    pub fn question(&self) -> &str {
+        self.question
+    }
+
+    /// The prompt as it would be written to a terminal, including
+    /// any hints and the reason an earlier response was rejected.
+    pub fn text(&self) -> &str {
+        self.text
+    }
+
+    /// The default answer, if one has been set.
+    pub fn default(&self) -> Option<&Answer> {
+        self.default
+    }
+
+    /// The responses that are accepted, if they are limited.
+    pub fn choices(&self) -> &[&str] {
+        &self.choices
+    }
+
+    /// Whether this is a yes/no question.
+    pub fn is_yes_no(&self) -> bool {
+        self.yes_no
+    }
+}
+
+/// Write `prompt` to `writer` and read one line from `reader` in
+/// response, as a `Question` does without any of its options:
+/// the line is read a byte at a time, so nothing after it is
+/// consumed, and whitespace, including the line ending, is
+/// trimmed from both ends. Returns `None` if the input ended
+/// before anything was read, and fails with
+/// `io::ErrorKind::InvalidData` if the line is not valid UTF-8.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// # fn main() -> io::Result<()> {
+/// let name = question::read_answer(&mut io::stdin(), &mut io::stdout(), "Name? ")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_answer<R, W>(reader: &mut R, writer: &mut W, prompt: &str) -> io::Result<Option<String>>
+where
+    R: Read,
+    W: Write,
+{
+    writer.write_all(prompt.as_bytes())?;
+    writer.flush()?;
+    Ok(read_line(reader)?.map(|line| Trim::Both.apply(line)))
+}
+
+/// A `Prompter` writing to standard output and reading lines from
+/// standard input, as a `Question` does without one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stdio;
+
+impl Prompter for Stdio {
+    fn prompt(&mut self, prompt: &Prompt) -> io::Result<Option<String>> {
+        self.show(prompt.text())?;
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line)? {
+            0 => Ok(None),
+            _ => Ok(Some(line)),
+        }
+    }
+
+    fn show(&mut self, text: &str) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+/// A `Prompter` for programs driving a CLI rather than people,
+/// such as editor plugins and GUI wrappers, writing each question
+/// as a line of JSON and reading each answer as one.
+///
+/// Questions are written as objects with `"type": "question"`
+/// along with the `question`, the full `prompt` text, the
+/// `default` if any, the accepted `choices` and whether it is a
+/// `yes_no` question. Other text is written with
+/// `"type": "message"`. An answer is a JSON value on its own
+/// line, or an object with it under `"answer"`: a string is the
+/// response, `true` and `false` answer yes and no, a number is
+/// its digits and `null` takes the default.
+///
+/// Questions created with `Question::new` use this on standard
+/// input and output when the `QUESTION_PROTOCOL` environment
+/// variable is `json`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{JsonLines, Question};
+/// Question::new("Continue?")
+///     .prompter(JsonLines::stdio())
+///     .confirm();
+/// ```
+pub struct JsonLines<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    reader: R,
+    writer: W,
+}
+
+impl JsonLines<io::Stdin, io::Stdout> {
+    /// Read answers from standard input and write questions to
+    /// standard output.
+    pub fn stdio() -> JsonLines<io::Stdin, io::Stdout> {
+        JsonLines::new(io::stdin(), io::stdout())
+    }
+}
+
+impl<R, W> JsonLines<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Read answers from `reader` and write questions to `writer`.
+    /// Each answer is read one byte at a time, so nothing past it
+    /// is taken from `reader`, and several prompters can share it.
+    pub fn new(reader: R, writer: W) -> JsonLines<R, W> {
+        JsonLines { reader, writer }
+    }
+
+    fn write(&mut self, message: &Json) -> io::Result<()> {
+        writeln!(self.writer, "{}", message.to_line())?;
+        self.writer.flush()
+    }
+}
+
+impl<R, W> Prompter for JsonLines<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    fn prompt(&mut self, prompt: &Prompt) -> io::Result<Option<String>> {
+        let default = match prompt.default() {
+            Some(answer) => answer_json(answer, prompt.is_yes_no()),
+            None => Json::Null,
+        };
+        let choices = prompt
+            .choices()
+            .iter()
+            .map(|choice| Json::String(choice.to_string()))
+            .collect();
+        self.write(&Json::Object(vec![
+            (String::from("type"), Json::String(String::from("question"))),
+            (
+                String::from("question"),
+                Json::String(prompt.question().to_string()),
+            ),
+            (
+                String::from("prompt"),
+                Json::String(prompt.text().to_string()),
+            ),
+            (String::from("default"), default),
+            (String::from("choices"), Json::Array(choices)),
+            (String::from("yes_no"), Json::Bool(prompt.is_yes_no())),
+        ]))?;
+        let line = match read_line(&mut self.reader)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let value =
+            Json::parse(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let answer = value.get("answer").unwrap_or(&value);
+        match *answer {
+            Json::Null => Ok(Some(String::new())),
+            Json::Bool(true) => Ok(Some(String::from("yes"))),
+            Json::Bool(false) => Ok(Some(String::from("no"))),
+            Json::Number(n) => Ok(Some(n.to_string())),
+            Json::String(ref response) => Ok(Some(response.clone())),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "an answer must be a string, boolean, number or null",
+            )),
+        }
+    }
+
+    fn show(&mut self, text: &str) -> io::Result<()> {
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        self.write(&Json::Object(vec![
+            (String::from("type"), Json::String(String::from("message"))),
+            (String::from("text"), Json::String(text.to_string())),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use Question;
+
+    #[test]
+    fn read_answer() {
+        let mut input = Cursor::new(b"  Ada \r\nrest\n\xff\n".to_vec());
+        let mut output = Vec::new();
+        let answer = super::read_answer(&mut input, &mut output, "Name? ").unwrap();
+        assert_eq!(Some(String::from("Ada")), answer);
+        assert_eq!(b"Name? ", &output[..]);
+        let answer = super::read_answer(&mut input, &mut output, "").unwrap();
+        assert_eq!(Some(String::from("rest")), answer);
+        let error = super::read_answer(&mut input, &mut output, "").unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        assert_eq!(
+            None,
+            super::read_answer(&mut input, &mut output, "").unwrap()
+        );
+    }
+
+    /// Replays responses and records what it was shown.
+    struct Script {
+        responses: Vec<&'static str>,
+        shown: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Prompter for Script {
+        fn prompt(&mut self, prompt: &Prompt) -> io::Result<Option<String>> {
+            let choices = prompt.choices().join("|");
+            self.shown.lock().unwrap().push(format!(
+                "{} [{}] {}",
+                prompt.question(),
+                choices,
+                prompt.text()
+            ));
+            if self.responses.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(self.responses.remove(0).to_string()))
+        }
+
+        fn show(&mut self, text: &str) -> io::Result<()> {
+            self.shown.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn prompter() {
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let script = Script {
+            responses: vec!["maybe", "y"],
+            shown: shown.clone(),
+        };
+        let mut output = Vec::new();
+        let answer = Question::with_cursor("Continue?", &b"n\n"[..], &mut output)
+            .clarification("yes or no")
+            .prompter(script)
+            .confirm();
+        assert_eq!(Answer::YES, answer);
+        assert!(output.is_empty());
+        assert_eq!(
+            vec![
+                "Continue? [n|no|y|yes] Continue? ",
+                "Continue? [n|no|y|yes] yes or no\nContinue? ",
+            ],
+            *shown.lock().unwrap()
+        );
+
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let script = Script {
+            responses: vec!["2"],
+            shown: shown.clone(),
+        };
+        let choice = Question::with_cursor("Env?", &b""[..], Vec::new())
+            .prompter(script)
+            .select(&["dev", "prod"])
+            .ask();
+        assert_eq!(Some(String::from("prod")), choice);
+        assert_eq!("  1) dev\n  2) prod\n", shown.lock().unwrap()[0]);
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut output = Vec::new();
+        let answers = "true\n{\"answer\": \"blue\"}\nnull\n[1]\n";
+        let mut lines = JsonLines::new(answers.as_bytes(), &mut output);
+        let yes = Answer::YES;
+        let prompt = Prompt::new("Continue?", "Continue? ", Some(&yes), vec!["y", "n"], true);
+        assert_eq!(Some(String::from("yes")), lines.prompt(&prompt).unwrap());
+        let prompt = Prompt::new("Color?", "Color? ", None, Vec::new(), false);
+        assert_eq!(Some(String::from("blue")), lines.prompt(&prompt).unwrap());
+        assert_eq!(Some(String::new()), lines.prompt(&prompt).unwrap());
+        let error = lines.prompt(&prompt).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        assert_eq!(None, lines.prompt(&prompt).unwrap());
+        lines.show("\n").unwrap();
+        lines.show("Saved\n").unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let output: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            "{\"type\":\"question\",\"question\":\"Continue?\",\"prompt\":\"Continue? \",\
+             \"default\":true,\"choices\":[\"y\",\"n\"],\"yes_no\":true}",
+            output[0]
+        );
+        assert_eq!(
+            "{\"type\":\"question\",\"question\":\"Color?\",\"prompt\":\"Color? \",\
+             \"default\":null,\"choices\":[],\"yes_no\":false}",
+            output[1]
+        );
+        assert_eq!(6, output.len());
+        assert_eq!("{\"type\":\"message\",\"text\":\"Saved\\n\"}", output[5]);
+
+        let answer = Question::with_cursor("Name?", &b""[..], Vec::new())
+            .default(Answer::RESPONSE(String::from("anon")))
+            .prompter(JsonLines::new(&b"null\n"[..], Vec::new()))
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("anon"))), answer);
+
+        let mut input = &b"\"first\"\n\"second\"\n"[..];
+        let prompt = Prompt::new("Name?", "Name? ", None, Vec::new(), false);
+        let first = JsonLines::new(&mut input, Vec::new())
+            .prompt(&prompt)
+            .unwrap();
+        let second = JsonLines::new(&mut input, Vec::new())
+            .prompt(&prompt)
+            .unwrap();
+        assert_eq!(Some(String::from("first")), first);
+        assert_eq!(Some(String::from("second")), second);
+    }
+}
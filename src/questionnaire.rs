@@ -0,0 +1,404 @@
+//! Questions declared in TOML or YAML files rather than in code,
+//! with the `toml` or `yaml` feature.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, Write};
+
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use {Answer, Answers, Question};
+
+/// A sequence of questions loaded from a definition, so their
+/// wording can be changed without recompiling.
+///
+/// Each entry of `questions` has an `id` and `text`, and may have:
+///
+/// - `type`: `text` (the default), `yes_no`, `number` or `choice`.
+/// - `choices`: the acceptable responses, making it a `choice`.
+/// - `default`: the response taken when none is given.
+/// - `pattern`: a regular expression responses must match.
+/// - `help`: shown when the user enters "?".
+/// - `when`: only ask when an earlier answer is as given, written
+///   `id` (answered yes), `id == value` or `id != value`.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "toml", doc = "```no_run")]
+#[cfg_attr(not(feature = "toml"), doc = "```ignore")]
+/// # use question::Questionnaire;
+/// let questionnaire = Questionnaire::from_toml(
+///     r#"
+///     [[questions]]
+///     id = "name"
+///     text = "Project name?"
+///     pattern = "^[a-z][a-z0-9-]*$"
+///
+///     [[questions]]
+///     id = "ci"
+///     text = "Set up CI?"
+///     type = "yes_no"
+///     default = "yes"
+///
+///     [[questions]]
+///     id = "provider"
+///     text = "CI provider?"
+///     choices = ["github", "gitlab"]
+///     when = "ci"
+///     "#,
+/// )
+/// .unwrap();
+/// let answers = questionnaire.ask();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Questionnaire {
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Definition {
+    questions: Vec<Entry>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Kind {
+    Text,
+    YesNo,
+    Number,
+    Choice,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    id: String,
+    text: String,
+    #[serde(rename = "type")]
+    kind: Option<Kind>,
+    #[serde(default)]
+    choices: Vec<String>,
+    default: Option<String>,
+    pattern: Option<String>,
+    help: Option<String>,
+    when: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct Item {
+    id: String,
+    text: String,
+    kind: Kind,
+    choices: Vec<String>,
+    default: Option<Answer>,
+    pattern: Option<Regex>,
+    help: Option<String>,
+    when: Option<Condition>,
+}
+
+/// When to ask a question, based on an earlier answer.
+#[derive(Clone, Debug)]
+enum Condition {
+    Yes(String),
+    Equals(String, String),
+    NotEquals(String, String),
+}
+
+impl Condition {
+    fn parse(when: &str) -> Condition {
+        let split = |op: &str| {
+            when.find(op).map(|i| {
+                let id = when[..i].trim().to_string();
+                let value = when[i + op.len()..].trim().trim_matches('"').to_string();
+                (id, value)
+            })
+        };
+        if let Some((id, value)) = split("!=") {
+            Condition::NotEquals(id, value)
+        } else if let Some((id, value)) = split("==") {
+            Condition::Equals(id, value)
+        } else {
+            Condition::Yes(when.trim().to_string())
+        }
+    }
+
+    fn holds(&self, answers: &Answers) -> bool {
+        let answer = |id: &str| answers.get(id).map(Answer::as_str);
+        match *self {
+            Condition::Yes(ref id) => answers.get(id) == Some(&Answer::YES),
+            Condition::Equals(ref id, ref value) => answer(id) == Some(value.as_str()),
+            Condition::NotEquals(ref id, ref value) => answer(id) != Some(value.as_str()),
+        }
+    }
+}
+
+/// Why a questionnaire could not be loaded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefinitionError(String);
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid questionnaire: {}", self.0)
+    }
+}
+
+impl Error for DefinitionError {}
+
+impl Questionnaire {
+    /// Load a questionnaire from TOML, with a `[[questions]]`
+    /// table for each question.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(definition: &str) -> Result<Questionnaire, DefinitionError> {
+        let definition: Definition =
+            ::toml::from_str(definition).map_err(|e| DefinitionError(e.to_string()))?;
+        Questionnaire::new(definition)
+    }
+
+    /// Load a questionnaire from YAML, with a `questions` list.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(definition: &str) -> Result<Questionnaire, DefinitionError> {
+        let definition: Definition =
+            ::serde_yaml::from_str(definition).map_err(|e| DefinitionError(e.to_string()))?;
+        Questionnaire::new(definition)
+    }
+
+    fn new(definition: Definition) -> Result<Questionnaire, DefinitionError> {
+        let mut items = Vec::new();
+        for entry in definition.questions {
+            if items.iter().any(|item: &Item| item.id == entry.id) {
+                return Err(DefinitionError(format!("'{}' is defined twice", entry.id)));
+            }
+            let kind = match entry.kind {
+                Some(kind) => kind,
+                None if !entry.choices.is_empty() => Kind::Choice,
+                None => Kind::Text,
+            };
+            if kind == Kind::Choice && entry.choices.is_empty() {
+                return Err(DefinitionError(format!("'{}' has no choices", entry.id)));
+            }
+            let pattern = match entry.pattern {
+                Some(ref pattern) => Some(Regex::new(pattern).map_err(|e| {
+                    DefinitionError(format!("the pattern of '{}' is invalid: {}", entry.id, e))
+                })?),
+                None => None,
+            };
+            let default = entry.default.map(|default| match kind {
+                Kind::YesNo if default.eq_ignore_ascii_case("yes") => Answer::YES,
+                Kind::YesNo if default.eq_ignore_ascii_case("no") => Answer::NO,
+                _ => Answer::RESPONSE(default),
+            });
+            items.push(Item {
+                id: entry.id,
+                text: entry.text,
+                kind,
+                choices: entry.choices,
+                default,
+                pattern,
+                help: entry.help,
+                when: entry.when.as_ref().map(|when| Condition::parse(when)),
+            });
+        }
+        Ok(Questionnaire { items })
+    }
+
+    /// Ask each question whose condition holds, in order,
+    /// collecting the answers by id.
+    pub fn ask(&self) -> Answers {
+        self.ask_with(|text| Question::new(text))
+    }
+
+    /// Ask the questionnaire like `ask`, creating each question
+    /// with `new` from its text, so it can be configured further or
+    /// asked on other input.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Answers, Question, Questionnaire};
+    /// fn ask(questionnaire: &Questionnaire) -> Answers {
+    ///     questionnaire.ask_with(|text| {
+    ///         let mut question = Question::new(text);
+    ///         question.show_defaults();
+    ///         question
+    ///     })
+    /// }
+    /// ```
+    pub fn ask_with<R, W, F>(&self, mut new: F) -> Answers
+    where
+        R: Read,
+        W: Write,
+        F: FnMut(&str) -> Question<R, W>,
+    {
+        let mut answers = Answers::new();
+        for item in &self.items {
+            if let Some(ref when) = item.when {
+                if !when.holds(&answers) {
+                    continue;
+                }
+            }
+            let mut question = new(&item.text);
+            if let Some(answer) = item.ask(&mut question) {
+                answers.insert(&item.id, answer);
+            }
+        }
+        answers
+    }
+}
+
+impl Item {
+    fn ask<R: Read, W: Write>(&self, question: &mut Question<R, W>) -> Option<Answer> {
+        if let Some(ref default) = self.default {
+            question.default(default.clone());
+        }
+        if let Some(ref help) = self.help {
+            question.help(help);
+        }
+        match self.kind {
+            Kind::YesNo => return Some(question.confirm()),
+            Kind::Choice => {
+                let choices: Vec<&str> = self.choices.iter().map(String::as_str).collect();
+                question.acceptable(choices).until_acceptable();
+            }
+            _ => {}
+        }
+        let kind = self.kind;
+        let pattern = self.pattern.clone();
+        if kind == Kind::Choice && pattern.is_none() {
+            return question.ask();
+        }
+        question.until_acceptable();
+        let response = question.ask_parsed(|response| {
+            if kind == Kind::Number && response.parse::<f64>().is_err() {
+                return Err(format!("'{}' is not a number", response));
+            }
+            match pattern {
+                Some(ref pattern) if !pattern.is_match(response) => {
+                    Err(format!("'{}' does not match {}", response, pattern))
+                }
+                _ => Ok(response.to_string()),
+            }
+        });
+        response.map(Answer::RESPONSE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::Script;
+
+    #[cfg(feature = "toml")]
+    const DEFINITION: &str = r#"
+        [[questions]]
+        id = "name"
+        text = "Project name?"
+        pattern = "^[a-z][a-z0-9-]*$"
+
+        [[questions]]
+        id = "port"
+        text = "Port?"
+        type = "number"
+        default = "8080"
+
+        [[questions]]
+        id = "ci"
+        text = "Set up CI?"
+        type = "yes_no"
+
+        [[questions]]
+        id = "provider"
+        text = "CI provider?"
+        choices = ["github", "gitlab"]
+        when = "ci"
+
+        [[questions]]
+        id = "mirror"
+        text = "Mirror to GitHub?"
+        type = "yes_no"
+        when = "provider != github"
+    "#;
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn questionnaire() {
+        let questionnaire = Questionnaire::from_toml(DEFINITION).unwrap();
+        macro_rules! questionnaire {
+            ($responses:expr, $expected:expr) => {
+                let script = Script::new(&$responses);
+                let answers = questionnaire.ask_with(|text| {
+                    let mut question = Question::new(text);
+                    question.prompter(script.clone());
+                    question
+                });
+                let actual: Vec<(&str, &str)> = answers
+                    .iter()
+                    .map(|(id, answer)| (id.as_str(), answer.as_str()))
+                    .collect();
+                assert_eq!($expected, actual);
+                script.assert_finished();
+            };
+        }
+        questionnaire!(
+            ["My App", "my-app", "", "y", "gh", "gitlab", "n"],
+            vec![
+                ("name", "my-app"),
+                ("port", "8080"),
+                ("ci", "yes"),
+                ("provider", "gitlab"),
+                ("mirror", "no"),
+            ]
+        );
+        questionnaire!(
+            ["app", "http", "80", "n", "y"],
+            vec![
+                ("name", "app"),
+                ("port", "80"),
+                ("ci", "no"),
+                ("mirror", "yes")
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn invalid() {
+        macro_rules! invalid {
+            ($definition:expr, $expected:expr) => {
+                let error = Questionnaire::from_toml($definition).unwrap_err();
+                assert_eq!($expected, error.to_string());
+            };
+        }
+        invalid!(
+            "[[questions]]\nid = \"a\"\ntext = \"A?\"\ntype = \"choice\"",
+            "invalid questionnaire: 'a' has no choices"
+        );
+        invalid!(
+            "[[questions]]\nid = \"a\"\ntext = \"A?\"\npattern = \"(\"",
+            "invalid questionnaire: the pattern of 'a' is invalid: \
+             found open group without closing ')'"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml() {
+        let questionnaire = Questionnaire::from_yaml(
+            "questions:\n\
+             - id: env\n  text: Environment?\n  choices: [dev, prod]\n  default: dev\n",
+        )
+        .unwrap();
+        let script = Script::new(&["staging", ""]);
+        let answers = questionnaire.ask_with(|text| {
+            let mut question = Question::new(text);
+            question.prompter(script.clone());
+            question
+        });
+        assert_eq!(
+            Some(&Answer::RESPONSE(String::from("dev"))),
+            answers.get("env")
+        );
+        assert!(Questionnaire::from_yaml("questions: 3").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::io::{Read, Write};
+
+use term::{self, Key, RawMode};
+use Question;
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask the user to put `items` in order, such as by priority,
+    /// returning them reordered.
+    ///
+    /// On an interactive terminal (see `toggle`) the items are
+    /// listed and the highlighted one is moved with the arrow keys
+    /// while space holds it, and enter confirms the order.
+    /// Otherwise the numbered items are listed and the new order is
+    /// entered as their numbers, like "3,1,2". Items left out keep
+    /// their order after those given, so an empty response keeps
+    /// the order as it is.
+    ///
+    /// `None` is returned if input ends first, or if no valid order
+    /// is given within `tries`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let priorities = Question::new("Order by priority:")
+    ///     .until_acceptable()
+    ///     .rank(&["speed", "cost", "reliability"]);
+    /// ```
+    pub fn rank(&mut self, items: &[&str]) -> Option<Vec<String>> {
+        let order = if self.keys {
+            self.rank_interactive(items).ok()?
+        } else {
+            self.rank_lines(items).ok()?
+        };
+        order.map(|order| order.into_iter().map(|i| items[i].to_string()).collect())
+    }
+
+    fn rank_interactive(&mut self, items: &[&str]) -> Result<Option<Vec<usize>>, std::io::Error> {
+        let raw = RawMode::enable()?;
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        let (mut selected, mut held) = (0, false);
+        let mut drawn = 0;
+        loop {
+            let lines = render_rank(&self.question_text(), items, &order, selected, held);
+            let mut frame = String::new();
+            if drawn > 1 {
+                frame += &format!("\x1b[{}A", drawn - 1);
+            }
+            frame += "\r";
+            frame += &lines
+                .iter()
+                .map(|line| format!("\x1b[2K{}", line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            drawn = lines.len();
+            self.show(&frame)?;
+
+            let key = match term::read_key(&mut self.reader)? {
+                Some(key) => key,
+                None => {
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+            };
+            match key {
+                Key::Up if selected > 0 => {
+                    if held {
+                        order.swap(selected, selected - 1);
+                    }
+                    selected -= 1;
+                }
+                Key::Down if selected + 1 < order.len() => {
+                    if held {
+                        order.swap(selected, selected + 1);
+                    }
+                    selected += 1;
+                }
+                Key::Char(' ') => held = !held,
+                Key::Enter => {
+                    self.show_text("\n")?;
+                    return Ok(Some(order));
+                }
+                Key::Interrupt => {
+                    self.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
+                Key::Eof => {
+                    self.show_text("\n")?;
+                    return Ok(None);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn rank_lines(&mut self, items: &[&str]) -> Result<Option<Vec<usize>>, std::io::Error> {
+        let mut list = String::new();
+        for (i, item) in items.iter().enumerate() {
+            list += &format!("  {}. {}\n", i + 1, item);
+        }
+        self.show_text(&list)?;
+        Ok(self.ask_parsed(|response| parse_order(response, items.len())))
+    }
+}
+
+/// Parse an order given as 1-based item numbers, filling in the
+/// items left out in their original order.
+fn parse_order(response: &str, count: usize) -> Result<Vec<usize>, String> {
+    let mut order = Vec::with_capacity(count);
+    for number in response
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+    {
+        let index = match number.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= count => n - 1,
+            _ => {
+                return Err(format!(
+                    "'{}' is not an item number from 1 to {}",
+                    number, count
+                ))
+            }
+        };
+        if order.contains(&index) {
+            return Err(format!("Item {} is listed more than once", number));
+        }
+        order.push(index);
+    }
+    for index in 0..count {
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+    Ok(order)
+}
+
+/// Render the items in their current order, highlighting the
+/// selected one and marking it when it is held.
+fn render_rank(
+    question: &str,
+    items: &[&str],
+    order: &[usize],
+    selected: usize,
+    held: bool,
+) -> Vec<String> {
+    let mut lines = vec![question.to_string()];
+    for (position, &index) in order.iter().enumerate() {
+        let text = format!("{}. {}", position + 1, items[index]);
+        lines.push(if position != selected {
+            format!("  {}", text)
+        } else if held {
+            format!("= \x1b[7m{}\x1b[0m", text)
+        } else {
+            format!("> \x1b[7m{}\x1b[0m", text)
+        });
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn rank() {
+        macro_rules! rank {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Priority?", input, Vec::new());
+                q.keys = $keys;
+                q.tries(2);
+                let actual = q.rank(&["speed", "cost", "reliability"]);
+                let expected: Option<Vec<&str>> = $expected;
+                let expected = expected.map(|v| v.iter().map(|s| s.to_string()).collect());
+                assert_eq!(expected, actual);
+            };
+        }
+        let unchanged = Some(vec!["speed", "cost", "reliability"]);
+        rank!(b"\n", false, unchanged.clone());
+        rank!(
+            b"3,1,2\n",
+            false,
+            Some(vec!["reliability", "speed", "cost"])
+        );
+        rank!(b"3\n", false, Some(vec!["reliability", "speed", "cost"]));
+        rank!(b"4\n2 2\n", false, None);
+        rank!(
+            b"4\n2 1\n",
+            false,
+            Some(vec!["cost", "speed", "reliability"])
+        );
+        rank!(b"\r", true, unchanged);
+        rank!(
+            b"\x1b[B\x1b[B \x1b[A\x1b[A \r",
+            true,
+            Some(vec!["reliability", "speed", "cost"])
+        );
+        rank!(b" \x1b[B", true, None);
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!(Ok(vec![1, 0, 2]), parse_order("2", 3));
+        assert_eq!(
+            Err(String::from("'0' is not an item number from 1 to 3")),
+            parse_order("0", 3)
+        );
+        assert_eq!(
+            Err(String::from("Item 1 is listed more than once")),
+            parse_order("1,1", 3)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Remembering answers between questions and runs.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use {Answer, Answers, Question};
+
+/// Where `Question::remember` keeps answers, by id.
+///
+/// `MemoryStore` remembers them for as long as the program runs
+/// and `FileStore` in a file, so they are remembered across runs.
+pub trait AnswerStore {
+    /// The answer remembered for `id`, if any.
+    fn get(&self, id: &str) -> Option<Answer>;
+
+    /// Remember `answer` for `id`, replacing any earlier answer.
+    fn set(&mut self, id: &str, answer: &Answer) -> io::Result<()>;
+}
+
+/// Keeps answers in memory. Clones share the answers, so one
+/// store can be given to several questions.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore(Arc<Mutex<Answers>>);
+
+impl MemoryStore {
+    /// Create a store with nothing remembered.
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl AnswerStore for MemoryStore {
+    fn get(&self, id: &str) -> Option<Answer> {
+        let answers = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        answers.get(id).cloned()
+    }
+
+    fn set(&mut self, id: &str, answer: &Answer) -> io::Result<()> {
+        let mut answers = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        answers.insert(id, answer.clone());
+        Ok(())
+    }
+}
+
+/// Keeps answers in a file as a JSON object, in the same format
+/// as `Form::resume`, so they are remembered across runs.
+///
+/// The file is read each time an answer is looked up and written
+/// each time one is remembered. A file that is missing, cannot be
+/// read or does not hold answers remembers nothing.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// Create a store keeping answers in the file at `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> FileStore {
+        FileStore {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The answers in the file, or none if it cannot be read.
+    fn load(&self) -> Answers {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| Answers::from_json(&text).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl AnswerStore for FileStore {
+    fn get(&self, id: &str) -> Option<Answer> {
+        self.load().get(id).cloned()
+    }
+
+    fn set(&mut self, id: &str, answer: &Answer) -> io::Result<()> {
+        let mut answers = self.load();
+        answers.insert(id, answer.clone());
+        fs::write(&self.path, answers.to_json())
+    }
+}
+
+/// How a question uses the answer remembered for it.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Recall {
+    /// Ask as usual, with the remembered answer as the default.
+    /// This is the default.
+    Default,
+
+    /// Answer with the remembered answer without asking.
+    Answer,
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Remember the answer to the question in `store`, under `id`,
+    /// and use it the next time the question is asked, as set by
+    /// `recall`: as the default, unless set otherwise, in place of
+    /// any `default_with`. A store that cannot be written to does
+    /// not stop the question being answered. Answers to `secret`
+    /// questions are never remembered.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{FileStore, Question, Recall};
+    /// let store = FileStore::new(".installer-answers.json");
+    /// Question::new("Send anonymous usage statistics?")
+    ///     .yes_no()
+    ///     .remember("telemetry-choice", store)
+    ///     .recall(Recall::Answer)
+    ///     .confirm();
+    /// ```
+    pub fn remember<S>(&mut self, id: &str, store: S) -> &mut Question<R, W>
+    where
+        S: AnswerStore + Send + 'static,
+    {
+        self.remember = Some((id.to_string(), Arc::new(Mutex::new(store))));
+        self
+    }
+
+    /// Choose how the answer remembered with `remember` is used.
+    pub fn recall(&mut self, recall: Recall) -> &mut Question<R, W> {
+        self.recall = recall;
+        self
+    }
+
+    /// Ask with `ask`, using and then remembering the answer if
+    /// the question is remembered.
+    pub(crate) fn recalled<F>(&mut self, ask: F) -> Option<Answer>
+    where
+        F: FnOnce(&mut Question<R, W>) -> Option<Answer>,
+    {
+        let (id, store) = match self.remember {
+            Some((ref id, ref store)) if !self.secret => (id.clone(), store.clone()),
+            _ => return ask(self),
+        };
+        // Computed now, so the remembered answer replaces it.
+        self.resolve_default();
+        let remembered = store.lock().unwrap_or_else(|e| e.into_inner()).get(&id);
+        let default = self.default.clone();
+        match (remembered, self.recall) {
+            (Some(answer), Recall::Answer) => return Some(answer),
+            (Some(answer), Recall::Default) => self.default = Some(answer),
+            (None, _) => {}
+        }
+        let answer = ask(self);
+        self.default = default;
+        if let Some(ref answer) = answer {
+            if *answer != Answer::CANCELLED {
+                let mut store = store.lock().unwrap_or_else(|e| e.into_inner());
+                // The answer is still returned if it cannot be remembered.
+                let _ = store.set(&id, answer);
+            }
+        }
+        answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn remember() {
+        macro_rules! remember {
+            ($store:expr, $recall:expr, $i:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let mut q = Question::with_cursor("Telemetry?", input, Vec::new());
+                q.show_defaults()
+                    .remember("telemetry", $store.clone())
+                    .recall($recall);
+                assert_eq!($expected, q.confirm());
+                assert_eq!(None, q.default);
+                let output = String::from_utf8(q.writer).expect("Not UTF-8");
+                assert_eq!($shown, output);
+            };
+        }
+        let store = MemoryStore::new();
+        remember!(
+            store,
+            Recall::Default,
+            "n\n",
+            Answer::NO,
+            "Telemetry? (y/n) "
+        );
+        remember!(
+            store,
+            Recall::Default,
+            "\n",
+            Answer::NO,
+            "Telemetry? (y/N) "
+        );
+        remember!(store, Recall::Answer, "y\n", Answer::NO, "");
+        remember!(store, Recall::Default, "", Answer::NO, "Telemetry? (y/N) ");
+        assert_eq!(Some(Answer::NO), store.get("telemetry"));
+
+        let store = MemoryStore::new();
+        remember!(
+            store,
+            Recall::Default,
+            "",
+            Answer::CANCELLED,
+            "Telemetry? (y/n) "
+        );
+        assert_eq!(None, store.get("telemetry"));
+
+        let path = std::env::temp_dir().join(format!("question-remember-{}", std::process::id()));
+        let store = FileStore::new(&path);
+        assert_eq!(None, store.get("telemetry"));
+        remember!(
+            store,
+            Recall::Answer,
+            "y\n",
+            Answer::YES,
+            "Telemetry? (y/n) "
+        );
+        remember!(store, Recall::Answer, "n\n", Answer::YES, "");
+        let saved = fs::read_to_string(&path).unwrap();
+        assert_eq!("{\n  \"telemetry\": true\n}", saved);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn remember_default_with() {
+        let mut store = MemoryStore::new();
+        store.set("telemetry", &Answer::NO).unwrap();
+        let input = Cursor::new(b"\n".to_vec());
+        let mut q = Question::with_cursor("Telemetry?", input, Vec::new());
+        q.show_defaults()
+            .default_with(|| Answer::YES)
+            .remember("telemetry", store.clone());
+        assert_eq!(Answer::NO, q.confirm());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Telemetry? (y/N) ", output);
+    }
+
+    #[test]
+    fn remember_secret() {
+        let mut store = MemoryStore::new();
+        let input = Cursor::new(b"hunter2\n".to_vec());
+        let mut q = Question::with_cursor("Passphrase?", input, Vec::new());
+        q.secret().remember("passphrase", store.clone());
+        assert_eq!(Some(Answer::RESPONSE(String::from("hunter2"))), q.ask());
+        assert_eq!(None, store.get("passphrase"));
+
+        let remembered = Answer::RESPONSE(String::from("swordfish"));
+        store.set("passphrase", &remembered).unwrap();
+        let input = Cursor::new(b"hunter2\n".to_vec());
+        let mut q = Question::with_cursor("Passphrase?", input, Vec::new());
+        q.secret()
+            .remember("passphrase", store.clone())
+            .recall(Recall::Answer);
+        assert_eq!(Some(Answer::RESPONSE(String::from("hunter2"))), q.ask());
+        assert_eq!(Some(remembered), store.get("passphrase"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::{Read, Write};
+use std::ops::RangeInclusive;
+
+use Question;
+
+/// A `Question` answered with a number on a scale, such as a
+/// rating from 1 to 5.
+///
+/// Created with `Question::scale`. The range is shown after the
+/// question, and any labels are listed above it. A response that
+/// is not a whole number on the scale is rejected, and the
+/// question asked again as set by `tries` or `until_acceptable`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::Question;
+/// let rating = Question::new("The docs were easy to follow.")
+///     .until_acceptable()
+///     .scale(1..=5)
+///     .label(1, "strongly disagree")
+///     .label(3, "neutral")
+///     .label(5, "strongly agree")
+///     .ask();
+/// ```
+pub struct ScaleQuestion<'a, R, W>
+where
+    R: Read + 'a,
+    W: Write + 'a,
+{
+    question: &'a mut Question<R, W>,
+    range: RangeInclusive<i64>,
+    labels: Vec<(i64, String)>,
+}
+
+impl<'a, R, W> ScaleQuestion<'a, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    pub(crate) fn new(
+        question: &'a mut Question<R, W>,
+        range: RangeInclusive<i64>,
+    ) -> ScaleQuestion<'a, R, W> {
+        ScaleQuestion {
+            question,
+            range,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Describe what a point on the scale means.
+    pub fn label(&mut self, value: i64, label: &str) -> &mut ScaleQuestion<'a, R, W> {
+        self.labels.push((value, label.to_string()));
+        self
+    }
+
+    /// Ask the question, returning the point on the scale chosen.
+    ///
+    /// An empty response uses the default set on the question
+    /// with `Answer::RESPONSE`. `None` is returned if no point on
+    /// the scale was given.
+    pub fn ask(&mut self) -> Option<i64> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        if !self.labels.is_empty() {
+            let mut labels = self.labels.clone();
+            labels.sort_by_key(|&(value, _)| value);
+            let legend: Vec<String> = labels
+                .iter()
+                .map(|(value, label)| format!("{} = {}", value, label))
+                .collect();
+            self.question
+                .show_text(&format!("{}\n", legend.join(", ")))
+                .ok()?;
+        }
+        let question = self.question.question.clone();
+        self.question.question = format!("{} [{}-{}]", question, start, end);
+        let range = self.range.clone();
+        let answer = self.question.ask_parsed(|response| match response.parse() {
+            Ok(value) if range.contains(&value) => Ok(value),
+            _ => Err(format!(
+                "Please enter a whole number from {} to {}",
+                start, end
+            )),
+        });
+        self.question.question = question;
+        answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use {Answer, Question};
+
+    #[test]
+    fn scale() {
+        macro_rules! scale {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let actual = Question::with_cursor("Rating?", input, Vec::new())
+                    .tries(2)
+                    .scale(1..=5)
+                    .ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        scale!("4\n", Some(4));
+        scale!("6\n1\n", Some(1));
+        scale!("2.5\nfive\n", None);
+        scale!(" 5 \n", Some(5));
+
+        let input = Cursor::new(String::from("0\n\n").into_bytes());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Easy?", input, &mut output);
+            q.until_acceptable()
+                .default(Answer::RESPONSE(String::from("3")));
+            let actual = q
+                .scale(1..=5)
+                .label(5, "strongly agree")
+                .label(1, "strongly disagree")
+                .ask();
+            assert_eq!(Some(3), actual);
+            assert_eq!("Easy?", q.question);
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "1 = strongly disagree, 5 = strongly agree\n\
+             Easy? [1-5] \
+             Please enter a whole number from 1 to 5\nEasy? [1-5] ",
+            output
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Answers to `secret` questions, wiped from memory once dropped.
+
+use std::fmt;
+use std::io::{Read, Write};
+#[cfg(not(feature = "zeroize"))]
+use std::ptr;
+#[cfg(not(feature = "zeroize"))]
+use std::sync::atomic::{self, Ordering};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use {Answer, Question, REDACTED};
+
+/// A response to a `secret` question, such as a passphrase.
+///
+/// Its memory is overwritten with zeroes when it is dropped, with
+/// the `zeroize` crate when the `zeroize` feature is enabled. It
+/// is shown as `REDACTED` by `Debug` and cannot be cloned, so the
+/// only copies are the ones made from `expose`.
+///
+/// Returned by `Question::ask_secret`.
+pub struct Secret(String);
+
+impl Secret {
+    /// Wrap `secret`, taking ownership so it is wiped on drop.
+    pub fn new(secret: String) -> Secret {
+        Secret(secret)
+    }
+
+    /// The secret itself.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// Overwrite `text` with zeroes and empty it.
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe(text: &mut String) {
+    text.zeroize();
+}
+
+/// Overwrite `text` with zeroes and empty it.
+#[cfg(not(feature = "zeroize"))]
+pub(crate) fn wipe(text: &mut String) {
+    let mut bytes = std::mem::take(text).into_bytes();
+    for byte in bytes.iter_mut() {
+        // Volatile so the writes to memory about to be freed are
+        // not optimized away.
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// Overwrite `chars` with zeroes and empty it.
+pub(crate) fn wipe_chars(chars: &mut Vec<char>) {
+    #[cfg(feature = "zeroize")]
+    chars.zeroize();
+    #[cfg(not(feature = "zeroize"))]
+    {
+        for c in chars.iter_mut() {
+            unsafe { ptr::write_volatile(c, '\0') };
+        }
+        atomic::compiler_fence(Ordering::SeqCst);
+        chars.clear();
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask the question as a `secret`, returning the response as a
+    /// `Secret` that is wiped from memory once dropped. Copies made
+    /// while reading and checking the response are wiped too. Any
+    /// other answer, such as a skip, gives `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// if let Some(passphrase) = Question::new("Vault passphrase:").ask_secret() {
+    ///     println!("{} characters", passphrase.expose().chars().count());
+    /// }
+    /// ```
+    pub fn ask_secret(&mut self) -> Option<Secret> {
+        self.secret();
+        match self.ask() {
+            Some(Answer::RESPONSE(response)) => Some(Secret(response)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use AskError;
+
+    #[test]
+    fn secret() {
+        let input = Cursor::new(String::from("hunter2\n").into_bytes());
+        let secret = Question::with_cursor("Passphrase?", input, Vec::new())
+            .ask_secret()
+            .unwrap();
+        assert_eq!("hunter2", secret.expose());
+        assert_eq!("Secret([redacted])", format!("{:?}", secret));
+
+        let mut text = String::from("hunter2");
+        wipe(&mut text);
+        assert_eq!("", text);
+
+        let input = Cursor::new(String::from("short\n").into_bytes());
+        let mut q = Question::with_cursor("Passphrase?", input, Vec::new());
+        q.secret().min_len(8).strict();
+        assert_eq!(Err(AskError::Rejected(String::from(REDACTED))), q.try_ask());
+    }
+}
@@ -0,0 +1,778 @@
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use locale::Texts;
+use term::{self, Key, RawMode};
+use {Answer, Question};
+
+/// How many choices are shown at once on an interactive terminal.
+const VISIBLE: usize = 10;
+
+/// A `Question` answered by choosing one of a list of choices.
+///
+/// Created with `Question::select`. On an interactive terminal
+/// (see `toggle`) the choices are listed under the question and
+/// one is highlighted with the arrow keys and chosen with enter.
+/// Typing filters the list to the choices that match, and escape
+/// clears the filter. With the `fuzzy` feature the letters typed
+/// only need to appear in order, like fzf, and the closest matches
+/// are listed first; otherwise choices must contain the text.
+///
+/// Elsewhere the choices are numbered and either the number or the
+/// choice itself is entered, asking again as set by `tries` or
+/// `until_acceptable`. Long lists are shown a page at a time, and
+/// "n" and "p" move to the next and previous pages.
+///
+/// Choices are numbered either way, and on an interactive terminal
+/// pressing one of the digits 1 to 9 before typing a filter chooses
+/// that choice straight away. A `default_choice` is marked, and is
+/// chosen when enter is pressed without a response.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::Question;
+/// let region = Question::new("Region?")
+///     .until_acceptable()
+///     .select(&["us-east-1", "us-west-2", "eu-west-1", "ap-south-1"])
+///     .ask();
+/// ```
+pub struct SelectQuestion<'a, R, W>
+where
+    R: Read + 'a,
+    W: Write + 'a,
+{
+    question: &'a mut Question<R, W>,
+    choices: Vec<String>,
+    headers: Vec<(usize, String)>,
+    disabled: Vec<(usize, String)>,
+    page_size: usize,
+    default: Option<usize>,
+}
+
+/// What a response to a numbered list of choices asks for.
+enum Pick {
+    Choice(usize),
+    NextPage,
+    PreviousPage,
+}
+
+impl<'a, R, W> SelectQuestion<'a, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    pub(crate) fn new(
+        question: &'a mut Question<R, W>,
+        choices: &[&str],
+    ) -> SelectQuestion<'a, R, W> {
+        SelectQuestion {
+            question,
+            choices: choices.iter().map(|c| c.to_string()).collect(),
+            headers: Vec::new(),
+            disabled: Vec::new(),
+            page_size: 10,
+            default: None,
+        }
+    }
+
+    /// Make `choice` the default, marked in the list and chosen
+    /// when no response is given. It is ignored if it is not one
+    /// of the choices.
+    pub fn default_choice(&mut self, choice: &str) -> &mut SelectQuestion<'a, R, W> {
+        self.default = self.choices.iter().position(|c| c == choice);
+        self
+    }
+
+    /// Add `choices` to the end of the list under `header`, which
+    /// is shown above them as a row that cannot be chosen, such as
+    /// "— production —". An empty header shows a plain separator.
+    /// Headers are hidden while the list is filtered, as matches
+    /// are no longer in their groups.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let instance = Question::new("Instance?")
+    ///     .select(&[])
+    ///     .group("production", &["web-1", "web-2"])
+    ///     .group("staging", &["web-3"])
+    ///     .ask();
+    /// ```
+    pub fn group(&mut self, header: &str, choices: &[&str]) -> &mut SelectQuestion<'a, R, W> {
+        self.headers.push((self.choices.len(), header.to_string()));
+        self.choices.extend(choices.iter().map(|c| c.to_string()));
+        self
+    }
+
+    /// Keep `choice` in the list but stop it being chosen, showing
+    /// `reason`, such as "requires admin", beside it, dimmed on
+    /// interactive terminals. Choosing it shows the reason instead.
+    /// It is ignored if it is not one of the choices.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let action = Question::new("Action?")
+    ///     .select(&["view logs", "restart", "delete"])
+    ///     .disable("delete", "requires admin")
+    ///     .ask();
+    /// ```
+    pub fn disable(&mut self, choice: &str, reason: &str) -> &mut SelectQuestion<'a, R, W> {
+        if let Some(i) = self.choices.iter().position(|c| c == choice) {
+            self.disabled.push((i, reason.to_string()));
+        }
+        self
+    }
+
+    /// Why the choice at `i` cannot be chosen, if it is disabled.
+    fn disabled_reason(&self, i: usize) -> Option<&str> {
+        self.disabled
+            .iter()
+            .find(|&&(d, _)| d == i)
+            .map(|(_, reason)| reason.as_str())
+    }
+
+    /// Show at most `size` numbered choices at a time when the
+    /// list is not interactive. The default is 10, and 0 shows
+    /// every choice at once.
+    pub fn page_size(&mut self, size: usize) -> &mut SelectQuestion<'a, R, W> {
+        self.page_size = size;
+        self
+    }
+
+    /// Ask the question, returning the choice made, or `None` if
+    /// input ends or no choice is made.
+    pub fn ask(&mut self) -> Option<String> {
+        self.ask_index().map(|i| self.choices[i].clone())
+    }
+
+    /// Ask the question like `ask`, returning the position of the
+    /// choice made in the list.
+    pub fn ask_index(&mut self) -> Option<usize> {
+        if self.choices.is_empty() {
+            return None;
+        }
+        if self.question.dry_run {
+            // Previewed with the default choice in place of the
+            // question's own default.
+            let default = self
+                .default
+                .map(|i| Answer::RESPONSE(self.choices[i].clone()));
+            let own = std::mem::replace(&mut self.question.default, default);
+            let previewed = self.question.preview();
+            self.question.default = own;
+            return previewed.and(self.default);
+        }
+        if self.question.keys && self.question.overridden().is_none() {
+            self.select_interactive().unwrap_or(None)
+        } else {
+            self.select_lines().unwrap_or(None)
+        }
+    }
+
+    /// The choices as they are listed, numbered and with the
+    /// default and the reasons disabled choices cannot be chosen
+    /// marked.
+    fn labels(&self) -> Vec<String> {
+        let marker = self.question.text("choice-default", &[]);
+        self.choices
+            .iter()
+            .enumerate()
+            .map(
+                |(i, choice)| match (self.default, self.disabled_reason(i)) {
+                    (_, Some(reason)) => format!("{}) {} ({})", i + 1, choice, reason),
+                    (Some(d), None) if d == i => format!("{}) {} {}", i + 1, choice, marker),
+                    _ => format!("{}) {}", i + 1, choice),
+                },
+            )
+            .collect()
+    }
+
+    fn select_interactive(&mut self) -> Result<Option<usize>, std::io::Error> {
+        let raw = RawMode::enable()?;
+        let mut filter = String::new();
+        let first = self.default.unwrap_or(0);
+        let (mut selected, mut offset) = (first, 0);
+        let mut drawn = 0;
+        let labels: Vec<String> = self
+            .labels()
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| match self.disabled_reason(i) {
+                Some(_) => format!("\x1b[2m{}\x1b[22m", label),
+                None => label,
+            })
+            .collect();
+        let mut notice: Option<String> = None;
+        loop {
+            let matches = filter_choices(&filter, &self.choices);
+            selected = selected.min(matches.len().saturating_sub(1));
+            if selected < offset {
+                offset = selected;
+            } else if selected >= offset + VISIBLE {
+                offset = selected + 1 - VISIBLE;
+            }
+            let headers: &[(usize, String)] = if filter.is_empty() {
+                &self.headers
+            } else {
+                &[]
+            };
+            let heading = format!("{} {}", self.question.question_text(), filter);
+            let mut lines = render_select(
+                &heading,
+                &self.question.texts(),
+                &labels,
+                headers,
+                &matches,
+                selected,
+                offset,
+            );
+            lines.extend(notice.take());
+            let mut frame = String::new();
+            if drawn > 1 {
+                frame += &format!("\x1b[{}A", drawn - 1);
+            }
+            frame += "\r";
+            frame += &lines
+                .iter()
+                .map(|line| format!("\x1b[2K{}", line))
+                .collect::<Vec<_>>()
+                .join("\n");
+            frame += "\x1b[J";
+            drawn = lines.len();
+            self.question.show(&frame)?;
+
+            match term::read_key(&mut self.question.reader)? {
+                Some(Key::Up) => selected = selected.saturating_sub(1),
+                Some(Key::Down) => selected = (selected + 1).min(matches.len().saturating_sub(1)),
+                Some(Key::Char(c)) if filter.is_empty() && ('1'..='9').contains(&c) => {
+                    let i = c as usize - '1' as usize;
+                    if i < self.choices.len() {
+                        notice = self.disabled_reason(i).map(String::from);
+                        if notice.is_none() {
+                            self.question.show_text("\n")?;
+                            return Ok(Some(i));
+                        }
+                    }
+                }
+                Some(Key::Char(c)) => {
+                    filter.push(c);
+                    selected = 0;
+                }
+                Some(Key::Backspace) => {
+                    filter.pop();
+                    selected = if filter.is_empty() { first } else { 0 };
+                }
+                Some(Key::Esc) => {
+                    filter.clear();
+                    selected = first;
+                }
+                Some(Key::Enter) if !matches.is_empty() => {
+                    notice = self.disabled_reason(matches[selected]).map(String::from);
+                    if notice.is_none() {
+                        self.question.show_text("\n")?;
+                        return Ok(Some(matches[selected]));
+                    }
+                }
+                Some(Key::Interrupt) => {
+                    self.question.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
+                Some(Key::Eof) | None => {
+                    self.question.show_text("\n")?;
+                    return Ok(None);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn select_lines(&mut self) -> Result<Option<usize>, std::io::Error> {
+        let count = self.choices.len();
+        let size = if self.page_size == 0 {
+            count
+        } else {
+            self.page_size
+        };
+        let pages = count.div_ceil(size);
+        let mut page = 0;
+        loop {
+            let start = page * size;
+            let end = (start + size).min(count);
+            let mut list = String::new();
+            let labels = self.labels();
+            for (i, label) in labels.iter().enumerate().take(end).skip(start) {
+                if let Some(header) = header_above(&self.headers, i, i == start) {
+                    list += &format!("  {}\n", header);
+                }
+                list += &format!("  {}\n", label);
+            }
+            if pages > 1 {
+                let (first, last) = ((start + 1).to_string(), end.to_string());
+                let args = [
+                    ("first", first.as_str()),
+                    ("last", last.as_str()),
+                    ("count", &count.to_string()),
+                ];
+                list += &format!("  {}\n", self.question.text("choice-page", &args));
+            }
+            self.question.show_text(&list)?;
+            let choices = &self.choices;
+            let disabled = &self.disabled;
+            let default = self.default;
+            let required = self
+                .question
+                .text("choice-required", &[("count", &count.to_string())]);
+            let pick = self.question.ask_parsed(|response| {
+                let chosen = match response.parse::<usize>() {
+                    _ if response.is_empty() => default,
+                    Ok(n) if n >= 1 && n <= count => Some(n - 1),
+                    _ => choices
+                        .iter()
+                        .position(|c| c.eq_ignore_ascii_case(response)),
+                };
+                if let Some(i) = chosen {
+                    return match disabled.iter().find(|&&(d, _)| d == i) {
+                        Some((_, reason)) => Err(reason.clone()),
+                        None => Ok(Pick::Choice(i)),
+                    };
+                }
+                match response {
+                    "n" if pages > 1 => Ok(Pick::NextPage),
+                    "p" if pages > 1 => Ok(Pick::PreviousPage),
+                    _ => Err(required.clone()),
+                }
+            });
+            match pick {
+                Some(Pick::Choice(i)) => return Ok(Some(i)),
+                Some(Pick::NextPage) => page = (page + 1) % pages,
+                Some(Pick::PreviousPage) => page = (page + pages - 1) % pages,
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// The positions of the choices matching `filter`, best first.
+fn filter_choices(filter: &str, choices: &[String]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i64)> = choices
+        .iter()
+        .enumerate()
+        .filter_map(|(i, choice)| score(filter, choice).map(|s| (i, s)))
+        .collect();
+    scored.sort_by_key(|&(i, score)| (-score, i));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+/// How closely `choice` matches `filter`, if it does at all: the
+/// letters of the filter must appear in order, and score higher
+/// when they are next to each other or start a word.
+#[cfg(feature = "fuzzy")]
+fn score(filter: &str, choice: &str) -> Option<i64> {
+    if filter.is_empty() {
+        return Some(0);
+    }
+    let choice: Vec<char> = choice.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in filter.chars().flat_map(char::to_lowercase) {
+        let found = position + choice[position..].iter().position(|&d| d == c)?;
+        score += 1;
+        if previous.map_or(found == 0, |p| found == p + 1) {
+            score += 4;
+        }
+        if found == 0 || !choice[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score - choice.len() as i64 / 8)
+}
+
+/// Whether `choice` contains `filter`, ignoring case. Choices keep
+/// their order, so every match scores the same.
+#[cfg(not(feature = "fuzzy"))]
+fn score(filter: &str, choice: &str) -> Option<i64> {
+    let filter = filter.to_lowercase();
+    if choice.to_lowercase().contains(&filter) {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// The header to show above the choice at `i`: the one starting
+/// its group, or when it is the `first` choice shown, the one of
+/// the group it is in.
+fn header_above(headers: &[(usize, String)], i: usize, first: bool) -> Option<String> {
+    let &(start, ref header) = headers.iter().rev().find(|&&(start, _)| start <= i)?;
+    if start != i && !first {
+        return None;
+    }
+    Some(if header.is_empty() {
+        String::from("—")
+    } else {
+        format!("— {} —", header)
+    })
+}
+
+/// Render the `heading`, the question and filter, then the labels
+/// of the matching choices visible from `offset`, under their
+/// `headers`, highlighting the selected one.
+fn render_select(
+    heading: &str,
+    texts: &Texts,
+    labels: &[String],
+    headers: &[(usize, String)],
+    matches: &[usize],
+    selected: usize,
+    offset: usize,
+) -> Vec<String> {
+    let mut lines = vec![heading.to_string()];
+    if matches.is_empty() {
+        lines.push(format!("  {}", texts.text("no-matches", &[])));
+    }
+    for (position, &i) in matches.iter().enumerate().skip(offset).take(VISIBLE) {
+        if let Some(header) = header_above(headers, i, position == offset) {
+            lines.push(format!("  {}", header));
+        }
+        if position == selected {
+            lines.push(format!("> \x1b[7m{}\x1b[0m", labels[i]));
+        } else {
+            lines.push(format!("  {}", labels[i]));
+        }
+    }
+    if matches.len() > VISIBLE {
+        let shown = matches.len().min(offset + VISIBLE).to_string();
+        let count = matches.len().to_string();
+        let args = [("shown", shown.as_str()), ("count", count.as_str())];
+        lines.push(format!("  {}", texts.text("matches-shown", &args)));
+    }
+    lines
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask the user to choose one of `items`, listed as they are
+    /// displayed, returning the item chosen. See `SelectQuestion`
+    /// for how the choice is made.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// # use std::fmt;
+    /// struct Account {
+    ///     name: &'static str,
+    ///     id: u64,
+    /// }
+    ///
+    /// impl fmt::Display for Account {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "{} ({})", self.name, self.id)
+    ///     }
+    /// }
+    ///
+    /// let accounts = [
+    ///     Account { name: "staging", id: 1021 },
+    ///     Account { name: "production", id: 2048 },
+    /// ];
+    /// if let Some(account) = Question::new("Account?").select_from(&accounts) {
+    ///     println!("Using account {}", account.id);
+    /// }
+    /// ```
+    pub fn select_from<'t, T: Display>(&mut self, items: &'t [T]) -> Option<&'t T> {
+        let labels: Vec<String> = items.iter().map(T::to_string).collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        self.select(&labels).ask_index().map(|i| &items[i])
+    }
+
+    /// Ask the user to choose one of `items` like `select_from`,
+    /// returning the item chosen and dropping the rest.
+    pub fn select_owned<T: Display>(&mut self, items: Vec<T>) -> Option<T> {
+        let i = {
+            let labels: Vec<String> = items.iter().map(T::to_string).collect();
+            let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+            self.select(&labels).ask_index()?
+        };
+        items.into_iter().nth(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use AskError;
+
+    #[test]
+    fn group() {
+        macro_rules! group {
+            ($i:expr, $keys:expr, $page_size:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut output = Vec::new();
+                {
+                    let mut q = Question::with_cursor("Instance?", input, &mut output);
+                    q.keys = $keys;
+                    let actual = q
+                        .select(&["local"])
+                        .group("production", &["web-1", "web-2"])
+                        .group("", &["web-3"])
+                        .page_size($page_size)
+                        .ask();
+                    assert_eq!(Some(String::from($expected)), actual);
+                }
+                let output = String::from_utf8(output).unwrap();
+                assert!(output.contains($shown), "{:?}", output);
+            };
+        }
+        let list = "  1) local\n  — production —\n  2) web-1\n  3) web-2\n  —\n  4) web-3\n";
+        group!(b"3\n", false, 0, "web-2", list);
+        group!(
+            b"n\n3\n",
+            false,
+            2,
+            "web-2",
+            "  — production —\n  3) web-2\n"
+        );
+        group!(
+            b"\x1b[B\x1b[B\r",
+            true,
+            0,
+            "web-2",
+            "\x1b[2K  — production —"
+        );
+        group!(b"web-3\r", true, 0, "web-3", "\x1b[2K> \x1b[7m4) web-3");
+    }
+
+    #[test]
+    fn disable() {
+        macro_rules! disable {
+            ($i:expr, $keys:expr, $expected:expr, $shown:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut output = Vec::new();
+                {
+                    let mut q = Question::with_cursor("Action?", input, &mut output);
+                    q.keys = $keys;
+                    q.tries(2);
+                    let actual = q
+                        .select(&["restart", "delete"])
+                        .disable("delete", "requires admin")
+                        .disable("missing", "ignored")
+                        .ask();
+                    assert_eq!($expected.map(String::from), actual);
+                }
+                let output = String::from_utf8(output).unwrap();
+                assert!(output.contains($shown), "{:?}", output);
+            };
+        }
+        disable!(
+            b"2\n1\n",
+            false,
+            Some("restart"),
+            "2) delete (requires admin)"
+        );
+        disable!(
+            b"delete\n1\n",
+            false,
+            Some("restart"),
+            "requires admin\nAction? "
+        );
+        disable!(b"2\n", false, None::<&str>, "requires admin\n");
+        disable!(
+            b"2\r",
+            true,
+            Some("restart"),
+            "\x1b[2m2) delete (requires admin)\x1b[22m"
+        );
+        disable!(
+            b"\x1b[B\r\x1b[A\r",
+            true,
+            Some("restart"),
+            "\x1b[2Krequires admin"
+        );
+    }
+
+    #[test]
+    fn select_from() {
+        let ports = [22, 80, 443];
+        let input = Cursor::new(b"443\n".to_vec());
+        let mut q = Question::with_cursor("Port?", input, Vec::new());
+        assert_eq!(Some(&443), q.select_from(&ports));
+
+        let input = Cursor::new(b"2\n".to_vec());
+        let mut q = Question::with_cursor("Port?", input, Vec::new());
+        assert_eq!(Some(80), q.select_owned(ports.to_vec()));
+
+        let input = Cursor::new(Vec::new());
+        let mut q = Question::with_cursor("Port?", input, Vec::new());
+        assert_eq!(None, q.select_from(&ports));
+    }
+
+    #[test]
+    fn select() {
+        macro_rules! select {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Region?", input, Vec::new());
+                q.keys = $keys;
+                q.tries(2);
+                let actual = q.select(&["us-east-1", "us-west-2", "eu-west-1"]).ask();
+                assert_eq!($expected.map(String::from), actual);
+            };
+        }
+        select!(b"2\n", false, Some("us-west-2"));
+        select!(b"EU-WEST-1\n", false, Some("eu-west-1"));
+        select!(b"4\n0\n", false, None::<&str>);
+        select!(b"x\n1\n", false, Some("us-east-1"));
+        select!(b"\r", true, Some("us-east-1"));
+        select!(b"\x1b[B\x1b[B\x1b[B\r", true, Some("eu-west-1"));
+        select!(b"eu\r", true, Some("eu-west-1"));
+        select!(b"zz\r\x7f\x7f\x1b[A\r", true, Some("us-east-1"));
+        select!(b"west\x1b[B\r", true, Some("eu-west-1"));
+        select!(b"", true, None::<&str>);
+    }
+
+    #[test]
+    fn select_dry_run() {
+        let input = Cursor::new(b"1\n".to_vec());
+        let mut q = Question::with_cursor("Region?", input, Vec::new());
+        q.dry_run();
+        let actual = q
+            .select(&["us-east-1", "eu-west-1"])
+            .default_choice("eu-west-1")
+            .ask();
+        assert_eq!(Some(String::from("eu-west-1")), actual);
+        assert_eq!(None, q.default);
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert_eq!("Region? eu-west-1\n", output);
+
+        let input = Cursor::new(b"1\n".to_vec());
+        let mut q = Question::with_cursor("Region?", input, Vec::new());
+        q.dry_run();
+        assert_eq!(None, q.select(&["us-east-1", "eu-west-1"]).ask_index());
+        assert_eq!(Some(AskError::NoDefault), q.error);
+    }
+
+    #[test]
+    fn pages() {
+        let choices: Vec<String> = (1..=25).map(|i| format!("c{}", i)).collect();
+        let choices: Vec<&str> = choices.iter().map(|c| c.as_str()).collect();
+        let input = Cursor::new(b"n\nn\np\n14\n".to_vec());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Pick?", input, &mut output);
+            q.keys = false;
+            let actual = q.select(&choices).ask();
+            assert_eq!(Some(String::from("c14")), actual);
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        let navigation: Vec<&str> = output.lines().filter(|l| l.contains(" of 25")).collect();
+        assert_eq!(
+            vec![
+                "  1-10 of 25, n=next, p=prev",
+                "  11-20 of 25, n=next, p=prev",
+                "  21-25 of 25, n=next, p=prev",
+                "  11-20 of 25, n=next, p=prev",
+            ],
+            navigation
+        );
+        assert!(output.contains("  21) c21\n"));
+        let first_page = output.split("  1-10 of 25").next().unwrap();
+        assert!(first_page.ends_with("  10) c10\n"));
+        assert!(!first_page.contains("c11"));
+
+        let input = Cursor::new(b"n\nb\n".to_vec());
+        let mut q = Question::with_cursor("Pick?", input, Vec::new());
+        q.keys = false;
+        assert_eq!(Some(1), q.select(&["a", "b", "c"]).page_size(2).ask_index());
+        let input = Cursor::new(b"n\n".to_vec());
+        let mut q = Question::with_cursor("Pick?", input, Vec::new());
+        q.keys = false;
+        assert_eq!(None, q.select(&["a", "b", "c"]).page_size(0).ask_index());
+        let input = Cursor::new(b"n\n".to_vec());
+        let mut q = Question::with_cursor("Pick?", input, Vec::new());
+        q.keys = false;
+        assert_eq!(Some(1), q.select(&["m", "n"]).ask_index());
+    }
+
+    #[test]
+    fn default_choice() {
+        macro_rules! default {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Env?", input, Vec::new());
+                q.keys = $keys;
+                let actual = q
+                    .select(&["dev", "staging", "production"])
+                    .default_choice("staging")
+                    .ask();
+                assert_eq!(Some(String::from($expected)), actual);
+            };
+        }
+        default!(b"\n", false, "staging");
+        default!(b"3\n", false, "production");
+        default!(b"\r", true, "staging");
+        default!(b"\x1b[A\r", true, "dev");
+        default!(b"3", true, "production");
+        default!(b"p\x7f\r", true, "staging");
+        default!(b"9d\r", true, "dev");
+
+        let input = Cursor::new(b"1\n".to_vec());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Env?", input, &mut output);
+            q.keys = false;
+            q.select(&["dev", "staging"])
+                .default_choice("staging")
+                .ask();
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!("  1) dev\n  2) staging (default)\nEnv? ", output);
+    }
+
+    #[test]
+    fn filter() {
+        let choices: Vec<String> = ["staging", "production", "preview"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(vec![0, 1, 2], filter_choices("", &choices));
+        assert_eq!(vec![1], filter_choices("DUC", &choices));
+        #[cfg(feature = "fuzzy")]
+        {
+            assert_eq!(vec![2, 1], filter_choices("pr", &choices));
+            assert_eq!(vec![0], filter_choices("sgn", &choices));
+        }
+        #[cfg(not(feature = "fuzzy"))]
+        {
+            assert_eq!(vec![1, 2], filter_choices("pr", &choices));
+            assert!(filter_choices("sgn", &choices).is_empty());
+        }
+    }
+
+    #[test]
+    fn render() {
+        let choices: Vec<String> = (1..=12).map(|i| format!("{}) c{}", i, i)).collect();
+        let matches: Vec<usize> = (0..12).collect();
+        let texts = Texts::default();
+        let lines = render_select("Pick? c", &texts, &choices, &[], &matches, 2, 1);
+        assert_eq!("Pick? c", lines[0]);
+        assert_eq!("  2) c2", lines[1]);
+        assert_eq!("> \x1b[7m3) c3\x1b[0m", lines[2]);
+        assert_eq!("  (11 of 12)", lines[11]);
+        assert_eq!(
+            vec!["Pick? x", "  (no matches)"],
+            render_select("Pick? x", &texts, &choices, &[], &[], 0, 0)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Sharing the terminal between threads that ask questions.
+
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Condvar, Mutex};
+
+use Question;
+
+/// The order threads take turns at the terminal in.
+#[derive(Debug, Default)]
+struct Queue {
+    next: u64,
+    serving: u64,
+}
+
+/// Shares the terminal between worker threads that may need to ask
+/// the operator something, such as in a parallel deployment tool.
+///
+/// Questions asked in a session take turns, in the order they were
+/// asked, so one is answered before the next is shown rather than
+/// their prompts interleaving. Each worker can be given its own
+/// copy with `labeled`, so the operator can tell which one is
+/// asking.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{PromptSession, Question};
+/// # use std::thread;
+/// let session = PromptSession::new();
+/// let workers: Vec<_> = ["web-1", "web-2", "db-1"]
+///     .iter()
+///     .map(|host| {
+///         let session = session.labeled(host);
+///         thread::spawn(move || {
+///             session.println("Deploying");
+///             Question::new("Restart the service?")
+///                 .yes_no()
+///                 .session(&session)
+///                 .confirm()
+///         })
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PromptSession {
+    queue: Arc<(Mutex<Queue>, Condvar)>,
+    label: Option<String>,
+}
+
+impl PromptSession {
+    /// Create a session with no questions waiting.
+    pub fn new() -> PromptSession {
+        PromptSession::default()
+    }
+
+    /// A copy of the session whose questions and lines are shown
+    /// after `label` in brackets, such as "[web-1] Restart?".
+    pub fn labeled(&self, label: &str) -> PromptSession {
+        PromptSession {
+            queue: self.queue.clone(),
+            label: Some(label.to_string()),
+        }
+    }
+
+    /// The label given by `labeled`, if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Print `line` to standard output once no question is being
+    /// asked, after the label if there is one.
+    pub fn println(&self, line: &str) {
+        let _turn = self.turn();
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        // Like `println!`, but not panicking if stdout has closed.
+        let _ = writeln!(stdout, "{}", self.labeled_text(line));
+    }
+
+    /// `text` after the label, if there is one.
+    pub(crate) fn labeled_text(&self, text: &str) -> String {
+        match self.label {
+            Some(ref label) => format!("[{}] {}", label, text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Wait for the terminal, holding it until the turn is dropped.
+    pub(crate) fn turn(&self) -> Turn {
+        let (ref queue, ref turns) = *self.queue;
+        let mut queue = queue.lock().unwrap_or_else(|e| e.into_inner());
+        let ticket = queue.next;
+        queue.next += 1;
+        while queue.serving != ticket {
+            queue = turns.wait(queue).unwrap_or_else(|e| e.into_inner());
+        }
+        Turn {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+/// A thread's turn at the terminal, passed on when dropped.
+pub(crate) struct Turn {
+    queue: Arc<(Mutex<Queue>, Condvar)>,
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let (ref queue, ref turns) = *self.queue;
+        queue.lock().unwrap_or_else(|e| e.into_inner()).serving += 1;
+        turns.notify_all();
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Ask the question in `session`, waiting for any question
+    /// being asked by another thread to be answered first, and
+    /// showing the session's label before the question.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{PromptSession, Question};
+    /// let session = PromptSession::new().labeled("web-1");
+    /// // Shown as "[web-1] Overwrite the config?"
+    /// Question::new("Overwrite the config?")
+    ///     .session(&session)
+    ///     .confirm();
+    /// ```
+    pub fn session(&mut self, session: &PromptSession) -> &mut Question<R, W> {
+        self.session = Some(session.clone());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::thread;
+    use Answer;
+
+    /// Output shared by several questions, written a byte at a time
+    /// so unserialized prompts would interleave.
+    #[derive(Clone)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            match buf.first() {
+                Some(&byte) => {
+                    self.0.lock().unwrap().push(byte);
+                    thread::yield_now();
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn session() {
+        let session = PromptSession::new();
+        let output = Shared(Arc::new(Mutex::new(Vec::new())));
+        let workers: Vec<_> = ["a", "b"]
+            .iter()
+            .map(|label| {
+                let session = session.labeled(label);
+                let output = output.clone();
+                thread::spawn(move || {
+                    let input = Cursor::new(String::from("maybe\ny\n").into_bytes());
+                    Question::with_cursor("Continue?", input, output)
+                        .yes_no()
+                        .session(&session)
+                        .confirm()
+                })
+            })
+            .collect();
+        for worker in workers {
+            assert_eq!(Answer::YES, worker.join().unwrap());
+        }
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let asked = |label| format!("[{0}] Continue? [{0}] Continue? ", label);
+        assert!(
+            output == asked("a") + &asked("b") || output == asked("b") + &asked("a"),
+            "{:?}",
+            output
+        );
+        assert_eq!(Some("a"), session.labeled("a").label());
+        assert_eq!("text", session.labeled_text("text"));
+    }
+}
@@ -0,0 +1,258 @@
+use std::io::{Read, Write};
+use std::ops::RangeInclusive;
+
+use term::{self, Key, RawMode};
+use {Answer, Question};
+
+/// A `Question` answered with a whole number in a range, such as
+/// a number of workers.
+///
+/// Created with `Question::stepper`. On an interactive terminal
+/// (see `toggle`) the number is shown highlighted after the
+/// question, and the up and down arrow keys step it up and down
+/// within the range, until enter is pressed. A number can also be
+/// typed in. Elsewhere the range is shown after the question and
+/// the number is typed, as with `scale`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Answer, Question};
+/// let workers = Question::new("How many workers?")
+///     .default(Answer::RESPONSE(String::from("4")))
+///     .until_acceptable()
+///     .stepper(1..=64)
+///     .ask();
+/// ```
+pub struct StepperQuestion<'a, R, W>
+where
+    R: Read + 'a,
+    W: Write + 'a,
+{
+    question: &'a mut Question<R, W>,
+    range: RangeInclusive<i64>,
+    step: i64,
+}
+
+impl<'a, R, W> StepperQuestion<'a, R, W>
+where
+    R: Read,
+    W: Write,
+{
+    pub(crate) fn new(
+        question: &'a mut Question<R, W>,
+        range: RangeInclusive<i64>,
+    ) -> StepperQuestion<'a, R, W> {
+        StepperQuestion {
+            question,
+            range,
+            step: 1,
+        }
+    }
+
+    /// Step the number up and down by `step` rather than one. A
+    /// number that is typed must be a whole number of steps from
+    /// the start of the range, and the arrow keys only stop on
+    /// such numbers, the default being rounded down to one.
+    pub fn step(&mut self, step: i64) -> &mut StepperQuestion<'a, R, W> {
+        self.step = step.max(1);
+        self
+    }
+
+    /// Ask the question, returning the number chosen.
+    ///
+    /// The default set on the question with `Answer::RESPONSE` is
+    /// the number stepping starts from, and is used for an empty
+    /// response. `None` is returned if no number was given.
+    pub fn ask(&mut self) -> Option<i64> {
+        if self.question.keys && self.question.overridden().is_none() {
+            return self.step_interactive().unwrap_or(None);
+        }
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let question = self.question.question.clone();
+        self.question.question = format!("{} [{}-{}]", question, start, end);
+        let (range, step) = (self.range.clone(), self.step);
+        let answer = self.question.ask_parsed(|response| match response.parse() {
+            Ok(value) if accepts(&range, step, value) => Ok(value),
+            _ if step > 1 => Err(format!(
+                "Please enter a whole number from {} to {} in steps of {}",
+                start, end, step
+            )),
+            _ => Err(format!(
+                "Please enter a whole number from {} to {}",
+                start, end
+            )),
+        });
+        self.question.question = question;
+        answer
+    }
+
+    /// Step the number with the arrow keys on an interactive
+    /// terminal.
+    fn step_interactive(&mut self) -> Result<Option<i64>, std::io::Error> {
+        self.question.resolve_default();
+        let start = *self.range.start();
+        let mut value = match self.question.default {
+            Some(Answer::RESPONSE(ref default)) => default.trim().parse().unwrap_or(start),
+            _ => start,
+        };
+        let last = last_step(&self.range, self.step);
+        value = on_step(&self.range, self.step, value.clamp(start, last));
+        let mut typed = String::new();
+        let raw = RawMode::enable()?;
+        loop {
+            let shown = if typed.is_empty() {
+                value.to_string()
+            } else {
+                typed.clone()
+            };
+            let frame = format!(
+                "\r\x1b[2K{} \x1b[7m {} \x1b[0m",
+                self.question.question_text(),
+                shown
+            );
+            self.question.show(&frame)?;
+            match term::read_key(&mut self.question.reader)? {
+                Some(Key::Up) | Some(Key::Right) => {
+                    value = value.saturating_add(self.step).min(last);
+                    typed.clear();
+                }
+                Some(Key::Down) | Some(Key::Left) => {
+                    value = value.saturating_sub(self.step).max(start);
+                    typed.clear();
+                }
+                Some(Key::Home) => {
+                    value = start;
+                    typed.clear();
+                }
+                Some(Key::End) => {
+                    value = last;
+                    typed.clear();
+                }
+                Some(Key::Char(c)) if c.is_ascii_digit() || (c == '-' && typed.is_empty()) => {
+                    typed.push(c);
+                }
+                Some(Key::Backspace) => {
+                    typed.pop();
+                }
+                Some(Key::Esc) => typed.clear(),
+                Some(Key::Enter) => match typed.parse() {
+                    Ok(number) if !accepts(&self.range, self.step, number) => typed.clear(),
+                    result => {
+                        self.question.show_text("\n")?;
+                        return Ok(Some(result.unwrap_or(value)));
+                    }
+                },
+                Some(Key::Interrupt) => {
+                    self.question.show_text("\n")?;
+                    raw.interrupt();
+                    return Ok(None);
+                }
+                Some(Key::Eof) | None => {
+                    self.question.show_text("\n")?;
+                    return Ok(None);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+/// Whether `value` is in `range` and a whole number of `step`s
+/// from its start.
+fn accepts(range: &RangeInclusive<i64>, step: i64, value: i64) -> bool {
+    range.contains(&value) && offset(range, value) % i128::from(step) == 0
+}
+
+/// The last value in `range` a whole number of `step`s from its
+/// start, or the start if the range is empty.
+fn last_step(range: &RangeInclusive<i64>, step: i64) -> i64 {
+    on_step(range, step, (*range.end()).max(*range.start()))
+}
+
+/// `value`, from the start of `range` on, rounded down to a whole
+/// number of `step`s from the start.
+fn on_step(range: &RangeInclusive<i64>, step: i64, value: i64) -> i64 {
+    let step = i128::from(step);
+    (i128::from(*range.start()) + offset(range, value) / step * step) as i64
+}
+
+/// How far `value` is from the start of `range`, which may not fit
+/// in an `i64`.
+fn offset(range: &RangeInclusive<i64>, value: i64) -> i128 {
+    i128::from(value) - i128::from(*range.start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use {Answer, Question};
+
+    #[test]
+    fn stepper() {
+        macro_rules! stepper {
+            ($i:expr, $keys:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Workers?", input, Vec::new());
+                q.keys = $keys;
+                q.tries(2).default(Answer::RESPONSE(String::from("4")));
+                let actual = q.stepper(2..=8).step(2).ask();
+                assert_eq!($expected, actual);
+                assert_eq!("Workers?", q.question);
+            };
+        }
+        stepper!(b"6\n", false, Some(6));
+        stepper!(b"\n", false, Some(4));
+        stepper!(b"5\n9\n", false, None);
+        stepper!(b"5\n8\n", false, Some(8));
+        stepper!(b"\r", true, Some(4));
+        stepper!(b"\x1b[A\x1b[A\r", true, Some(8));
+        stepper!(b"\x1b[A\x1b[A\x1b[A\r", true, Some(8));
+        stepper!(b"\x1b[B\x1b[B\r", true, Some(2));
+        stepper!(b"6\r", true, Some(6));
+        stepper!(b"5\r\x1b[A\r", true, Some(6));
+        stepper!(b"\x1b[A", true, None);
+
+        let input = Cursor::new(b"0\n3\n".to_vec());
+        let mut output = Cursor::new(Vec::new());
+        {
+            let mut q = Question::with_cursor("Workers?", input, &mut output);
+            assert_eq!(Some(3), q.until_acceptable().stepper(1..=8).ask());
+        }
+        let output = String::from_utf8(output.into_inner()).expect("Not UTF-8");
+        assert_eq!(
+            "Workers? [1-8] Please enter a whole number from 1 to 8\nWorkers? [1-8] ",
+            output
+        );
+
+        let input = Cursor::new(b"\x1b[A\r".to_vec());
+        let mut q = Question::with_cursor("Workers?", input, Vec::new());
+        q.keys = true;
+        assert_eq!(Some(2), q.stepper(1..=8).ask());
+        let output = String::from_utf8(q.writer).expect("Not UTF-8");
+        assert!(output.ends_with("\r\x1b[2KWorkers? \x1b[7m 2 \x1b[0m\n"));
+
+        macro_rules! off_grid {
+            ($i:expr, $default:expr, $expected:expr) => {
+                let input = Cursor::new($i.to_vec());
+                let mut q = Question::with_cursor("Workers?", input, Vec::new());
+                q.keys = true;
+                q.default(Answer::RESPONSE(String::from($default)));
+                assert_eq!(Some($expected), q.stepper(1..=8).step(3).ask());
+            };
+        }
+        off_grid!(b"\x1b[A\x1b[A\x1b[A\r", "1", 7);
+        off_grid!(b"\x1b[F\r", "1", 7);
+        off_grid!(b"\r", "6", 4);
+        off_grid!(b"\r", "9", 7);
+    }
+
+    #[test]
+    fn accepts_whole_range() {
+        let range = i64::MIN..=i64::MAX;
+        assert!(accepts(&range, 1, i64::MAX));
+        assert!(!accepts(&range, 2, i64::MAX));
+        assert_eq!(i64::MAX - 1, last_step(&range, 2));
+    }
+}
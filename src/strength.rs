@@ -0,0 +1,189 @@
+//! Estimating how hard a passphrase would be to guess.
+
+use std::fmt;
+use std::io::{Read, Write};
+
+use Question;
+
+/// Passphrases too common to be worth guessing at all.
+const COMMON: &[&str] = &[
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "password",
+    "password1",
+    "qwerty",
+    "qwerty123",
+    "abc123",
+    "letmein",
+    "welcome",
+    "iloveyou",
+    "admin",
+    "monkey",
+    "dragon",
+    "football",
+    "baseball",
+    "sunshine",
+    "princess",
+    "trustno1",
+    "passw0rd",
+];
+
+/// How hard a passphrase would be to guess, from `VeryWeak` to
+/// `VeryStrong`.
+///
+/// The estimate counts the characters used from each class, such
+/// as lower case letters, digits and symbols, discounting repeated
+/// and sequential characters. It is a rough guide rather than a
+/// full analysis like zxcvbn.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Strength {
+    /// Common, or guessed almost immediately.
+    VeryWeak,
+    /// Guessed quickly by an offline attack.
+    Weak,
+    /// Good enough against online attacks.
+    Fair,
+    /// Good enough against most offline attacks.
+    Strong,
+    /// Far beyond any practical attack.
+    VeryStrong,
+}
+
+impl Strength {
+    /// Estimate the strength of `passphrase`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use question::Strength;
+    /// assert_eq!(Strength::VeryWeak, Strength::of("password1"));
+    /// assert!(Strength::of("correct horse battery staple") >= Strength::Strong);
+    /// ```
+    pub fn of(passphrase: &str) -> Strength {
+        if COMMON.iter().any(|c| c.eq_ignore_ascii_case(passphrase)) {
+            return Strength::VeryWeak;
+        }
+        let bits = entropy(passphrase);
+        match bits as u32 {
+            0..=29 => Strength::VeryWeak,
+            30..=49 => Strength::Weak,
+            50..=69 => Strength::Fair,
+            70..=119 => Strength::Strong,
+            _ => Strength::VeryStrong,
+        }
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match *self {
+            Strength::VeryWeak => "very weak",
+            Strength::Weak => "weak",
+            Strength::Fair => "fair",
+            Strength::Strong => "strong",
+            Strength::VeryStrong => "very strong",
+        };
+        f.write_str(text)
+    }
+}
+
+/// The bits of entropy in `passphrase`, counting each character
+/// as a choice from the classes it uses, but repeated characters
+/// and runs like "abc" or "321" as half a character each.
+fn entropy(passphrase: &str) -> f64 {
+    let mut pool = 0;
+    let chars: Vec<char> = passphrase.chars().collect();
+    if chars.iter().any(char::is_ascii_lowercase) {
+        pool += 26;
+    }
+    if chars.iter().any(char::is_ascii_uppercase) {
+        pool += 26;
+    }
+    if chars.iter().any(char::is_ascii_digit) {
+        pool += 10;
+    }
+    if chars
+        .iter()
+        .any(|c| c.is_ascii() && !c.is_ascii_alphanumeric())
+    {
+        pool += 33;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        pool += 100;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    let mut length = 0.0;
+    for (i, &c) in chars.iter().enumerate() {
+        let predictable = i > 0 && {
+            let step = c as i64 - chars[i - 1] as i64;
+            (-1..=1).contains(&step)
+        };
+        length += if predictable { 0.5 } else { 1.0 };
+    }
+    length * f64::from(pool).log2()
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Reject responses estimated to be weaker than `strength`,
+    /// asking again with how strong the response was. Meant for
+    /// choosing a new `secret`, such as a passphrase. The question
+    /// is asked until a strong enough response is given, or as set
+    /// by `tries`. See `Strength` for how strength is estimated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Question, Strength};
+    /// Question::new("New passphrase?")
+    ///     .min_strength(Strength::Strong)
+    ///     .ask_secret();
+    /// ```
+    pub fn min_strength(&mut self, strength: Strength) -> &mut Question<R, W> {
+        self.min_strength = Some(strength);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn strength() {
+        assert_eq!(Strength::VeryWeak, Strength::of(""));
+        assert_eq!(Strength::VeryWeak, Strength::of("Password"));
+        assert_eq!(Strength::VeryWeak, Strength::of("aaaaaaaa"));
+        assert_eq!(Strength::Weak, Strength::of("hunter2"));
+        assert_eq!(Strength::Fair, Strength::of("Tr0ub4dor"));
+        assert_eq!(Strength::Strong, Strength::of("correct horse battery"));
+        assert_eq!(
+            Strength::VeryStrong,
+            Strength::of("correct horse battery staple")
+        );
+        assert_eq!("very weak", Strength::VeryWeak.to_string());
+    }
+
+    #[test]
+    fn min_strength() {
+        let input = Cursor::new(String::from("hunter2\ncorrect horse battery\n").into_bytes());
+        let mut output = Vec::new();
+        let secret = Question::with_cursor("Passphrase?", input, &mut output)
+            .min_strength(Strength::Strong)
+            .ask_secret()
+            .unwrap();
+        assert_eq!("correct horse battery", secret.expose());
+        let expected = "Passphrase? That passphrase is weak, please choose a stronger one\n\
+                        Passphrase? ";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,69 @@
+//! Writing a question's output to two places at once.
+
+use std::io::{self, Write};
+
+/// A writer that writes everything to two writers, such as the
+/// terminal and an audit log, for `Question::write_to`.
+///
+/// Each write goes to `first` and then to `second`, and fails if
+/// either does.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Question, Tee};
+/// # use std::fs::File;
+/// let log = File::create("audit.log").unwrap();
+/// Question::new("Apply the migration?")
+///     .write_to(Tee::new(std::io::stdout(), log))
+///     .confirm();
+/// ```
+#[derive(Debug)]
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Write, B: Write> Tee<A, B> {
+    /// Create a writer writing to both `first` and `second`.
+    pub fn new(first: A, second: B) -> Tee<A, B> {
+        Tee { first, second }
+    }
+
+    /// Take back the two writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.first.write_all(buf)?;
+        self.second.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use Question;
+
+    #[test]
+    fn tee() {
+        let input = Cursor::new(b"maybe\ny\n".to_vec());
+        let output = Tee::new(Vec::new(), Vec::new());
+        let mut q = Question::with_cursor("Continue?", input, output);
+        q.yes_no().until_acceptable();
+        q.ask();
+        let (terminal, log) = q.writer.into_inner();
+        assert_eq!(terminal, log);
+        assert_eq!("Continue? Continue? ", String::from_utf8(log).unwrap());
+    }
+}
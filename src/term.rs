@@ -0,0 +1,457 @@
+//! Keystroke level terminal input.
+//!
+//! Interactive widgets read individual keys rather than whole
+//! lines. Decoding works on any `Read` so it can be exercised
+//! with a `Cursor`, while switching the real terminal into raw
+//! mode requires the `term` feature. Unix terminals are driven
+//! through termios, and Windows consoles, both conhost and Windows
+//! Terminal, through the console API with virtual terminal input,
+//! so keys arrive as the same escape sequences.
+
+use std::io::{self, Read};
+use std::time::Duration;
+
+#[cfg(all(feature = "term", windows))]
+use windows_sys::Win32::Foundation::{HANDLE, INVALID_HANDLE_VALUE, WAIT_OBJECT_0, WAIT_TIMEOUT};
+#[cfg(all(feature = "term", windows))]
+use windows_sys::Win32::System::Console::{
+    GetConsoleMode, GetStdHandle, CONSOLE_MODE, STD_INPUT_HANDLE, STD_OUTPUT_HANDLE,
+};
+
+/// A single decoded keypress.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub(crate) enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Tab,
+    Esc,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Delete,
+    Interrupt,
+    Eof,
+    Unknown,
+}
+
+/// Read one key from `reader`, returning `None` at end of input.
+pub(crate) fn read_key<R: Read>(reader: &mut R) -> io::Result<Option<Key>> {
+    let first = match read_byte(reader)? {
+        Some(byte) => byte,
+        None => return Ok(None),
+    };
+    let key = match first {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x03 => Key::Interrupt,
+        0x04 => Key::Eof,
+        0x05 => Key::End,
+        0x1b => read_escape(reader)?,
+        byte if byte < 0x20 => Key::Unknown,
+        byte => read_char(reader, byte)?,
+    };
+    Ok(Some(key))
+}
+
+fn read_escape<R: Read>(reader: &mut R) -> io::Result<Key> {
+    match read_byte(reader)? {
+        Some(b'[') | Some(b'O') => {}
+        Some(0x1b) | None => return Ok(Key::Esc),
+        Some(_) => return Ok(Key::Unknown),
+    }
+    // Collect any parameters, the final byte identifies the key.
+    let mut parameters = Vec::new();
+    loop {
+        match read_byte(reader)? {
+            Some(b'A') => return Ok(Key::Up),
+            Some(b'B') => return Ok(Key::Down),
+            Some(b'C') => return Ok(Key::Right),
+            Some(b'D') => return Ok(Key::Left),
+            Some(b'H') => return Ok(Key::Home),
+            Some(b'F') => return Ok(Key::End),
+            Some(b'~') => {
+                return Ok(match &parameters[..] {
+                    b"1" | b"7" => Key::Home,
+                    b"4" | b"8" => Key::End,
+                    b"3" => Key::Delete,
+                    _ => Key::Unknown,
+                })
+            }
+            Some(byte) if (0x40..0x7f).contains(&byte) => return Ok(Key::Unknown),
+            Some(byte) => parameters.push(byte),
+            None => return Ok(Key::Esc),
+        }
+    }
+}
+
+fn read_char<R: Read>(reader: &mut R, first: u8) -> io::Result<Key> {
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Ok(Key::Unknown),
+    };
+    let mut bytes = vec![first];
+    while bytes.len() < len {
+        match read_byte(reader)? {
+            Some(byte) => bytes.push(byte),
+            None => return Ok(Key::Unknown),
+        }
+    }
+    match std::str::from_utf8(&bytes) {
+        Ok(s) => Ok(s.chars().next().map(Key::Char).unwrap_or(Key::Unknown)),
+        Err(_) => Ok(Key::Unknown),
+    }
+}
+
+fn read_byte<R: Read>(reader: &mut R) -> io::Result<Option<u8>> {
+    let mut byte = [0; 1];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Whether standard input is a terminal, even one that cannot be
+/// driven one keystroke at a time or without the `term` feature.
+pub(crate) fn stdin_is_terminal() -> bool {
+    use std::io::IsTerminal;
+    io::stdin().is_terminal()
+}
+
+/// Whether the process is attached to a terminal that can be
+/// driven one keystroke at a time.
+pub(crate) fn is_interactive() -> bool {
+    #[cfg(all(feature = "term", unix))]
+    {
+        use std::io::IsTerminal;
+        let dumb = match std::env::var("TERM") {
+            Ok(term) => term == "dumb",
+            Err(_) => true,
+        };
+        !dumb && io::stdin().is_terminal() && io::stdout().is_terminal()
+    }
+    #[cfg(all(feature = "term", windows))]
+    {
+        console_mode(STD_INPUT_HANDLE).is_some() && console_mode(STD_OUTPUT_HANDLE).is_some()
+    }
+    #[cfg(not(all(feature = "term", any(unix, windows))))]
+    {
+        false
+    }
+}
+
+/// The standard handle `which` and its console mode, if it is a
+/// console.
+#[cfg(all(feature = "term", windows))]
+fn console_mode(which: u32) -> Option<(HANDLE, CONSOLE_MODE)> {
+    unsafe {
+        let handle = GetStdHandle(which);
+        if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            return None;
+        }
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return None;
+        }
+        Some((handle, mode))
+    }
+}
+
+/// The width of the terminal standard output is written to, in
+/// columns, if it is one.
+pub(crate) fn width() -> Option<usize> {
+    #[cfg(all(feature = "term", unix))]
+    {
+        unsafe {
+            let mut size: ::libc::winsize = std::mem::zeroed();
+            if ::libc::ioctl(::libc::STDOUT_FILENO, ::libc::TIOCGWINSZ, &mut size) != 0 {
+                return None;
+            }
+            if size.ws_col == 0 {
+                return None;
+            }
+            Some(usize::from(size.ws_col))
+        }
+    }
+    #[cfg(all(feature = "term", windows))]
+    {
+        use windows_sys::Win32::System::Console::{
+            GetConsoleScreenBufferInfo, CONSOLE_SCREEN_BUFFER_INFO,
+        };
+        let (handle, _) = console_mode(STD_OUTPUT_HANDLE)?;
+        unsafe {
+            let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+            if GetConsoleScreenBufferInfo(handle, &mut info) == 0 {
+                return None;
+            }
+            let columns = i32::from(info.srWindow.Right) - i32::from(info.srWindow.Left) + 1;
+            usize::try_from(columns).ok().filter(|&columns| columns > 0)
+        }
+    }
+    #[cfg(not(all(feature = "term", any(unix, windows))))]
+    {
+        None
+    }
+}
+
+/// Wait up to `timeout` for standard input to have something to
+/// read, returning whether it does. Without the `term` feature,
+/// or when standard input is not a terminal, input is assumed to
+/// be ready straight away.
+pub(crate) fn poll_input(timeout: Duration) -> io::Result<bool> {
+    #[cfg(all(feature = "term", unix))]
+    unsafe {
+        if ::libc::isatty(::libc::STDIN_FILENO) != 1 {
+            return Ok(true);
+        }
+        let mut fds = ::libc::pollfd {
+            fd: ::libc::STDIN_FILENO,
+            events: ::libc::POLLIN,
+            revents: 0,
+        };
+        let millis = whole_millis(timeout).min(i32::MAX as u128) as ::libc::c_int;
+        match ::libc::poll(&mut fds, 1, millis) {
+            -1 => Err(io::Error::last_os_error()),
+            0 => Ok(false),
+            _ => Ok(true),
+        }
+    }
+    #[cfg(all(feature = "term", windows))]
+    {
+        use windows_sys::Win32::System::Threading::WaitForSingleObject;
+        let (handle, _) = match console_mode(STD_INPUT_HANDLE) {
+            Some(console) => console,
+            None => return Ok(true),
+        };
+        let until = std::time::Instant::now() + timeout;
+        loop {
+            let left = until.saturating_duration_since(std::time::Instant::now());
+            let millis = whole_millis(left).min(u128::from(u32::MAX - 1)) as u32;
+            // The handle is also signalled by focus, mouse and resize
+            // events, and by keys released, none of which can be read.
+            match unsafe { WaitForSingleObject(handle, millis) } {
+                WAIT_OBJECT_0 if key_waiting(handle)? => return Ok(true),
+                WAIT_OBJECT_0 if left.is_zero() => return Ok(false),
+                WAIT_OBJECT_0 => {}
+                WAIT_TIMEOUT => return Ok(false),
+                _ => return Err(io::Error::last_os_error()),
+            }
+        }
+    }
+    #[cfg(not(all(feature = "term", any(unix, windows))))]
+    {
+        let _ = timeout;
+        Ok(true)
+    }
+}
+
+/// Whether the next console input event is a key pressed that
+/// gives a character to read, discarding it if it is not.
+#[cfg(all(feature = "term", windows))]
+fn key_waiting(handle: HANDLE) -> io::Result<bool> {
+    use windows_sys::Win32::System::Console::{
+        PeekConsoleInputW, ReadConsoleInputW, INPUT_RECORD, KEY_EVENT,
+    };
+    unsafe {
+        let mut record: INPUT_RECORD = std::mem::zeroed();
+        let mut count = 0;
+        if PeekConsoleInputW(handle, &mut record, 1, &mut count) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if count == 0 {
+            return Ok(false);
+        }
+        if u32::from(record.EventType) == KEY_EVENT {
+            let key = record.Event.KeyEvent;
+            if key.bKeyDown != 0 && key.uChar.UnicodeChar != 0 {
+                return Ok(true);
+            }
+        }
+        if ReadConsoleInputW(handle, &mut record, 1, &mut count) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(false)
+    }
+}
+
+/// `timeout` in milliseconds, rounded up, so a wait shorter than a
+/// millisecond is not a wait of none.
+#[cfg(all(feature = "term", any(unix, windows)))]
+fn whole_millis(timeout: Duration) -> u128 {
+    timeout.as_nanos().div_ceil(1_000_000)
+}
+
+/// Puts the terminal into raw mode for as long as it is alive.
+///
+/// When standard input is not a terminal, or the `term` feature
+/// is disabled, this does nothing.
+pub(crate) struct RawMode {
+    #[cfg(all(feature = "term", unix))]
+    original: Option<::libc::termios>,
+    /// The console handles and modes of standard input and output.
+    #[cfg(all(feature = "term", windows))]
+    original: Option<[(HANDLE, CONSOLE_MODE); 2]>,
+}
+
+impl RawMode {
+    #[cfg(all(feature = "term", unix))]
+    pub(crate) fn enable() -> io::Result<RawMode> {
+        use libc::{ECHO, ICANON, ICRNL, IEXTEN, ISIG, IXON, STDIN_FILENO, TCSANOW, VMIN, VTIME};
+        unsafe {
+            if ::libc::isatty(STDIN_FILENO) != 1 {
+                return Ok(RawMode { original: None });
+            }
+            let mut termios: ::libc::termios = std::mem::zeroed();
+            if ::libc::tcgetattr(STDIN_FILENO, &mut termios) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let original = termios;
+            termios.c_lflag &= !(ICANON | ECHO | ISIG | IEXTEN);
+            termios.c_iflag &= !(IXON | ICRNL);
+            termios.c_cc[VMIN] = 1;
+            termios.c_cc[VTIME] = 0;
+            if ::libc::tcsetattr(STDIN_FILENO, TCSANOW, &termios) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(RawMode {
+                original: Some(original),
+            })
+        }
+    }
+
+    #[cfg(all(feature = "term", windows))]
+    pub(crate) fn enable() -> io::Result<RawMode> {
+        use windows_sys::Win32::System::Console::{
+            SetConsoleMode, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT,
+            ENABLE_VIRTUAL_TERMINAL_INPUT, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        };
+        let (input, output) = match (
+            console_mode(STD_INPUT_HANDLE),
+            console_mode(STD_OUTPUT_HANDLE),
+        ) {
+            (Some(input), Some(output)) => (input, output),
+            _ => return Ok(RawMode { original: None }),
+        };
+        let raw_input = input.1 & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT);
+        unsafe {
+            // Consoles older than Windows 10 have no virtual terminal
+            // support, so only characters, not arrow keys, arrive.
+            if SetConsoleMode(input.0, raw_input | ENABLE_VIRTUAL_TERMINAL_INPUT) == 0
+                && SetConsoleMode(input.0, raw_input) == 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            SetConsoleMode(output.0, output.1 | ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+        }
+        Ok(RawMode {
+            original: Some([input, output]),
+        })
+    }
+
+    #[cfg(not(all(feature = "term", any(unix, windows))))]
+    pub(crate) fn enable() -> io::Result<RawMode> {
+        Ok(RawMode {})
+    }
+
+    /// Restore the terminal and deliver the interrupt that raw mode
+    /// swallowed, so Ctrl-C behaves as it would outside a prompt.
+    pub(crate) fn interrupt(mut self) {
+        if self.restore() {
+            raise_interrupt();
+        }
+    }
+
+    /// Restore the original terminal settings, returning whether
+    /// raw mode had actually been enabled.
+    fn restore(&mut self) -> bool {
+        #[cfg(all(feature = "term", unix))]
+        {
+            if let Some(original) = self.original.take() {
+                unsafe {
+                    ::libc::tcsetattr(::libc::STDIN_FILENO, ::libc::TCSANOW, &original);
+                }
+                return true;
+            }
+        }
+        #[cfg(all(feature = "term", windows))]
+        {
+            use windows_sys::Win32::System::Console::SetConsoleMode;
+            if let Some(original) = self.original.take() {
+                for (handle, mode) in original {
+                    unsafe {
+                        SetConsoleMode(handle, mode);
+                    }
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(all(feature = "term", unix))]
+fn raise_interrupt() {
+    unsafe {
+        ::libc::raise(::libc::SIGINT);
+    }
+}
+
+#[cfg(all(feature = "term", windows))]
+fn raise_interrupt() {
+    use windows_sys::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_C_EVENT};
+    unsafe {
+        GenerateConsoleCtrlEvent(CTRL_C_EVENT, 0);
+    }
+}
+
+#[cfg(not(all(feature = "term", any(unix, windows))))]
+fn raise_interrupt() {}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn decode_keys() {
+        macro_rules! keys {
+            ($input:expr, $expected:expr) => {
+                let mut input = Cursor::new($input.to_vec());
+                let mut actual = Vec::new();
+                while let Some(key) = read_key(&mut input).unwrap() {
+                    actual.push(key);
+                }
+                assert_eq!($expected.to_vec(), actual);
+            };
+        }
+        keys!(b"y\r", [Key::Char('y'), Key::Enter]);
+        keys!(b"\x1b[C\x1b[D", [Key::Right, Key::Left]);
+        keys!(b"\x1bOA\x1b[1;5B", [Key::Up, Key::Down]);
+        keys!(
+            b"\x7f\t\x03\n",
+            [Key::Backspace, Key::Tab, Key::Interrupt, Key::Enter]
+        );
+        keys!("é".as_bytes(), [Key::Char('é')]);
+        keys!(b"\x1b", [Key::Esc]);
+        keys!(b"\x1b[H\x1b[4~\x1b[3~", [Key::Home, Key::End, Key::Delete]);
+        keys!(b"\x01\x05\x04", [Key::Home, Key::End, Key::Eof]);
+    }
+}
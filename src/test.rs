@@ -0,0 +1,149 @@
+//! Helpers for testing code that asks questions.
+//!
+//! A `Script` is a `Prompter` replaying a list of responses and
+//! recording every prompt it is asked to show, so code built on
+//! this crate can be tested without a terminal.
+//!
+//! # Examples
+//!
+//! ```
+//! # use question::{Answer, Question};
+//! use question::test::Script;
+//!
+//! let script = Script::new(&["maybe", "y"]);
+//! let answer = Question::new("Continue?")
+//!     .clarification("Please enter yes or no")
+//!     .prompter(script.clone())
+//!     .confirm();
+//! assert_eq!(Answer::YES, answer);
+//! script.assert_prompts(&["Continue? ", "Please enter yes or no\nContinue? "]);
+//! script.assert_finished();
+//! ```
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use {Prompt, Prompter};
+
+/// A `Prompter` answering with scripted responses, in order, and
+/// recording what it was shown. Copies share the same script, so
+/// one can be given to a question and inspected afterwards.
+///
+/// Once the responses run out input ends, as it would if the
+/// user pressed Ctrl-D.
+#[derive(Clone, Debug, Default)]
+pub struct Script {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: VecDeque<String>,
+    prompts: Vec<String>,
+    output: String,
+}
+
+impl Script {
+    /// Create a script giving `responses` in order.
+    pub fn new<S: AsRef<str>>(responses: &[S]) -> Script {
+        let script = Script::default();
+        for response in responses {
+            script.respond(response.as_ref());
+        }
+        script
+    }
+
+    /// Add `response` to the end of the script.
+    pub fn respond(&self, response: &str) -> &Script {
+        self.state().responses.push_back(response.to_string());
+        self
+    }
+
+    /// The prompts shown so far, in order.
+    pub fn prompts(&self) -> Vec<String> {
+        self.state().prompts.clone()
+    }
+
+    /// Everything shown so far, prompts and other text alike, as
+    /// it would have been written to a terminal.
+    pub fn output(&self) -> String {
+        self.state().output.clone()
+    }
+
+    /// The responses not yet given.
+    pub fn remaining(&self) -> Vec<String> {
+        self.state().responses.iter().cloned().collect()
+    }
+
+    /// Panic unless the prompts shown so far are `expected`.
+    pub fn assert_prompts(&self, expected: &[&str]) {
+        let prompts = self.prompts();
+        assert_eq!(expected, &prompts[..], "prompts shown do not match");
+    }
+
+    /// Panic unless every response has been given.
+    pub fn assert_finished(&self) {
+        let remaining = self.remaining();
+        assert!(
+            remaining.is_empty(),
+            "responses were not given: {:?}",
+            remaining
+        );
+    }
+
+    fn state(&self) -> ::std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Prompter for Script {
+    fn prompt(&mut self, prompt: &Prompt) -> io::Result<Option<String>> {
+        let mut state = self.state();
+        state.prompts.push(prompt.text().to_string());
+        state.output.push_str(prompt.text());
+        let response = state.responses.pop_front();
+        if let Some(ref response) = response {
+            state.output.push_str(response);
+            state.output.push('\n');
+        }
+        Ok(response)
+    }
+
+    fn show(&mut self, text: &str) -> io::Result<()> {
+        self.state().output.push_str(text);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {Answer, Question};
+
+    #[test]
+    fn script() {
+        let script = Script::new(&["", "8080", "extra"]);
+        let port = Question::new("Port?")
+            .prompter(script.clone())
+            .until_acceptable()
+            .ask_as::<u16>();
+        assert_eq!(Some(8080), port);
+        assert_eq!(vec!["extra"], script.remaining());
+        assert_eq!(2, script.prompts().len());
+
+        let script = Script::new(&["2"]);
+        let env = Question::new("Env?")
+            .prompter(script.clone())
+            .select(&["dev", "prod"])
+            .ask();
+        assert_eq!(Some(String::from("prod")), env);
+        assert_eq!("  1) dev\n  2) prod\nEnv? 2\n", script.output());
+        script.assert_finished();
+
+        let script = Script::new::<&str>(&[]);
+        let answer = Question::new("Name?").prompter(script.clone()).ask();
+        assert_eq!(Some(Answer::RESPONSE(String::new())), answer);
+        script.assert_prompts(&["Name? "]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Colors and emphasis for the parts of a prompt, enabled with
+//! the `color` feature.
+
+use std::io::{Read, Write};
+
+use Question;
+
+/// One of the eight standard terminal colors.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+/// How a piece of text is displayed.
+///
+/// # Examples
+///
+/// ```
+/// # use question::{Color, Style};
+/// let style = Style::new().foreground(Color::Red).bold();
+/// assert_eq!("\x1b[1;31mNo\x1b[0m", style.paint("No"));
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct Style {
+    foreground: Option<Color>,
+    bold: bool,
+    dim: bool,
+}
+
+impl Style {
+    /// Create a plain `Style`, displaying text as is.
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Display text in `color`.
+    pub fn foreground(mut self, color: Color) -> Style {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Display text in bold.
+    pub fn bold(mut self) -> Style {
+        self.bold = true;
+        self
+    }
+
+    /// Display text dimmed.
+    pub fn dim(mut self) -> Style {
+        self.dim = true;
+        self
+    }
+
+    /// Wrap `text` in the escape sequences for this style.
+    pub fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push(String::from("1"));
+        }
+        if self.dim {
+            codes.push(String::from("2"));
+        }
+        if let Some(color) = self.foreground {
+            codes.push((30 + color as u8).to_string());
+        }
+        if codes.is_empty() || text.is_empty() {
+            return text.to_string();
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// The styles of each part of a prompt.
+///
+/// The default theme shows the question in bold, the default
+/// hint dimmed and clarifications and other errors in red.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Color, Question, Style, Theme};
+/// let mut theme = Theme::default();
+/// theme.question = Style::new().foreground(Color::Cyan).bold();
+/// Question::new("Continue?")
+///     .yes_no()
+///     .show_defaults()
+///     .theme(theme)
+///     .confirm();
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Theme {
+    /// The question itself.
+    pub question: Style,
+    /// The default hint, such as "(Y/n)".
+    pub hint: Style,
+    /// Clarifications and reasons a response was not accepted.
+    pub error: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            question: Style::new().bold(),
+            hint: Style::new().dim(),
+            error: Style::new().foreground(Color::Red),
+        }
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Style the prompt with `theme`. Styles are only shown when
+    /// standard output is a terminal and the `NO_COLOR`
+    /// environment variable is not set, so piped output stays
+    /// plain text.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::{Question, Theme};
+    /// Question::new("Continue?").theme(Theme::default()).confirm();
+    /// ```
+    pub fn theme(&mut self, theme: Theme) -> &mut Question<R, W> {
+        self.theme = Some(theme);
+        self
+    }
+}
+
+/// Whether standard output should be styled.
+pub(crate) fn supports_color() -> bool {
+    use std::io::IsTerminal;
+    let dumb = match std::env::var("TERM") {
+        Ok(term) => term == "dumb",
+        Err(_) => !cfg!(windows),
+    };
+    !dumb && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paint() {
+        assert_eq!("text", Style::new().paint("text"));
+        assert_eq!("\x1b[1mtext\x1b[0m", Style::new().bold().paint("text"));
+        assert_eq!(
+            "\x1b[2;36mtext\x1b[0m",
+            Style::new().dim().foreground(Color::Cyan).paint("text")
+        );
+        assert_eq!("", Style::new().bold().paint(""));
+    }
+}
@@ -0,0 +1,265 @@
+//! Recording what was asked and answered, for audit trails.
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+use json::Json;
+use Question;
+
+/// What is recorded in place of the responses to questions marked
+/// with `Question::secret`.
+pub const REDACTED: &str = "[redacted]";
+
+/// Something that happened while asking a question.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// The prompt `text` was shown for `question`.
+    Prompt {
+        /// The question as it was written.
+        question: String,
+        /// The prompt as it was shown, with any hints.
+        text: String,
+    },
+
+    /// A response was given to `question`, or `None` if input
+    /// ended first. Responses to secret questions are `REDACTED`.
+    Response {
+        /// The question as it was written.
+        question: String,
+        /// The response, trimmed.
+        response: Option<String>,
+    },
+}
+
+impl Event {
+    fn to_json(&self) -> Json {
+        let string = |s: &str| Json::String(s.to_string());
+        match *self {
+            Event::Prompt {
+                ref question,
+                ref text,
+            } => Json::Object(vec![
+                (String::from("type"), string("prompt")),
+                (String::from("question"), string(question)),
+                (String::from("text"), string(text)),
+            ]),
+            Event::Response {
+                ref question,
+                ref response,
+            } => Json::Object(vec![
+                (String::from("type"), string("response")),
+                (String::from("question"), string(question)),
+                (
+                    String::from("response"),
+                    response.as_ref().map_or(Json::Null, |r| string(r)),
+                ),
+            ]),
+        }
+    }
+}
+
+/// A record of every prompt shown and response given by the
+/// questions it is set on with `Question::transcript`, in order.
+///
+/// Copies of a transcript share the same record, so one can be
+/// set on many questions and read afterwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::{Question, Transcript};
+/// let transcript = Transcript::new();
+/// Question::new("Drop the production database?")
+///     .transcript(&transcript)
+///     .confirm();
+/// for event in transcript.events() {
+///     println!("{:?}", event);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Transcript {
+    events: Arc<Mutex<Vec<Event>>>,
+    writer: Option<Arc<Mutex<dyn Write + Send>>>,
+}
+
+impl Transcript {
+    /// Create an empty transcript.
+    pub fn new() -> Transcript {
+        Transcript::default()
+    }
+
+    /// Create a transcript that also writes each event to `writer`
+    /// as it happens, as a line of JSON such as
+    /// `{"type":"response","question":"Continue?","response":"y"}`.
+    /// Failing to write does not stop the question being asked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Transcript;
+    /// # use std::fs::File;
+    /// let log = File::create("audit.jsonl").unwrap();
+    /// let transcript = Transcript::to_writer(log);
+    /// ```
+    pub fn to_writer<W>(writer: W) -> Transcript
+    where
+        W: Write + Send + 'static,
+    {
+        Transcript {
+            events: Arc::default(),
+            writer: Some(Arc::new(Mutex::new(writer))),
+        }
+    }
+
+    /// The events recorded so far, oldest first.
+    pub fn events(&self) -> Vec<Event> {
+        self.events
+            .lock()
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record(&self, event: Event) {
+        if let Some(ref writer) = self.writer {
+            if let Ok(mut writer) = writer.lock() {
+                let _ = writeln!(writer, "{}", event.to_json().to_line());
+                let _ = writer.flush();
+            }
+        }
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+}
+
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Record every prompt shown and response given in
+    /// `transcript`. See `Transcript` for details.
+    pub fn transcript(&mut self, transcript: &Transcript) -> &mut Question<R, W> {
+        self.transcript = Some(transcript.clone());
+        self
+    }
+
+    /// Treat responses as secrets, such as passphrases: they are
+    /// not shown as they are typed on an interactive terminal (see
+    /// `toggle`), not kept in the history and recorded as
+    /// `REDACTED` in transcripts.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// let passphrase = Question::new("Vault passphrase:").secret().ask();
+    /// ```
+    pub fn secret(&mut self) -> &mut Question<R, W> {
+        self.secret = true;
+        self
+    }
+
+    /// Record that `text` was shown in the transcript, if any.
+    pub(crate) fn record_prompt(&self, text: &str) {
+        if let Some(ref transcript) = self.transcript {
+            transcript.record(Event::Prompt {
+                question: self.question.clone(),
+                text: text.to_string(),
+            });
+        }
+    }
+
+    /// Record `response`, or the end of input, in the transcript,
+    /// if any.
+    pub(crate) fn record_response(&self, response: Option<&str>) {
+        if let Some(ref transcript) = self.transcript {
+            let response = match response {
+                Some(_) if self.secret => Some(String::from(REDACTED)),
+                response => response.map(String::from),
+            };
+            transcript.record(Event::Response {
+                question: self.question.clone(),
+                response,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use Answer;
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transcript() {
+        let transcript = Transcript::new();
+        let input = Cursor::new(String::from("maybe\ny\n").into_bytes());
+        let answer = Question::with_cursor("Continue?", input, Vec::new())
+            .transcript(&transcript)
+            .confirm();
+        assert_eq!(Answer::YES, answer);
+        let input = Cursor::new(String::from("hunter2\n").into_bytes());
+        Question::with_cursor("Passphrase:", input, Vec::new())
+            .secret()
+            .transcript(&transcript)
+            .ask();
+        Question::with_cursor("Name?", Cursor::new(Vec::new()), Vec::new())
+            .transcript(&transcript)
+            .ask();
+
+        let prompt = |question: &str, text: &str| Event::Prompt {
+            question: question.to_string(),
+            text: text.to_string(),
+        };
+        let response = |question: &str, response: Option<&str>| Event::Response {
+            question: question.to_string(),
+            response: response.map(String::from),
+        };
+        assert_eq!(
+            vec![
+                prompt("Continue?", "Continue? "),
+                response("Continue?", Some("maybe")),
+                prompt("Continue?", "Continue? "),
+                response("Continue?", Some("y")),
+                prompt("Passphrase:", "Passphrase: "),
+                response("Passphrase:", Some(REDACTED)),
+                prompt("Name?", "Name? "),
+                response("Name?", None),
+            ],
+            transcript.events()
+        );
+    }
+
+    #[test]
+    fn writer() {
+        let log = Shared::default();
+        let transcript = Transcript::to_writer(log.clone());
+        let input = Cursor::new(String::from("blue\n").into_bytes());
+        Question::with_cursor("Color?", input, Vec::new())
+            .transcript(&transcript)
+            .ask();
+        let log = String::from_utf8(log.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            "{\"type\":\"prompt\",\"question\":\"Color?\",\"text\":\"Color? \"}\n\
+             {\"type\":\"response\",\"question\":\"Color?\",\"response\":\"blue\"}\n",
+            log
+        );
+        assert_eq!(2, transcript.events().len());
+    }
+}
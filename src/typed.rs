@@ -0,0 +1,120 @@
+use std::io::{Read, Write};
+
+use {Answer, Question};
+
+/// A `Question` whose acceptable responses map to values of
+/// any type `T`, so the answer can be used without matching
+/// on strings.
+///
+/// Created with `Question::typed`. Each mapped response is
+/// also added to the question's acceptable responses, so
+/// `until_acceptable` and `tries` work as usual.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use question::Question;
+/// #[derive(Clone)]
+/// enum Environment {
+///     Staging,
+///     Production,
+/// }
+///
+/// let environment = Question::new("Deploy to which environment?")
+///     .until_acceptable()
+///     .typed()
+///     .map_response("s", Environment::Staging)
+///     .map_response("staging", Environment::Staging)
+///     .map_response("p", Environment::Production)
+///     .map_response("production", Environment::Production)
+///     .ask();
+/// ```
+pub struct TypedQuestion<'a, T, R, W>
+where
+    T: Clone,
+    R: Read + 'a,
+    W: Write + 'a,
+{
+    question: &'a mut Question<R, W>,
+    values: Vec<(String, T)>,
+}
+
+impl<'a, T, R, W> TypedQuestion<'a, T, R, W>
+where
+    T: Clone,
+    R: Read,
+    W: Write,
+{
+    pub(crate) fn new(question: &'a mut Question<R, W>) -> TypedQuestion<'a, T, R, W> {
+        TypedQuestion {
+            question,
+            values: Vec::new(),
+        }
+    }
+
+    /// Map a response to the value returned when the user
+    /// enters it. Several responses may map to equal values.
+    pub fn map_response(&mut self, response: &str, value: T) -> &mut TypedQuestion<'a, T, R, W> {
+        self.question.accept(response);
+        self.values.push((response.to_string(), value));
+        self
+    }
+
+    /// Ask the question, returning the value mapped to the
+    /// user's response.
+    ///
+    /// A default set on the question with
+    /// `Answer::RESPONSE` is looked up like any other
+    /// response. `None` is returned if no mapped response
+    /// was given, such as when every try was used up or
+    /// the question was skipped.
+    pub fn ask(&mut self) -> Option<T> {
+        match self.question.ask() {
+            Some(Answer::RESPONSE(response)) => self.value(&response),
+            _ => None,
+        }
+    }
+
+    fn value(&self, response: &str) -> Option<T> {
+        self.values
+            .iter()
+            .find(|(key, _)| key == response)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Environment {
+        Staging,
+        Production,
+    }
+
+    #[test]
+    fn typed_ask() {
+        macro_rules! typed {
+            ($i:expr, $expected:expr) => {
+                let input = Cursor::new(String::from($i).into_bytes());
+                let output = Cursor::new(Vec::new());
+                let actual = Question::with_cursor("Environment?", input, output)
+                    .default(Answer::RESPONSE(String::from("s")))
+                    .tries(2)
+                    .typed()
+                    .map_response("s", Environment::Staging)
+                    .map_response("staging", Environment::Staging)
+                    .map_response("p", Environment::Production)
+                    .ask();
+                assert_eq!($expected, actual);
+            };
+        }
+        typed!("staging\n", Some(Environment::Staging));
+        typed!("P\n", Some(Environment::Production));
+        typed!("\n", Some(Environment::Staging));
+        typed!("dev\np\n", Some(Environment::Production));
+        typed!("dev\nqa\n", None);
+    }
+}
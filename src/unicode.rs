@@ -0,0 +1,185 @@
+//! Unicode aware matching and column counting, so responses typed
+//! through an input method match and wide characters line up.
+
+#[cfg(feature = "unicode")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "unicode")]
+use unicode_normalization::char::is_combining_mark;
+#[cfg(feature = "unicode")]
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "unicode")]
+use unicode_width::UnicodeWidthChar;
+
+#[cfg(feature = "unicode")]
+use Question;
+
+/// How responses and the responses they are matched against are
+/// normalized before comparing them.
+#[cfg(feature = "unicode")]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Normalization {
+    /// Canonical composition, so "é" matches whether it was typed
+    /// as one character or as "e" and a combining accent.
+    Nfc,
+    /// Compatibility composition, which also folds full-width
+    /// forms typed through an input method, like "ｙｅｓ", to
+    /// "yes".
+    Nfkc,
+}
+
+/// `text` in `normalization`, with diacritics removed when
+/// `fold_diacritics` is set.
+#[cfg(feature = "unicode")]
+pub(crate) fn normalize(
+    text: &str,
+    normalization: Option<Normalization>,
+    fold_diacritics: bool,
+) -> String {
+    let text: String = match normalization {
+        Some(Normalization::Nfc) | None => text.nfc().collect(),
+        Some(Normalization::Nfkc) => text.nfkc().collect(),
+    };
+    if fold_diacritics {
+        text.nfd()
+            .filter(|&c| !is_combining_mark(c))
+            .nfc()
+            .collect()
+    } else {
+        text
+    }
+}
+
+/// The number of columns `c` takes up on a terminal.
+#[cfg(feature = "unicode")]
+pub(crate) fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// The number of columns `c` takes up on a terminal: none for
+/// combining marks and zero width characters, two for the wide
+/// characters of East Asian scripts and emoji, and otherwise one.
+/// The `unicode` feature uses the full Unicode tables instead.
+#[cfg(not(feature = "unicode"))]
+pub(crate) fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl<R, W> Question<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Normalize responses, and the responses they are matched
+    /// against, before matching them. Without this only responses
+    /// identical up to case match.
+    ///
+    /// # Examples
+    ///
+    /// Accepting "ｙｅｓ", as typed with a Japanese input method,
+    /// as "yes".
+    ///
+    /// ```no_run
+    /// # use question::{Normalization, Question};
+    /// Question::new("Continue?")
+    ///     .normalize(Normalization::Nfkc)
+    ///     .confirm();
+    /// ```
+    pub fn normalize(&mut self, normalization: Normalization) -> &mut Question<R, W> {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Ignore diacritics when matching responses, so "Zurich"
+    /// matches "Zürich". Responses are normalized to `Nfc` unless
+    /// `normalize` chose otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use question::Question;
+    /// Question::new("City?")
+    ///     .acceptable(vec!["Zürich", "Genève"])
+    ///     .fold_diacritics()
+    ///     .until_acceptable()
+    ///     .ask();
+    /// ```
+    pub fn fold_diacritics(&mut self) -> &mut Question<R, W> {
+        self.fold_diacritics = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "unicode")]
+    use super::*;
+
+    #[test]
+    fn char_width() {
+        assert_eq!(1, super::char_width('a'));
+        assert_eq!(0, super::char_width('\u{301}'));
+        assert_eq!(2, super::char_width('は'));
+        assert_eq!(2, super::char_width('ｙ'));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn normalize() {
+        assert_eq!("é", super::normalize("e\u{301}", None, false));
+        assert_eq!(
+            "ｙes",
+            super::normalize("ｙes", Some(Normalization::Nfc), false)
+        );
+        assert_eq!(
+            "yes",
+            super::normalize("ｙｅｓ", Some(Normalization::Nfkc), false)
+        );
+        assert_eq!("Zurich", super::normalize("Zürich", None, true));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn matching() {
+        use std::io::Cursor;
+        use Answer;
+
+        let input = Cursor::new(String::from("ｙｅｓ\n").into_bytes());
+        let actual = Question::with_cursor("Continue?", input, Vec::new())
+            .normalize(Normalization::Nfkc)
+            .confirm();
+        assert_eq!(Answer::YES, actual);
+
+        let input = Cursor::new(String::from("zurich\n").into_bytes());
+        let actual = Question::with_cursor("City?", input, Vec::new())
+            .acceptable(vec!["Zürich", "Genève"])
+            .fold_diacritics()
+            .ask();
+        assert_eq!(Some(Answer::RESPONSE(String::from("Zürich"))), actual);
+
+        let input = Cursor::new(String::from("zurich\n").into_bytes());
+        let actual = Question::with_cursor("City?", input, Vec::new())
+            .acceptable(vec!["Zürich", "Genève"])
+            .ask();
+        assert_eq!(None, actual);
+    }
+}
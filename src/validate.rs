@@ -0,0 +1,171 @@
+//! Checks for common response formats, written by hand so no
+//! dependencies are needed.
+
+use std::fmt;
+
+/// A format a response must be in to be accepted, set with
+/// `Question::format`.
+#[non_exhaustive]
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Format {
+    /// An email address such as `jo@example.com`.
+    Email,
+
+    /// A hostname such as `db.example.com`.
+    Hostname,
+
+    /// An absolute URL with a host, such as
+    /// `https://example.com/docs`.
+    Url,
+}
+
+impl Format {
+    /// Whether `response` is in this format.
+    pub fn accepts(self, response: &str) -> bool {
+        match self {
+            Format::Email => is_email(response),
+            Format::Hostname => is_hostname(response),
+            Format::Url => is_url(response),
+        }
+    }
+}
+
+impl Format {
+    /// The id of the built in message naming this format.
+    pub(crate) fn message_id(self) -> &'static str {
+        match self {
+            Format::Email => "format-email",
+            Format::Hostname => "format-hostname",
+            Format::Url => "format-url",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Format::Email => write!(f, "email address"),
+            Format::Hostname => write!(f, "hostname"),
+            Format::Url => write!(f, "URL"),
+        }
+    }
+}
+
+fn is_hostname(s: &str) -> bool {
+    let s = s.strip_suffix('.').unwrap_or(s);
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+fn is_email(s: &str) -> bool {
+    let (local, domain) = match s.rfind('@') {
+        Some(at) => (&s[..at], &s[at + 1..]),
+        None => return false,
+    };
+    let special = "!#$%&'*+/=?^_`{|}~-";
+    !local.is_empty()
+        && local.len() <= 64
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || special.contains(c))
+        && domain.contains('.')
+        && is_hostname(domain)
+}
+
+fn is_url(s: &str) -> bool {
+    let (scheme, rest) = match s.find("://") {
+        Some(i) => (&s[..i], &s[i + 3..]),
+        None => return false,
+    };
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+    if !valid_scheme || s.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = match authority.rfind('@') {
+        Some(at) => &authority[at + 1..],
+        None => authority,
+    };
+    if let Some(ipv6) = host_port.strip_prefix('[') {
+        return match ipv6.find(']') {
+            Some(end) => valid_port(&ipv6[end + 1..]),
+            None => false,
+        };
+    }
+    let (host, port) = match host_port.find(':') {
+        Some(i) => (&host_port[..i], &host_port[i..]),
+        None => (host_port, ""),
+    };
+    is_hostname(host) && valid_port(port)
+}
+
+/// Whether `s` is empty or a colon followed by a port number.
+fn valid_port(s: &str) -> bool {
+    match s.strip_prefix(':') {
+        Some(port) => port.parse::<u16>().is_ok(),
+        None => s.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        macro_rules! formats {
+            ($format:expr, $accepted:expr, $rejected:expr) => {
+                for s in $accepted.iter() {
+                    assert!($format.accepts(s), "{} should be accepted", s);
+                }
+                for s in $rejected.iter() {
+                    assert!(!$format.accepts(s), "{} should be rejected", s);
+                }
+            };
+        }
+        formats!(
+            Format::Hostname,
+            ["localhost", "db-1.example.com", "example.com."],
+            ["", "-db.example.com", "a..b", "a_b.com", "ex ample.com"]
+        );
+        formats!(
+            Format::Email,
+            ["jo@example.com", "first.last+tag@mail.example.org"],
+            [
+                "jo",
+                "jo@localhost",
+                "@example.com",
+                "jo..x@example.com",
+                "j o@example.com"
+            ]
+        );
+        formats!(
+            Format::Url,
+            [
+                "https://example.com",
+                "http://user@example.com:8080/a?b#c",
+                "ftp://[::1]:21/",
+            ],
+            [
+                "example.com",
+                "https://",
+                "https://exa mple.com",
+                "1http://example.com",
+                "http://example.com:99999",
+            ]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Word wrapping questions to the width of the terminal.
+
+use editor::display_width;
+use unicode::char_width;
+
+/// How far lines after the first are indented.
+const INDENT: &str = "  ";
+
+/// Wrap `text` to `width` columns, breaking between words and
+/// indenting every line after the first. The last line keeps
+/// `reserved` columns free for whatever follows it. Words too
+/// long for a line are broken where the line ends.
+pub(crate) fn wrap(text: &str, width: usize, reserved: usize) -> String {
+    let indent = display_width(INDENT);
+    if width <= indent + 1 {
+        return text.to_string();
+    }
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut start = 0;
+        let mut line_width = 0;
+        for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+            if line_width > start && line_width + 1 + display_width(word) > width {
+                lines.push(line);
+                line = String::from(INDENT);
+                start = indent;
+                line_width = indent;
+            }
+            if line_width > start {
+                line.push(' ');
+                line_width += 1;
+            }
+            for c in word.chars() {
+                if line_width + char_width(c) > width {
+                    lines.push(line);
+                    line = String::from(INDENT);
+                    start = indent;
+                    line_width = indent;
+                }
+                line.push(c);
+                line_width += char_width(c);
+            }
+        }
+        if line_width + reserved > width {
+            if let Some(space) = line.rfind(' ').filter(|&i| i > start) {
+                let last = line.split_off(space);
+                lines.push(line);
+                line = format!("{}{}", INDENT, last.trim_start());
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_text() {
+        assert_eq!("Continue?", wrap("Continue?", 80, 6));
+        assert_eq!(
+            "Deploy the\n  release to\n  production?",
+            wrap("Deploy the release to production?", 13, 0)
+        );
+        assert_eq!(
+            "Deploy to\n  production?",
+            wrap("Deploy to production?", 22, 4)
+        );
+        assert_eq!("abcdefgh\n  ijklmn", wrap("abcdefghijklmn", 8, 0));
+        assert_eq!("First line\nSecond", wrap("First line\nSecond", 40, 0));
+        assert_eq!("日本語の\n  テキス\n  ト", wrap("日本語のテキスト", 8, 0));
+    }
+}